@@ -1,8 +1,10 @@
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
 use syn::{
     parenthesized,
     parse::{ParseBuffer, ParseStream},
-    Attribute, DeriveInput, Expr, ExprPath, GenericParam, LitStr, Token, WherePredicate,
+    punctuated::Punctuated,
+    token, Attribute, DeriveInput, Expr, ExprPath, ExprRange, GenericParam, LitStr, Token,
+    WherePredicate,
 };
 
 /// Attributes that are applied to fields.
@@ -11,25 +13,63 @@ pub struct FieldAttributesInfo {
     /// Whether the key corresponding to the field should be renamed to something different
     /// than the identifier of the field.
     pub rename: Option<LitStr>,
+    /// The other keys from which the field can also be deserialized.
+    pub aliases: Vec<LitStr>,
     /// The default value to deserialise to when the field is missing.
     pub default: Option<DefaultFieldAttribute>,
     /// The error to return when the field is missing and no default value exists.
     pub missing_field_error: Option<ExprPath>,
     /// The type of the error used to deserialize the field
     pub error: Option<syn::Type>,
-    /// The function to apply to the result after it has been deserialised successfully
-    pub map: Option<syn::ExprPath>,
+    /// The function or closure to apply to the result after it has been deserialised successfully
+    pub map: Option<Expr>,
     /// The function used to deserialize the whole type
     pub from: Option<AttributeFrom>,
     /// The function used to deserialize the whole type
     pub try_from: Option<AttributeTryFrom>,
+    /// The function converting the error of the `try_from` function to the error of the field
+    pub try_from_error: Option<ExprPath>,
     /// Whether an additional where clause should be added to deserialize this field
     pub needs_predicate: bool,
     /// Whether the field should be skipped
     pub skipped: bool,
+    /// Whether the field should be deserialized from the entries that no other field claimed
+    pub flatten: bool,
+    /// The range that the deserialized value must be contained in
+    pub range: Option<ExprRange>,
+    /// The minimum number of characters of the deserialized string
+    pub min_len: Option<Expr>,
+    /// The maximum number of characters of the deserialized string
+    pub max_len: Option<Expr>,
+    /// The regular expression that the deserialized string must match
+    pub pattern: Option<LitStr>,
+    /// Whether a warning should be reported when the field is given, with an optional message.
+    ///
+    /// It is `Some(None)` for `#[deserr(deprecated)]` and `Some(Some(msg))` for
+    /// `#[deserr(deprecated = "msg")]`.
+    pub deprecated: Option<Option<LitStr>>,
+    /// Whether an empty string should be deserialized like `null`
+    pub empty_string_as_none: bool,
+    /// Whether an explicit `null` should be handled like a missing field
+    pub null_is_none: bool,
+    /// Whether an explicit `null` should be deserialized as `Some(None)`, to distinguish it
+    /// from a missing field in an `Option<Option<T>>`
+    pub double_option: bool,
+    /// Whether an explicit `null` should be refused in an `Option<T>`, whose missing field
+    /// is `None`
+    pub deny_null: bool,
+    /// The field whose value is given to this field when it is missing
+    pub default_from: Option<syn::Member>,
+    /// Whether the duplicate elements of the deserialized `Vec` should be removed
+    pub dedup: bool,
+    /// Whether the integers out of the range of the deserialized type should be clamped to
+    /// its bounds instead of being an error
+    pub saturating: bool,
 
     /// Span of the `default` attribute, if any, for compile error reporting purposes
     default_span: Option<Span>,
+    /// Span of the `flatten` attribute, if any, for compile error reporting purposes
+    flatten_span: Option<Span>,
 }
 
 /// The value of the `default` field attribute
@@ -69,6 +109,7 @@ impl FieldAttributesInfo {
             }
             self.rename = Some(rename)
         }
+        self.aliases.extend(other.aliases);
         if let Some(default) = other.default {
             if let Some(self_default_span) = &self.default_span {
                 return Err(syn::Error::new(
@@ -78,6 +119,15 @@ impl FieldAttributesInfo {
             }
             self.default = Some(default)
         }
+        if let Some(default_from) = other.default_from {
+            if let Some(self_default_from) = &self.default_from {
+                return Err(syn::Error::new_spanned(
+                    self_default_from,
+                    "The `default_from` field attribute is defined twice.",
+                ));
+            }
+            self.default_from = Some(default_from)
+        }
         if let Some(missing_field_error) = other.missing_field_error {
             if let Some(self_missing_field_error) = &self.missing_field_error {
                 return Err(syn::Error::new_spanned(
@@ -133,11 +183,171 @@ impl FieldAttributesInfo {
             }
             self.try_from = Some(try_from)
         }
+        if let Some(try_from_error) = other.try_from_error {
+            if let Some(self_try_from_error) = &self.try_from_error {
+                return Err(syn::Error::new_spanned(
+                    self_try_from_error,
+                    "The `try_from_error` field attribute is defined twice.",
+                ));
+            }
+            self.try_from_error = Some(try_from_error)
+        }
+        if let Some(range) = other.range {
+            if let Some(self_range) = &self.range {
+                return Err(syn::Error::new_spanned(
+                    self_range,
+                    "The `range` field attribute is defined twice.",
+                ));
+            }
+            self.range = Some(range)
+        }
+        if let Some(min_len) = other.min_len {
+            if let Some(self_min_len) = &self.min_len {
+                return Err(syn::Error::new_spanned(
+                    self_min_len,
+                    "The `min_len` field attribute is defined twice.",
+                ));
+            }
+            self.min_len = Some(min_len)
+        }
+        if let Some(max_len) = other.max_len {
+            if let Some(self_max_len) = &self.max_len {
+                return Err(syn::Error::new_spanned(
+                    self_max_len,
+                    "The `max_len` field attribute is defined twice.",
+                ));
+            }
+            self.max_len = Some(max_len)
+        }
+        if let Some(pattern) = other.pattern {
+            if let Some(self_pattern) = &self.pattern {
+                return Err(syn::Error::new_spanned(
+                    self_pattern,
+                    "The `pattern` field attribute is defined twice.",
+                ));
+            }
+            self.pattern = Some(pattern)
+        }
+        if let Some(deprecated) = other.deprecated {
+            if self.deprecated.is_some() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "The `deprecated` field attribute is defined twice.",
+                ));
+            }
+            self.deprecated = Some(deprecated)
+        }
         self.needs_predicate |= other.needs_predicate;
         self.skipped |= other.skipped;
+        self.empty_string_as_none |= other.empty_string_as_none;
+        self.null_is_none |= other.null_is_none;
+        self.double_option |= other.double_option;
+        self.deny_null |= other.deny_null;
+        self.dedup |= other.dedup;
+        self.saturating |= other.saturating;
+        if other.flatten {
+            self.flatten = true;
+            self.flatten_span = other.flatten_span;
+        }
 
         Ok(())
     }
+
+    /// Return an error if the `flatten` attribute is used together with an attribute
+    /// that only makes sense for a field that has its own key, if the `try_from_error`
+    /// attribute is used without the `try_from` attribute, if the `default_from` attribute is
+    /// used together with an attribute that converts or maps the value, or if the `double_option`,
+    /// `null_is_none` and `deny_null` attributes, which handle `null` differently, are used
+    /// together.
+    fn validate(&self) -> Result<(), syn::Error> {
+        if let (Some(try_from_error), None) = (&self.try_from_error, &self.try_from) {
+            return Err(syn::Error::new_spanned(
+                try_from_error,
+                "The `try_from_error` attribute can only be used together with the `try_from` attribute",
+            ));
+        }
+        if let (Some(default_from), Some(_)) = (&self.default_from, &self.default) {
+            return Err(syn::Error::new_spanned(
+                default_from,
+                "Cannot use the `default_from` attribute together with the `default` attribute",
+            ));
+        }
+        if let Some(default_from) = &self.default_from {
+            // the value of the other field is already converted and mapped
+            for (attribute, used) in [
+                ("from", self.from.is_some()),
+                ("try_from", self.try_from.is_some()),
+                ("map", self.map.is_some()),
+                ("dedup", self.dedup),
+            ] {
+                if used {
+                    return Err(syn::Error::new_spanned(
+                        default_from,
+                        format!(
+                            "Cannot use the `default_from` attribute together with the `{attribute}` attribute"
+                        ),
+                    ));
+                }
+            }
+        }
+        if self.double_option && self.null_is_none {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Cannot use the `double_option` attribute together with the `null_is_none` attribute",
+            ));
+        }
+        if self.deny_null {
+            for (attribute, used) in [
+                ("null_is_none", self.null_is_none),
+                ("double_option", self.double_option),
+                ("saturating", self.saturating),
+            ] {
+                if used {
+                    return Err(syn::Error::new(
+                        Span::call_site(),
+                        format!(
+                            "Cannot use the `deny_null` attribute together with the `{attribute}` attribute"
+                        ),
+                    ));
+                }
+            }
+        }
+        let Some(flatten_span) = self.flatten_span else {
+            return Ok(());
+        };
+        let incompatible = [
+            ("rename", self.rename.is_some()),
+            ("alias", !self.aliases.is_empty()),
+            ("default", self.default.is_some()),
+            ("default_from", self.default_from.is_some()),
+            ("missing_field_error", self.missing_field_error.is_some()),
+            ("from", self.from.is_some()),
+            ("try_from", self.try_from.is_some()),
+            ("skip", self.skipped),
+            ("range", self.range.is_some()),
+            ("min_len", self.min_len.is_some()),
+            ("max_len", self.max_len.is_some()),
+            ("pattern", self.pattern.is_some()),
+            ("deprecated", self.deprecated.is_some()),
+            ("empty_string_as_none", self.empty_string_as_none),
+            ("null_is_none", self.null_is_none),
+            ("double_option", self.double_option),
+            ("deny_null", self.deny_null),
+            ("dedup", self.dedup),
+            ("saturating", self.saturating),
+        ];
+        for (attribute, used) in incompatible {
+            if used {
+                return Err(syn::Error::new(
+                    flatten_span,
+                    format!(
+                        "Cannot use the `{attribute}` attribute together with the `flatten` attribute"
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 fn parse_rename(input: &ParseBuffer) -> Result<LitStr, syn::Error> {
     let _eq = input.parse::<Token![=]>()?;
@@ -160,6 +370,11 @@ impl syn::parse::Parse for FieldAttributesInfo {
                 "rename" => {
                     other.rename = Some(parse_rename(input)?);
                 }
+                "alias" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    // #[deserr( ... alias = "lit" )]
+                    other.aliases.push(input.parse::<LitStr>()?);
+                }
                 "default" => {
                     if input.peek(Token![=]) {
                         let _eq = input.parse::<Token![=]>()?;
@@ -171,6 +386,11 @@ impl syn::parse::Parse for FieldAttributesInfo {
                     }
                     other.default_span = Some(attr_name.span());
                 }
+                "default_from" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    // #[deserr( ... default_from = field )]
+                    other.default_from = Some(input.parse::<syn::Member>()?);
+                }
                 "missing_field_error" => {
                     let _eq = input.parse::<Token![=]>()?;
                     let func = input.parse::<ExprPath>()?;
@@ -186,8 +406,8 @@ impl syn::parse::Parse for FieldAttributesInfo {
                 }
                 "map" => {
                     let _eq = input.parse::<Token![=]>()?;
-                    let func = input.parse::<syn::ExprPath>()?;
-                    // #[deserr( ... map = func )]
+                    let func = input.parse::<Expr>()?;
+                    // #[deserr( ... map = func )] or #[deserr( ... map = |x| ... )]
                     other.map = Some(func);
                 }
                 "from" => {
@@ -200,9 +420,82 @@ impl syn::parse::Parse for FieldAttributesInfo {
                     // #[deserr( .. try_from(from_ty) = function::path::<_> -> to_ty )]
                     other.try_from = Some(try_from_attr);
                 }
+                "try_from_error" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    let func = input.parse::<ExprPath>()?;
+                    // #[deserr( ... try_from_error = func )]
+                    other.try_from_error = Some(func);
+                }
                 "skip" => {
                     other.skipped = true;
                 }
+                "empty_string_as_none" => {
+                    other.empty_string_as_none = true;
+                }
+                "null_is_none" => {
+                    other.null_is_none = true;
+                }
+                "double_option" => {
+                    other.double_option = true;
+                }
+                "deny_null" => {
+                    other.deny_null = true;
+                }
+                "dedup" => {
+                    other.dedup = true;
+                }
+                "saturating" => {
+                    other.saturating = true;
+                }
+                "deprecated" => {
+                    if input.peek(Token![=]) {
+                        let _eq = input.parse::<Token![=]>()?;
+                        // #[deserr( ... deprecated = "lit" )]
+                        other.deprecated = Some(Some(input.parse::<LitStr>()?));
+                    } else {
+                        other.deprecated = Some(None);
+                    }
+                }
+                "flatten" => {
+                    other.flatten = true;
+                    other.flatten_span = Some(attr_name.span());
+                }
+                "min_len" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    // #[deserr( ... min_len = expr )]
+                    other.min_len = Some(input.parse::<Expr>()?);
+                }
+                "max_len" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    // #[deserr( ... max_len = expr )]
+                    other.max_len = Some(input.parse::<Expr>()?);
+                }
+                "pattern" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    // #[deserr( ... pattern = "regex" )]
+                    let pattern = input.parse::<LitStr>()?;
+                    // reject the invalid regexes at compile time rather than when deserializing
+                    if let Err(e) = regex_syntax::Parser::new().parse(&pattern.value()) {
+                        return Result::Err(syn::Error::new_spanned(
+                            pattern,
+                            format!("Invalid `pattern` regex: {e}"),
+                        ));
+                    }
+                    other.pattern = Some(pattern);
+                }
+                "range" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    // #[deserr( ... range = start..=end )]
+                    match input.parse::<Expr>()? {
+                        Expr::Range(range) => other.range = Some(range),
+                        expr => {
+                            return Result::Err(syn::Error::new_spanned(
+                                expr,
+                                "The `range` attribute expects a range, such as `1..=65535`",
+                            ))
+                        }
+                    }
+                }
                 _ => {
                     let message = format!("Unknown deserr field attribute: {}", attr_name);
                     return Result::Err(syn::Error::new_spanned(attr_name, message));
@@ -243,33 +536,47 @@ pub fn read_deserr_field_attributes(
             continue;
         }
     }
+    this.validate()?;
     Ok(this)
 }
 
-/// The value of the `default` field attribute
+/// The value of the `rename_all` attribute
+///
+/// The casings that are valid identifiers can be written with or without quotes,
+/// e.g. `#[deserr(rename_all = camelCase)]` or `#[deserr(rename_all = "camelCase")]`.
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum RenameAll {
     /// `#[deserr(rename_all = camelCase)]`
     CamelCase,
     /// `#[deserr(rename_all = lowercase)]`
     LowerCase,
+    /// `#[deserr(rename_all = UPPERCASE)]`
+    UpperCase,
+    /// `#[deserr(rename_all = PascalCase)]`
+    PascalCase,
+    /// `#[deserr(rename_all = snake_case)]`
+    SnakeCase,
+    /// `#[deserr(rename_all = SCREAMING_SNAKE_CASE)]`
+    ScreamingSnakeCase,
+    /// `#[deserr(rename_all = "kebab-case")]`
+    KebabCase,
+    /// `#[deserr(rename_all = "SCREAMING-KEBAB-CASE")]`
+    ScreamingKebabCase,
 }
 
 /// The value of the `tag` field attribute
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum TagType {
     /// `#[deserr(tag = "somestring")]`
     Internal(String),
+    /// `#[deserr(untagged)]`
+    Untagged,
     /// An external tag is the default value, when there is no `tag` attribute.
+    #[default]
     External,
 }
 
-impl Default for TagType {
-    fn default() -> Self {
-        Self::External
-    }
-}
-
 /// The value of the `deny_unknown_fields` field attribute
 #[derive(Debug, Clone)]
 pub enum DenyUnknownFields {
@@ -296,13 +603,15 @@ pub struct AttributeTryFrom {
 pub struct AttributeFrom {
     pub is_ref: bool,
     pub from_ty: syn::Type,
-    pub function: ExprPath,
+    /// The path of a function, or a closure
+    pub function: Expr,
     span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct FunctionReturningError {
-    pub function: ExprPath,
+    /// The path of a function, or a closure for the `try_from` attribute
+    pub function: Expr,
     pub error_ty: syn::Type,
 }
 
@@ -312,8 +621,12 @@ pub struct FunctionReturningError {
 #[derive(Default, Debug, Clone)]
 pub struct ContainerAttributesInfo {
     pub rename_all: Option<RenameAll>,
+    /// The casing under which the fields are also accepted, in addition to their key
+    pub alias_all: Option<RenameAll>,
     pub err_ty: Option<syn::Type>,
     pub tag: TagType,
+    /// The key containing the content of the variants of an adjacently tagged enum
+    pub content: Option<String>,
     pub deny_unknown_fields: Option<DenyUnknownFields>,
 
     pub generic_params: Vec<GenericParam>,
@@ -323,13 +636,49 @@ pub struct ContainerAttributesInfo {
     pub from: Option<AttributeFrom>,
     /// The function used to deserialize the whole container
     pub try_from: Option<AttributeTryFrom>,
+    /// The function converting the error of the `try_from` function to the error of the container
+    pub try_from_error: Option<ExprPath>,
 
     /// A function to call on the deserialized value to validate it
     pub validate: Option<FunctionReturningError>,
+    /// Whether to generate a companion struct whose fields are all optional, to apply
+    /// partial updates onto the container
+    pub partial: bool,
+    /// The traits to derive on the companion struct of the `partial` attribute
+    pub partial_derives: Vec<syn::Path>,
+    /// Whether to deserialize the single field of the struct in place of the struct
+    pub transparent: bool,
+    /// The value given to the fields of this type when they are missing
+    pub default: Option<DefaultFieldAttribute>,
+    /// The value of the struct in which its missing fields take their value
+    pub fill_from_default: Option<DefaultFieldAttribute>,
+    /// Whether the variants of the enum are matched while ignoring the ASCII case
+    pub case_insensitive: bool,
+    /// Whether the struct is deserialized from a sequence, whose elements are given to the
+    /// fields in their declaration order
+    pub from_sequence: bool,
+    /// Whether to implement `TryFrom<serde_json::Value>` for the container
+    pub try_from_json: bool,
+    /// Whether to implement `KnownFields` for the container
+    pub known_fields: bool,
+    /// Whether the missing required fields take their default value when the container is
+    /// deserialized with `MissingFieldPolicy::Default`
+    pub missing_field_policy: bool,
 
     validate_span: Option<Span>,
+    partial_span: Option<Span>,
+    transparent_span: Option<Span>,
+    default_span: Option<Span>,
+    fill_from_default_span: Option<Span>,
+    case_insensitive_span: Option<Span>,
+    from_sequence_span: Option<Span>,
+    try_from_json_span: Option<Span>,
+    known_fields_span: Option<Span>,
+    missing_field_policy_span: Option<Span>,
     rename_all_span: Option<Span>,
+    alias_all_span: Option<Span>,
     tag_span: Option<Span>,
+    content_span: Option<Span>,
     deny_unknown_fields_span: Option<Span>,
 }
 
@@ -357,6 +706,16 @@ impl ContainerAttributesInfo {
             }
             self.rename_all = Some(rename_all)
         }
+        if let Some(alias_all) = other.alias_all {
+            if let Some(self_alias_all_span) = self.alias_all_span {
+                return Err(syn::Error::new(
+                    self_alias_all_span,
+                    "The `alias_all` attribute is defined twice.",
+                ));
+            }
+            self.alias_all = Some(alias_all);
+            self.alias_all_span = other.alias_all_span;
+        }
         if let Some(err_ty) = other.err_ty {
             if let Some(self_err_ty) = &self.err_ty {
                 return Err(syn::Error::new_spanned(
@@ -366,14 +725,25 @@ impl ContainerAttributesInfo {
             }
             self.err_ty = Some(err_ty)
         }
-        if let TagType::Internal(x) = other.tag {
+        if !matches!(other.tag, TagType::External) {
             if let Some(self_tag_span) = self.tag_span {
                 return Err(syn::Error::new(
                     self_tag_span,
-                    "The `tag` attribute is defined twice.",
+                    duplicate_tag_message(&self.tag, &other.tag),
+                ));
+            }
+            self.tag = other.tag;
+            self.tag_span = other.tag_span;
+        }
+        if let Some(content) = other.content {
+            if let Some(self_content_span) = self.content_span {
+                return Err(syn::Error::new(
+                    self_content_span,
+                    "The `content` attribute is defined twice.",
                 ));
             }
-            self.tag = TagType::Internal(x)
+            self.content = Some(content);
+            self.content_span = other.content_span;
         }
         if let Some(x) = other.deny_unknown_fields {
             if let Some(self_deny_unknown_fields_span) = &self.deny_unknown_fields_span {
@@ -412,6 +782,15 @@ impl ContainerAttributesInfo {
             }
             self.try_from = Some(x);
         }
+        if let Some(x) = other.try_from_error {
+            if let Some(self_try_from_error) = &self.try_from_error {
+                return Err(syn::Error::new_spanned(
+                    self_try_from_error,
+                    "The `try_from_error` attribute is defined twice.",
+                ));
+            }
+            self.try_from_error = Some(x);
+        }
         if let Some(x) = other.validate {
             if let Some(self_validate_span) = &self.validate_span {
                 return Err(syn::Error::new(
@@ -421,6 +800,97 @@ impl ContainerAttributesInfo {
             }
             self.validate = Some(x);
         }
+        if other.partial {
+            if let Some(self_partial_span) = self.partial_span {
+                return Err(syn::Error::new(
+                    self_partial_span,
+                    "The `partial` attribute is defined twice.",
+                ));
+            }
+            self.partial = true;
+            self.partial_derives = other.partial_derives;
+            self.partial_span = other.partial_span;
+        }
+        if other.transparent {
+            if let Some(self_transparent_span) = self.transparent_span {
+                return Err(syn::Error::new(
+                    self_transparent_span,
+                    "The `transparent` attribute is defined twice.",
+                ));
+            }
+            self.transparent = true;
+            self.transparent_span = other.transparent_span;
+        }
+        if let Some(default) = other.default {
+            if let Some(self_default_span) = self.default_span {
+                return Err(syn::Error::new(
+                    self_default_span,
+                    "The `default` attribute is defined twice.",
+                ));
+            }
+            self.default = Some(default);
+            self.default_span = other.default_span;
+        }
+        if let Some(fill_from_default) = other.fill_from_default {
+            if let Some(self_fill_from_default_span) = self.fill_from_default_span {
+                return Err(syn::Error::new(
+                    self_fill_from_default_span,
+                    "The `fill_from_default` attribute is defined twice.",
+                ));
+            }
+            self.fill_from_default = Some(fill_from_default);
+            self.fill_from_default_span = other.fill_from_default_span;
+        }
+        if other.case_insensitive {
+            if let Some(self_case_insensitive_span) = self.case_insensitive_span {
+                return Err(syn::Error::new(
+                    self_case_insensitive_span,
+                    "The `case_insensitive` attribute is defined twice.",
+                ));
+            }
+            self.case_insensitive = true;
+            self.case_insensitive_span = other.case_insensitive_span;
+        }
+        if other.from_sequence {
+            if let Some(self_from_sequence_span) = self.from_sequence_span {
+                return Err(syn::Error::new(
+                    self_from_sequence_span,
+                    "The `from_sequence` attribute is defined twice.",
+                ));
+            }
+            self.from_sequence = true;
+            self.from_sequence_span = other.from_sequence_span;
+        }
+        if other.try_from_json {
+            if let Some(self_try_from_json_span) = self.try_from_json_span {
+                return Err(syn::Error::new(
+                    self_try_from_json_span,
+                    "The `try_from_json` attribute is defined twice.",
+                ));
+            }
+            self.try_from_json = true;
+            self.try_from_json_span = other.try_from_json_span;
+        }
+        if other.known_fields {
+            if let Some(self_known_fields_span) = self.known_fields_span {
+                return Err(syn::Error::new(
+                    self_known_fields_span,
+                    "The `known_fields` attribute is defined twice.",
+                ));
+            }
+            self.known_fields = true;
+            self.known_fields_span = other.known_fields_span;
+        }
+        if other.missing_field_policy {
+            if let Some(self_missing_field_policy_span) = self.missing_field_policy_span {
+                return Err(syn::Error::new(
+                    self_missing_field_policy_span,
+                    "The `missing_field_policy` attribute is defined twice.",
+                ));
+            }
+            self.missing_field_policy = true;
+            self.missing_field_policy_span = other.missing_field_policy_span;
+        }
 
         self.generic_params.extend(other.generic_params);
         self.where_predicates.extend(other.where_predicates);
@@ -436,17 +906,42 @@ impl ContainerAttributesInfo {
         self.rename_all = other.rename_all.clone();
     }
 }
-fn parse_rename_all(input: &ParseBuffer) -> Result<RenameAll, syn::Error> {
+/// The error message for a container with two `tag` or `untagged` attributes.
+fn duplicate_tag_message(first: &TagType, second: &TagType) -> &'static str {
+    match (first, second) {
+        (TagType::Internal(_), TagType::Internal(_)) => "The `tag` attribute is defined twice.",
+        (TagType::Untagged, TagType::Untagged) => "The `untagged` attribute is defined twice.",
+        _ => "The `tag` and `untagged` attributes can't be used together.",
+    }
+}
+
+/// Parse the casing given to the `rename_all` or `alias_all` attribute, whose name is `attr_name`.
+fn parse_rename_all(input: &ParseBuffer, attr_name: &str) -> Result<RenameAll, syn::Error> {
     let _eq = input.parse::<Token![=]>()?;
-    let ident = input.parse::<Ident>()?;
-    // #[deserr( ... rename_all = ident )]
-    let rename_all = match ident.to_string().as_str() {
+    // #[deserr( ... rename_all = ident )] or #[deserr( ... rename_all = "lit" )]
+    let (casing, span) = if input.peek(LitStr) {
+        let lit = input.parse::<LitStr>()?;
+        (lit.value(), lit.span())
+    } else {
+        let ident = input.parse::<Ident>()?;
+        (ident.to_string(), ident.span())
+    };
+    let rename_all = match casing.as_str() {
         "camelCase" => RenameAll::CamelCase,
         "lowercase" => RenameAll::LowerCase,
+        "UPPERCASE" => RenameAll::UpperCase,
+        "PascalCase" => RenameAll::PascalCase,
+        "snake_case" => RenameAll::SnakeCase,
+        "SCREAMING_SNAKE_CASE" => RenameAll::ScreamingSnakeCase,
+        "kebab-case" => RenameAll::KebabCase,
+        "SCREAMING-KEBAB-CASE" => RenameAll::ScreamingKebabCase,
         _ => {
-            return Result::Err(syn::Error::new_spanned(
-                ident,
-                "rename_all can either be equal to `camelCase` or `lowercase`",
+            return Result::Err(syn::Error::new(
+                span,
+                format!(
+                    "{attr_name} can be equal to `camelCase`, `lowercase`, `UPPERCASE`, `PascalCase`, `snake_case`, \
+                     `SCREAMING_SNAKE_CASE`, `\"kebab-case\"`, or `\"SCREAMING-KEBAB-CASE\"`"
+                ),
             ));
         }
     };
@@ -456,13 +951,54 @@ fn parse_rename_all(input: &ParseBuffer) -> Result<RenameAll, syn::Error> {
 fn parse_function_returning_error(
     input: &ParseBuffer,
 ) -> Result<FunctionReturningError, syn::Error> {
-    let function = input.parse::<ExprPath>()?;
+    let function = Expr::Path(input.parse::<ExprPath>()?);
     // #[deserr( .. from(from_ty) = function::path::<_> )]
     let _arrow = input.parse::<Token![->]>()?;
     let error_ty = input.parse::<syn::Type>()?;
     Ok(FunctionReturningError { function, error_ty })
 }
 
+/// Parse the function of the `from` and `try_from` attributes, which is either a path
+/// such as `String::parse::<usize>`, or a closure such as `|s| s.trim().parse()`.
+///
+/// The closure of a `try_from` attribute is followed by `-> error_ty`, which syn would
+/// parse as a subtraction in the body of the closure. So only the tokens before the first
+/// `->` that isn't the return type of the closure are parsed as the closure.
+fn parse_function_or_closure(input: &ParseBuffer) -> Result<Expr, syn::Error> {
+    if !(input.peek(Token![|]) || input.peek(Token![||]) || input.peek(Token![move])) {
+        return Ok(Expr::Path(input.parse()?));
+    }
+    let closure = input.step(|cursor| {
+        let mut rest = *cursor;
+        let mut closure = TokenStream::new();
+        // the parameters of the closure end with the second `|`
+        let mut pipes = 0;
+        let mut after_params = false;
+        while let Some((token, next)) = rest.token_tree() {
+            if let TokenTree::Punct(punct) = &token {
+                let is_arrow = punct.as_char() == '-'
+                    && punct.spacing() == Spacing::Joint
+                    && matches!(next.punct(), Some((gt, _)) if gt.as_char() == '>');
+                if is_arrow && !after_params {
+                    break;
+                }
+                if punct.as_char() == '|' && pipes < 2 {
+                    pipes += 1;
+                    after_params = pipes == 2;
+                } else {
+                    after_params = false;
+                }
+            } else {
+                after_params = false;
+            }
+            closure.extend([token]);
+            rest = next;
+        }
+        Ok((closure, rest))
+    })?;
+    Ok(Expr::Closure(syn::parse2(closure)?))
+}
+
 fn parse_attribute_from(span: Span, input: &ParseBuffer) -> Result<AttributeFrom, syn::Error> {
     let content;
     let _ = parenthesized!(content in input);
@@ -473,7 +1009,7 @@ fn parse_attribute_from(span: Span, input: &ParseBuffer) -> Result<AttributeFrom
     // #[deserr( .. from(from_ty) ..)]
     let _eq = input.parse::<Token![=]>()?;
     // #[deserr( .. from(from_ty) = ..)]
-    let function = input.parse::<ExprPath>()?;
+    let function = parse_function_or_closure(input)?;
 
     Ok(AttributeFrom {
         is_ref,
@@ -496,7 +1032,12 @@ fn parse_attribute_try_from(
     // #[deserr( .. try_from(from_ty) ..)]
     let _eq = input.parse::<Token![=]>()?;
     // #[deserr( .. try_from(from_ty) = ..)]
-    let function = parse_function_returning_error(input)?;
+    let function = parse_function_or_closure(input)?;
+    // #[deserr( .. try_from(from_ty) = function::path::<_> ..)]
+    let _arrow = input.parse::<Token![->]>()?;
+    let error_ty = input.parse::<syn::Type>()?;
+    // #[deserr( .. try_from(from_ty) = function::path::<_> -> error_ty)]
+    let function = FunctionReturningError { function, error_ty };
 
     Ok(AttributeTryFrom {
         is_ref,
@@ -506,6 +1047,24 @@ fn parse_attribute_try_from(
     })
 }
 
+fn parse_partial_derives(input: &ParseBuffer) -> Result<Vec<syn::Path>, syn::Error> {
+    let content;
+    let _ = parenthesized!(content in input);
+    // #[deserr( .. partial(..) ..)]
+    let derive = content.parse::<Ident>()?;
+    if derive != "derive" {
+        return Err(syn::Error::new_spanned(
+            derive,
+            "Expected `derive(..)`, e.g. `partial(derive(Debug, Clone))`",
+        ));
+    }
+    let traits;
+    let _ = parenthesized!(traits in content);
+    // #[deserr( .. partial(derive(Trait, other::Trait)) ..)]
+    let traits = Punctuated::<syn::Path, Token![,]>::parse_terminated(&traits)?;
+    Ok(traits.into_iter().collect())
+}
+
 impl syn::parse::Parse for ContainerAttributesInfo {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut this = ContainerAttributesInfo::default();
@@ -517,17 +1076,46 @@ impl syn::parse::Parse for ContainerAttributesInfo {
             // consumed input: #[deserr( ... attr_name ... )]
             match attr_name.to_string().as_str() {
                 "rename_all" => {
-                    let rename_all = parse_rename_all(input)?;
+                    let rename_all = parse_rename_all(input, "rename_all")?;
                     this.rename_all = Some(rename_all);
                     this.rename_all_span = Some(attr_name.span());
                 }
+                "alias_all" => {
+                    let alias_all = parse_rename_all(input, "alias_all")?;
+                    this.alias_all = Some(alias_all);
+                    this.alias_all_span = Some(attr_name.span());
+                }
                 "tag" => {
                     let _eq = input.parse::<Token![=]>()?;
                     let lit = input.parse::<LitStr>()?;
                     // #[deserr( ... tag = "lit" )]
+                    if this.tag_span.is_some() {
+                        return Err(syn::Error::new(
+                            attr_name.span(),
+                            duplicate_tag_message(&this.tag, &TagType::Internal(lit.value())),
+                        ));
+                    }
                     this.tag = TagType::Internal(lit.value());
                     this.tag_span = Some(attr_name.span());
                 }
+                "content" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    let lit = input.parse::<LitStr>()?;
+                    // #[deserr( ... content = "lit" )]
+                    this.content = Some(lit.value());
+                    this.content_span = Some(attr_name.span());
+                }
+                "untagged" => {
+                    // #[deserr( ... untagged )]
+                    if this.tag_span.is_some() {
+                        return Err(syn::Error::new(
+                            attr_name.span(),
+                            duplicate_tag_message(&this.tag, &TagType::Untagged),
+                        ));
+                    }
+                    this.tag = TagType::Untagged;
+                    this.tag_span = Some(attr_name.span());
+                }
                 "error" => {
                     let _eq = input.parse::<Token![=]>()?;
                     let err_ty = input.parse::<syn::Type>()?;
@@ -555,6 +1143,12 @@ impl syn::parse::Parse for ContainerAttributesInfo {
                     // #[deserr( .. try_from(from_ty) = function::path::<_> -> to_ty )]
                     this.try_from = Some(try_from_attr);
                 }
+                "try_from_error" => {
+                    let _eq = input.parse::<Token![=]>()?;
+                    let func = input.parse::<ExprPath>()?;
+                    // #[deserr( ... try_from_error = func )]
+                    this.try_from_error = Some(func);
+                }
                 "validate" => {
                     // #[deserr( ... validate .. )]
                     let _eq = input.parse::<Token![=]>()?;
@@ -563,6 +1157,68 @@ impl syn::parse::Parse for ContainerAttributesInfo {
                     // #[deserr( ... validate = some::func<T> )]
                     this.validate = Some(validate_func);
                 }
+                "partial" => {
+                    // #[deserr( ... partial )]
+                    this.partial = true;
+                    this.partial_span = Some(attr_name.span());
+                    if input.peek(token::Paren) {
+                        this.partial_derives = parse_partial_derives(input)?;
+                    }
+                }
+                "transparent" => {
+                    // #[deserr( ... transparent )]
+                    this.transparent = true;
+                    this.transparent_span = Some(attr_name.span());
+                }
+                "case_insensitive" => {
+                    // #[deserr( ... case_insensitive )]
+                    this.case_insensitive = true;
+                    this.case_insensitive_span = Some(attr_name.span());
+                }
+                "from_sequence" => {
+                    // #[deserr( ... from_sequence )]
+                    this.from_sequence = true;
+                    this.from_sequence_span = Some(attr_name.span());
+                }
+                "try_from_json" => {
+                    // #[deserr( ... try_from_json )]
+                    this.try_from_json = true;
+                    this.try_from_json_span = Some(attr_name.span());
+                }
+                "known_fields" => {
+                    // #[deserr( ... known_fields )]
+                    this.known_fields = true;
+                    this.known_fields_span = Some(attr_name.span());
+                }
+                "missing_field_policy" => {
+                    // #[deserr( ... missing_field_policy )]
+                    this.missing_field_policy = true;
+                    this.missing_field_policy_span = Some(attr_name.span());
+                }
+                "default" => {
+                    if input.peek(Token![=]) {
+                        let _eq = input.parse::<Token![=]>()?;
+                        let expr = input.parse::<Expr>()?;
+                        // #[deserr( ... default = expr )]
+                        this.default = Some(DefaultFieldAttribute::Function(expr));
+                    } else {
+                        // #[deserr( ... default )]
+                        this.default = Some(DefaultFieldAttribute::DefaultTrait);
+                    }
+                    this.default_span = Some(attr_name.span());
+                }
+                "fill_from_default" => {
+                    if input.peek(Token![=]) {
+                        let _eq = input.parse::<Token![=]>()?;
+                        let expr = input.parse::<Expr>()?;
+                        // #[deserr( ... fill_from_default = expr )]
+                        this.fill_from_default = Some(DefaultFieldAttribute::Function(expr));
+                    } else {
+                        // #[deserr( ... fill_from_default )]
+                        this.fill_from_default = Some(DefaultFieldAttribute::DefaultTrait);
+                    }
+                    this.fill_from_default_span = Some(attr_name.span());
+                }
                 "generic_param" => {
                     let _eq = input.parse::<Token![=]>()?;
                     let param = input.parse::<GenericParam>()?;
@@ -609,12 +1265,21 @@ pub fn validate_container_attributes(
                 "Cannot use the `rename_all` attribute together with the `try_from` attribute",
             ));
         }
-        if let Some(tag) = attributes.tag_span {
+        if let Some(alias_all_span) = attributes.alias_all_span {
             return Err(syn::Error::new(
-                tag,
-                "Cannot use the `tag` attribute together with the `try_from` attribute",
+                alias_all_span,
+                "Cannot use the `alias_all` attribute together with the `try_from` attribute",
             ));
         }
+        if let Some(tag) = attributes.tag_span {
+            let message = match attributes.tag {
+                TagType::Untagged => {
+                    "Cannot use the `untagged` attribute together with the `try_from` attribute"
+                }
+                _ => "Cannot use the `tag` attribute together with the `try_from` attribute",
+            };
+            return Err(syn::Error::new(tag, message));
+        }
         if let Some(span) = attributes.deny_unknown_fields_span {
             return Err(syn::Error::new(
                 span,
@@ -622,13 +1287,175 @@ pub fn validate_container_attributes(
             ));
         }
     }
+    if let (Some(try_from_error), None) = (&attributes.try_from_error, &attributes.try_from) {
+        return Err(syn::Error::new_spanned(
+            try_from_error,
+            "The `try_from_error` attribute can only be used together with the `try_from` attribute",
+        ));
+    }
+    if let Some(case_insensitive) = attributes.case_insensitive_span {
+        if !matches!(container.data, syn::Data::Enum(..)) {
+            return Err(syn::Error::new(
+                case_insensitive,
+                "The `case_insensitive` attribute can only be used on enums",
+            ));
+        }
+        if matches!(attributes.tag, TagType::Untagged) {
+            return Err(syn::Error::new(
+                case_insensitive,
+                "Cannot use the `case_insensitive` attribute together with the `untagged` attribute",
+            ));
+        }
+    }
+    if let Some(content) = attributes.content_span {
+        if !matches!(attributes.tag, TagType::Internal(_)) {
+            return Err(syn::Error::new(
+                content,
+                "The `content` attribute can only be used together with the `tag` attribute",
+            ));
+        }
+    }
     if matches!(container.data, syn::Data::Struct(..)) {
         if let Some(tag) = attributes.tag_span {
+            let message = match attributes.tag {
+                TagType::Untagged => "Cannot use the `untagged` attribute on structs",
+                _ => "Cannot use the `tag` attribute on structs",
+            };
+            return Err(syn::Error::new(tag, message));
+        }
+    }
+    if let Some(partial) = attributes.partial_span {
+        if !matches!(container.data, syn::Data::Struct(..)) {
+            return Err(syn::Error::new(
+                partial,
+                "The `partial` attribute can only be used on structs",
+            ));
+        }
+        if attributes.from.is_some() {
+            return Err(syn::Error::new(
+                partial,
+                "Cannot use the `partial` attribute together with the `from` attribute",
+            ));
+        }
+        if attributes.try_from.is_some() {
+            return Err(syn::Error::new(
+                partial,
+                "Cannot use the `partial` attribute together with the `try_from` attribute",
+            ));
+        }
+    }
+    if let Some(transparent) = attributes.transparent_span {
+        let fields = match &container.data {
+            syn::Data::Struct(s) => &s.fields,
+            _ => {
+                return Err(syn::Error::new(
+                    transparent,
+                    "The `transparent` attribute can only be used on structs",
+                ))
+            }
+        };
+        if fields.len() != 1 {
+            return Err(syn::Error::new(
+                transparent,
+                "The `transparent` attribute can only be used on structs with a single field",
+            ));
+        }
+        // The struct is deserialized as its field, so it has no keys and no other way to be deserialized
+        let incompatible_attributes = [
+            ("from", attributes.from.is_some()),
+            ("try_from", attributes.try_from.is_some()),
+            ("partial", attributes.partial),
+            ("rename_all", attributes.rename_all.is_some()),
+            ("alias_all", attributes.alias_all.is_some()),
+            (
+                "deny_unknown_fields",
+                attributes.deny_unknown_fields.is_some(),
+            ),
+        ];
+        for (name, used) in incompatible_attributes {
+            if used {
+                return Err(syn::Error::new(
+                    transparent,
+                    format!("Cannot use the `transparent` attribute together with the `{name}` attribute"),
+                ));
+            }
+        }
+    }
+    if let Some(from_sequence) = attributes.from_sequence_span {
+        if !matches!(container.data, syn::Data::Struct(..)) {
             return Err(syn::Error::new(
-                tag,
-                "Cannot use the `tag` attribute on structs",
+                from_sequence,
+                "The `from_sequence` attribute can only be used on structs",
             ));
         }
+        // The fields are found by their position, so there are no keys to deny and no map
+        // to deserialize a partial update from
+        let incompatible_attributes = [
+            ("from", attributes.from.is_some()),
+            ("try_from", attributes.try_from.is_some()),
+            ("partial", attributes.partial),
+            ("transparent", attributes.transparent),
+            (
+                "deny_unknown_fields",
+                attributes.deny_unknown_fields.is_some(),
+            ),
+        ];
+        for (name, used) in incompatible_attributes {
+            if used {
+                return Err(syn::Error::new(
+                    from_sequence,
+                    format!("Cannot use the `from_sequence` attribute together with the `{name}` attribute"),
+                ));
+            }
+        }
+    }
+    if let Some(known_fields) = attributes.known_fields_span {
+        match &container.data {
+            syn::Data::Struct(s) if matches!(s.fields, syn::Fields::Named(_)) => {}
+            _ => {
+                return Err(syn::Error::new(
+                    known_fields,
+                    "The `known_fields` attribute can only be used on structs with named fields",
+                ))
+            }
+        }
+        // The fields must be deserialized from the entries of a map to have keys
+        let incompatible_attributes = [
+            ("from", attributes.from.is_some()),
+            ("try_from", attributes.try_from.is_some()),
+            ("transparent", attributes.transparent),
+            ("from_sequence", attributes.from_sequence),
+        ];
+        for (name, used) in incompatible_attributes {
+            if used {
+                return Err(syn::Error::new(
+                    known_fields,
+                    format!("Cannot use the `known_fields` attribute together with the `{name}` attribute"),
+                ));
+            }
+        }
+    }
+    if let Some(fill_from_default) = attributes.fill_from_default_span {
+        if !matches!(container.data, syn::Data::Struct(..)) {
+            return Err(syn::Error::new(
+                fill_from_default,
+                "The `fill_from_default` attribute can only be used on structs",
+            ));
+        }
+        // The fields must be deserialized one by one to be filled
+        let incompatible_attributes = [
+            ("from", attributes.from.is_some()),
+            ("try_from", attributes.try_from.is_some()),
+            ("transparent", attributes.transparent),
+        ];
+        for (name, used) in incompatible_attributes {
+            if used {
+                return Err(syn::Error::new(
+                    fill_from_default,
+                    format!("Cannot use the `fill_from_default` attribute together with the `{name}` attribute"),
+                ));
+            }
+        }
     }
     Ok(())
 }
@@ -722,7 +1549,7 @@ impl syn::parse::Parse for VariantAttributesInfo {
                     this.rename = Some(parse_rename(input)?);
                 }
                 "rename_all" => {
-                    this.rename_all = Some(parse_rename_all(input)?);
+                    this.rename_all = Some(parse_rename_all(input, "rename_all")?);
                     this.rename_all_span = Some(attr_name.span());
                 }
                 _ => {
@@ -3,7 +3,7 @@ use quote::quote;
 
 use crate::parse_type::{
     CommonDerivedTypeInfo,
-    VariantData::{Named, Unit},
+    VariantData::{Named, Newtype, Unit},
     VariantInfo,
 };
 
@@ -12,7 +12,13 @@ pub fn generate_derive_tagged_enum_impl(
     info: CommonDerivedTypeInfo,
     tag: String,
     variants: Vec<VariantInfo>,
+    case_insensitive: bool,
 ) -> TokenStream {
+    let tag_value = variant_key(
+        quote! { tag_value_string.as_str() },
+        &variants,
+        case_insensitive,
+    );
     // `variant_impls` is the token stream of the code responsible for deserialising
     // all the fields of the enum variants and returning the fully deserialised enum.
     let variants_impls = variants
@@ -24,11 +30,15 @@ pub fn generate_derive_tagged_enum_impl(
         impl_trait_tokens,
         err_ty,
         validate,
+        default_when_missing,
+        ..
     } = info;
 
     quote! {
          #impl_trait_tokens {
-            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
                 // The value must always be a map
                 let deserr_final__ = match deserr_value__ {
                     ::deserr::Value::Map(mut deserr_map__) => {
@@ -44,7 +54,7 @@ pub fn generate_derive_tagged_enum_impl(
                         let tag_value_string = match tag_value.into_value() {
                             ::deserr::Value::String(x) => x,
                             v => {
-                                return ::std::result::Result::Err(
+                                return ::core::result::Result::Err(
                                     ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
                                         None,
                                         ::deserr::ErrorKind::IncorrectValueKind {
@@ -57,17 +67,17 @@ pub fn generate_derive_tagged_enum_impl(
                             }
                         };
 
-                        match tag_value_string.as_str() {
+                        match #tag_value {
                             #(#variants_impls)*
                             // this is the case where the tag exists and is a string, but its value does not
                             // correspond to any valid enum variant name
                             _ => {
-                                ::std::result::Result::Err(
+                                ::core::result::Result::Err(
                                     ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
                                         None,
                                         // TODO: expected one of {expected_tags_list}, found {actual_tag} error message
                                         ::deserr::ErrorKind::Unexpected {
-                                            msg: "Incorrect tag value".to_string(),
+                                            msg: ::deserr::alloc_crate::string::String::from("Incorrect tag value"),
                                         },
                                         deserr_location__
                                     ))
@@ -77,13 +87,12 @@ pub fn generate_derive_tagged_enum_impl(
                     },
                     // this is the case where the value is not a map
                     v => {
-                        ::std::result::Result::Err(
-                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<V>(
                                 None,
-                                ::deserr::ErrorKind::IncorrectValueKind {
-                                    actual: v,
-                                    accepted: &[::deserr::ValueKind::Map],
-                                },
+                                v,
+                                &[::deserr::ValueKind::Map],
+                                ::core::any::type_name::<Self>(),
                                 deserr_location__
                             ))
                         )
@@ -125,7 +134,7 @@ fn generate_derive_tagged_enum_variant_impl(
             // If the enum variant is a unit variant, there is nothing else to do.
             quote! {
                 #variant_key_name => {
-                    ::std::result::Result::Ok(Self::#variant_ident)
+                    ::core::result::Result::Ok(Self::#variant_ident)
                 }
             }
         }
@@ -134,6 +143,7 @@ fn generate_derive_tagged_enum_variant_impl(
                 fields,
                 err_ty,
                 quote! { Self :: #variant_ident },
+                crate::MapMode::Owned,
             );
             // The code here is virtually identical to the code of `generate_derive_struct_impl`
             quote! {
@@ -143,12 +153,266 @@ fn generate_derive_tagged_enum_variant_impl(
                 }
             }
         }
+        // There is no content key, so the content of a newtype variant is the rest of the map
+        Newtype(ty) => quote! {
+            #variant_key_name => {
+                <#ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value(::deserr::Value::<V>::Map(deserr_map__), deserr_location__)
+                    .map(Self::#variant_ident)
+            }
+        },
+    }
+}
+
+/// Return a token stream that implements `Deserr<E>` for the given derived enum with an
+/// adjacent tag, i.e. with both the `tag` and `content` attributes.
+///
+/// The variant is selected by the value of the `tag` key, and its content is deserialized
+/// from the value of the `content` key.
+pub fn generate_derive_adjacently_tagged_enum_impl(
+    info: CommonDerivedTypeInfo,
+    tag: String,
+    content: String,
+    unknown_key: TokenStream,
+    variants: Vec<VariantInfo>,
+    case_insensitive: bool,
+) -> TokenStream {
+    let tag_value = variant_key(
+        quote! { tag_value_string.as_str() },
+        &variants,
+        case_insensitive,
+    );
+    // all the variant of the enum as a slice of `&str`
+    let all_variants_as_str = variants
+        .iter()
+        .map(|v| &v.key_name)
+        .map(|v| quote!(#v, ))
+        .collect::<TokenStream>();
+    let all_variants_as_str = quote!(&[#all_variants_as_str]);
+
+    let variants_impls = variants
+        .into_iter()
+        .map(|v| generate_derive_adjacently_tagged_enum_variant_impl(&info, &content, &v))
+        .collect::<Vec<_>>();
+
+    let CommonDerivedTypeInfo {
+        impl_trait_tokens,
+        err_ty,
+        validate,
+        default_when_missing,
+        ..
+    } = info;
+
+    quote! {
+         #impl_trait_tokens {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
+                // The value must always be a map
+                let deserr_final__ = match deserr_value__ {
+                    ::deserr::Value::Map(mut deserr_map__) => {
+                        let tag_value = ::deserr::Map::remove(&mut deserr_map__, #tag).ok_or_else(|| {
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                None,
+                                ::deserr::ErrorKind::MissingField {
+                                    field: #tag,
+                                },
+                                deserr_location__
+                            ))
+                        })?;
+                        let tag_value_string = match tag_value.into_value() {
+                            ::deserr::Value::String(x) => x,
+                            v => {
+                                return ::core::result::Result::Err(
+                                    ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                        None,
+                                        ::deserr::ErrorKind::IncorrectValueKind {
+                                            actual: v,
+                                            accepted: &[::deserr::ValueKind::String],
+                                        },
+                                        deserr_location__.push_key(#tag)
+                                    ))
+                                );
+                            }
+                        };
+
+                        let deserr_content__ = ::deserr::Map::remove(&mut deserr_map__, #content);
+
+                        // The keys that are neither the tag nor the content are handled like the
+                        // unknown keys of a struct
+                        let mut deserr_error__ = None;
+                        for (deserr_key__, _) in ::deserr::Map::into_iter(deserr_map__) {
+                            let deserr_key__ = deserr_key__.as_str();
+                            #unknown_key
+                        }
+
+                        let deserr_variant__ = match #tag_value {
+                            #(#variants_impls)*
+                            // this is the case where the tag exists and is a string, but its value does not
+                            // correspond to any valid enum variant name
+                            s => {
+                                ::core::result::Result::Err(
+                                    ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                        None,
+                                        ::deserr::ErrorKind::UnknownValue {
+                                            value: s,
+                                            accepted: #all_variants_as_str,
+                                        },
+                                        deserr_location__.push_key(#tag)
+                                    ))
+                                )
+                            }
+                        };
+                        match (deserr_variant__, deserr_error__) {
+                            (deserr_variant__, ::core::option::Option::None) => deserr_variant__,
+                            (::core::result::Result::Ok(_), ::core::option::Option::Some(e)) => ::core::result::Result::Err(e),
+                            (::core::result::Result::Err(e), ::core::option::Option::Some(deserr_error__)) => {
+                                ::core::result::Result::Err(::deserr::take_cf_content(
+                                    <#err_ty as ::deserr::MergeWithError<#err_ty>>::merge(
+                                        ::core::option::Option::Some(deserr_error__),
+                                        e,
+                                        deserr_location__
+                                    )
+                                ))
+                            }
+                        }
+                    },
+                    // this is the case where the value is not a map
+                    v => {
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<V>(
+                                None,
+                                v,
+                                &[::deserr::ValueKind::Map],
+                                ::core::any::type_name::<Self>(),
+                                deserr_location__
+                            ))
+                        )
+                    }
+                }?;
+                #validate
+            }
+        }
+    }
+}
+
+/// Create a token stream that deserialises the content of the enum variant and return
+/// the fully deserialised enum.
+///
+/// The context of the token stream is:
+///
+/// ```ignore
+/// let deserr_map__: Map
+/// match tag_value_string.as_str() {
+///     === here ===
+///     key => { .. }
+/// }
+/// ```
+///
+fn generate_derive_adjacently_tagged_enum_variant_impl(
+    info: &CommonDerivedTypeInfo,
+    content: &str,
+    variant: &VariantInfo,
+) -> TokenStream {
+    let CommonDerivedTypeInfo { err_ty, .. } = info;
+
+    let VariantInfo {
+        ident: variant_ident,
+        data,
+        key_name: variant_key_name,
+    } = variant;
+
+    match data {
+        // The content of a unit variant can be omitted, or be `null`
+        Unit => quote! {
+            #variant_key_name => {
+                match deserr_content__.map(::deserr::IntoValue::into_value) {
+                    ::core::option::Option::None | ::core::option::Option::Some(::deserr::Value::Null) => {
+                        ::core::result::Result::Ok(Self::#variant_ident)
+                    }
+                    ::core::option::Option::Some(v) => {
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                None,
+                                ::deserr::ErrorKind::IncorrectValueKind {
+                                    actual: v,
+                                    accepted: &[::deserr::ValueKind::Null],
+                                },
+                                deserr_location__.push_key(#content)
+                            ))
+                        )
+                    }
+                }
+            }
+        },
+        Named(fields) => {
+            let fields_impl = crate::generate_named_fields_impl(
+                fields,
+                err_ty,
+                quote! { Self :: #variant_ident },
+                crate::MapMode::Owned,
+            );
+            quote! {
+                #variant_key_name => {
+                    match deserr_content__.map(::deserr::IntoValue::into_value) {
+                        ::core::option::Option::Some(::deserr::Value::Map(deserr_map__)) => {
+                            let deserr_location__ = deserr_location__.push_key(#content);
+                            let mut deserr_error__ = None;
+                            #fields_impl
+                        }
+                        ::core::option::Option::Some(v) => {
+                            ::core::result::Result::Err(
+                                ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                    None,
+                                    ::deserr::ErrorKind::IncorrectValueKind {
+                                        actual: v,
+                                        accepted: &[::deserr::ValueKind::Map],
+                                    },
+                                    deserr_location__.push_key(#content)
+                                ))
+                            )
+                        }
+                        ::core::option::Option::None => {
+                            ::core::result::Result::Err(
+                                ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                    None,
+                                    ::deserr::ErrorKind::MissingField {
+                                        field: #content,
+                                    },
+                                    deserr_location__
+                                ))
+                            )
+                        }
+                    }
+                }
+            }
+        }
+        Newtype(ty) => quote! {
+            #variant_key_name => {
+                match deserr_content__ {
+                    ::core::option::Option::Some(v) => {
+                        <#ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value(::deserr::IntoValue::into_value(v), deserr_location__.push_key(#content))
+                            .map(Self::#variant_ident)
+                    }
+                    ::core::option::Option::None => {
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                                None,
+                                ::deserr::ErrorKind::MissingField {
+                                    field: #content,
+                                },
+                                deserr_location__
+                            ))
+                        )
+                    }
+                }
+            }
+        },
     }
 }
 
 /// Create a token stream that deserialises all the fields of the enum variant and return
 /// the fully deserialised enum.
-/// /!\ Currently, we only support untagged enum that only contains unit variants.
+/// /!\ Currently, we only support externally tagged enums that only contain unit variants.
 ///
 /// The context of the token stream is:
 ///
@@ -160,10 +424,12 @@ fn generate_derive_tagged_enum_variant_impl(
 /// }
 /// ```
 ///
-pub fn generate_derive_untagged_enum_impl(
+pub fn generate_derive_unit_enum_impl(
     info: CommonDerivedTypeInfo,
     variants: Vec<VariantInfo>,
+    case_insensitive: bool,
 ) -> TokenStream {
+    let value = variant_key(quote! { s.as_str() }, &variants, case_insensitive);
     // all the variant of the enum as a slice of `&str`
     let all_variants_as_str = variants
         .iter()
@@ -174,7 +440,7 @@ pub fn generate_derive_untagged_enum_impl(
 
     // `variant_impls` is the token stream of the code responsible for deserialising
     // the enum variants and returning the correct variant. Since we've already ensured
-    // the enum was untagged, we can re-use the `generate_derive_tagged_enum_variant_impl`
+    // the enum only contains unit variants, we can re-use the `generate_derive_tagged_enum_variant_impl`
     // function and only use the `Unit` part of the match.
     let variants_impls = variants
         .into_iter()
@@ -185,20 +451,24 @@ pub fn generate_derive_untagged_enum_impl(
         impl_trait_tokens,
         err_ty,
         validate,
+        default_when_missing,
+        ..
     } = info;
 
     quote! {
          #impl_trait_tokens {
-            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
                 // The value must always be a string
                 let deserr_final__ = match deserr_value__ {
                     ::deserr::Value::String(s) => {
-                        match s.as_str() {
+                        match #value {
                             #(#variants_impls)*
                             // this is the case where the tag exists and is a string, but its value does not
                             // correspond to any valid enum variant name
                             s => {
-                                ::std::result::Result::Err(
+                                ::core::result::Result::Err(
                                     ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
                                         None,
                                         ::deserr::ErrorKind::UnknownValue {
@@ -213,13 +483,12 @@ pub fn generate_derive_untagged_enum_impl(
                     },
                     // this is the case where the value is not a String
                     v => {
-                        ::std::result::Result::Err(
-                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<V>(
                                 None,
-                                ::deserr::ErrorKind::IncorrectValueKind {
-                                    actual: v,
-                                    accepted: &[::deserr::ValueKind::String],
-                                },
+                                v,
+                                &[::deserr::ValueKind::String],
+                                ::core::any::type_name::<Self>(),
                                 deserr_location__
                             ))
                         )
@@ -230,3 +499,170 @@ pub fn generate_derive_untagged_enum_impl(
         }
     }
 }
+
+/// Return an expression evaluating to the `&str` that is matched against the keys of the
+/// variants. With the `case_insensitive` attribute, it is the key of the variant that is
+/// equal to the given value when ignoring the ASCII case, or the value itself otherwise.
+fn variant_key(
+    value: TokenStream,
+    variants: &[VariantInfo],
+    case_insensitive: bool,
+) -> TokenStream {
+    if !case_insensitive {
+        return value;
+    }
+    let key_names = variants.iter().map(|v| &v.key_name);
+    quote! {
+        {
+            let deserr_value__: &str = #value;
+            let deserr_key_names__: &[&'static str] = &[#(#key_names),*];
+            deserr_key_names__
+                .iter()
+                .copied()
+                .find(|key| key.eq_ignore_ascii_case(deserr_value__))
+                .unwrap_or(deserr_value__)
+        }
+    }
+}
+
+/// Return a token stream that implements `Deserr<E>` for the given derived enum with the
+/// `untagged` attribute.
+///
+/// The value is buffered, and each variant is deserialized from a copy of the buffer, in
+/// the order of declaration. The first variant that succeeds is returned. If they all fail,
+/// the error lists the error of each variant.
+pub fn generate_derive_untagged_enum_impl(
+    info: CommonDerivedTypeInfo,
+    variants: Vec<VariantInfo>,
+) -> TokenStream {
+    let key_names = variants.iter().map(|v| &v.key_name).collect::<Vec<_>>();
+    let variants_impls = variants
+        .iter()
+        .map(|v| generate_derive_untagged_enum_variant_impl(&info, v))
+        .collect::<Vec<_>>();
+
+    let CommonDerivedTypeInfo {
+        impl_trait_tokens,
+        err_ty,
+        validate,
+        default_when_missing,
+        ..
+    } = info;
+
+    quote! {
+         #impl_trait_tokens {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
+                let deserr_buffered__ = ::deserr::BufferedValue::from_value(deserr_value__);
+                let deserr_final__ = (|| {
+                    let mut deserr_variant_errors__ = ::deserr::alloc_crate::vec::Vec::new();
+                    #(
+                        let deserr_warnings__ = ::deserr::warnings_checkpoint();
+                        let deserr_missing_fields__ = ::deserr::missing_fields_checkpoint();
+                        let deserr_variant__ = (|| -> ::core::result::Result<Self, #err_ty> {
+                            let deserr_value__ = ::deserr::IntoValue::into_value(deserr_buffered__.clone());
+                            #variants_impls
+                        })();
+                        match deserr_variant__ {
+                            ::core::result::Result::Ok(deserr_variant__) => return ::core::result::Result::Ok(deserr_variant__),
+                            ::core::result::Result::Err(e) => {
+                                // The warnings and the defaulted fields of a variant that doesn't match are irrelevant
+                                ::deserr::rollback_warnings(deserr_warnings__);
+                                ::deserr::rollback_missing_fields(deserr_missing_fields__);
+                                deserr_variant_errors__.push((#key_names, e));
+                            }
+                        }
+                    )*
+                    // None of the variants could be deserialized
+                    ::core::result::Result::Err(
+                        ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                            None,
+                            ::deserr::ErrorKind::Unexpected {
+                                msg: ::deserr::describe_untagged_errors(&deserr_variant_errors__),
+                            },
+                            deserr_location__
+                        ))
+                    )
+                })()?;
+                #validate
+            }
+        }
+    }
+}
+
+/// Create a token stream that deserialises the enum variant from `deserr_value__`.
+///
+/// The context of the token stream is:
+///
+/// ```ignore
+/// let deserr_value__: Value<BufferedValue>;
+/// let deserr_variant__ = (|| -> Result<Self, E> {
+///     === here ===
+/// })();
+/// ```
+///
+fn generate_derive_untagged_enum_variant_impl(
+    info: &CommonDerivedTypeInfo,
+    variant: &VariantInfo,
+) -> TokenStream {
+    let CommonDerivedTypeInfo { err_ty, .. } = info;
+
+    let VariantInfo {
+        ident: variant_ident,
+        data,
+        ..
+    } = variant;
+
+    match data {
+        // A unit variant is deserialized from `null`
+        Unit => quote! {
+            match deserr_value__ {
+                ::deserr::Value::Null => ::core::result::Result::Ok(Self::#variant_ident),
+                v => {
+                    ::core::result::Result::Err(
+                        ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<::deserr::BufferedValue>(
+                            None,
+                            v,
+                            &[::deserr::ValueKind::Null],
+                            ::core::any::type_name::<Self>(),
+                            deserr_location__
+                        ))
+                    )
+                }
+            }
+        },
+        Newtype(ty) => quote! {
+            <#ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value(deserr_value__, deserr_location__)
+                .map(Self::#variant_ident)
+        },
+        Named(fields) => {
+            let fields_impl = crate::generate_named_fields_impl(
+                fields,
+                err_ty,
+                quote! { Self :: #variant_ident },
+                crate::MapMode::Owned,
+            );
+            // The code here is virtually identical to the code of `generate_derive_struct_impl`
+            quote! {
+                match deserr_value__ {
+                    ::deserr::Value::Map(deserr_map__) => {
+                        let mut deserr_error__ = None;
+                        #fields_impl
+                    }
+                    v => {
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<::deserr::BufferedValue>(
+                                None,
+                                v,
+                                &[::deserr::ValueKind::Map],
+                                ::core::any::type_name::<Self>(),
+                                deserr_location__
+                            ))
+                        )
+                    }
+                }
+            }
+        }
+    }
+}
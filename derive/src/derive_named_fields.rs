@@ -2,62 +2,103 @@ use crate::parse_type::NamedFieldsInfo;
 use proc_macro2::TokenStream;
 use quote::quote;
 
+/// Whether the generated code deserializes the fields from an owned `Map`
+/// or from a borrowed `MapRef`.
+#[derive(Clone, Copy)]
+pub enum MapMode {
+    Owned,
+    Borrowed,
+}
+
 pub fn generate_named_fields_impl(
     fields: &NamedFieldsInfo,
     err_ty: &syn::Type,
     create: TokenStream,
+    mode: MapMode,
 ) -> TokenStream {
     let NamedFieldsInfo {
         field_names,
-        field_tys,
-        field_defaults,
-        field_errs,
-        field_from_fns,
+        field_vars,
+        field_tys: _,
+        field_errs: _,
+        field_from_fns: _,
         field_from_errors: _,
         field_maps,
-        missing_field_errors,
-        key_names,
+        missing_field_handlers: _,
+        key_names: _,
+        aliases: _,
+        key_patterns,
+        duplicate_key_checks,
+        alias_key_vars,
+        deprecation_warnings,
+        empty_string_as_none: _,
+        null_is_none: _,
+        double_option: _,
+        deny_null: _,
+        saturating: _,
         unknown_key,
         needs_predicate: _,
+        has_flattened_field,
+        flattened_ty: _,
+        container_default: _,
+        from_sequence: _,
+        tuple_struct: _,
+        partial_field_decls: _,
     } = fields;
+
+    // In both modes, `deserr_key__` ends up being a `&str` in the body of the loop
+    // The owned maps of some formats have keys that can't be converted to a string
+    let (check_keys, map_iter, key_as_str) = match mode {
+        MapMode::Owned => (
+            quote! { ::deserr::check_map_keys::<_, #err_ty>(&deserr_map__, deserr_location__)?; },
+            quote! { ::deserr::Map::into_iter(deserr_map__) },
+            quote! { let deserr_key__ = deserr_key__.as_str(); },
+        ),
+        MapMode::Borrowed => (
+            quote! {},
+            quote! { ::deserr::MapRef::into_iter(deserr_map__) },
+            quote! {},
+        ),
+    };
+
+    let field_assignments = field_assignments(fields, err_ty, mode);
+    let missing_fields = missing_fields(fields);
+
+    // The entries that no field claimed, used to deserialize the flattened field
+    let flattened_entries = if *has_flattened_field {
+        quote! { let mut deserr_flattened__ = ::deserr::alloc_crate::vec::Vec::new(); }
+    } else {
+        quote! {}
+    };
+
     quote! {
-        // Start by declaring all the fields as mutable optionals
-        // Their initial value is given by the precomputed `#field_defaults`,
-        // see [NamedFieldsInfo] and [NamedFieldsInfo::parse].
-        //
-        // The initial value of #field_names is `None` if the field has no initial value and
-        // thus must be given by the map, and `Some` otherwise.
+        // Start by declaring all the fields as missing.
+        // Their default values are only evaluated once we know the field is not in the map,
+        // see `#missing_fields` and [NamedFieldsInfo::parse].
+        #(
+            #[allow(non_snake_case)]
+            let mut #field_vars : ::deserr::FieldState<_> = ::deserr::FieldState::Missing ;
+        )*
+        #flattened_entries
+        // The first key found for the fields that have aliases, to report the duplicates
         #(
-            let mut #field_names : ::deserr::FieldState<_> = #field_defaults ;
+            #[allow(non_snake_case)]
+            let mut #alias_key_vars : &'static str = "";
         )*
+        #check_keys
         // We traverse the entire map instead of looking for specific keys, because we want
         // to handle the case where a key is unknown and the attribute `deny_unknown_fields` was used.
-        for (deserr_key__, deserr_value__) in ::deserr::Map::into_iter(deserr_map__) {
-            match deserr_key__.as_str() {
+        for (deserr_key__, deserr_value__) in #map_iter {
+            #key_as_str
+            match deserr_key__ {
                 // For each known key, look at the corresponding value and try to deserialize it
 
                 #(
-                    #key_names => {
-                        #field_names = match
-                            <#field_tys as ::deserr::Deserr<#field_errs>>::deserialize_from_value(
-                                ::deserr::IntoValue::into_value(deserr_value__),
-                                deserr_location__.push_key(deserr_key__.as_str())
-                            ) {
-                                ::std::result::Result::Ok(x) => {
-                                    #field_from_fns
-                                },
-                                ::std::result::Result::Err(e) => {
-                                    deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
-                                        deserr_error__,
-                                        e,
-                                        deserr_location__.push_key(deserr_key__.as_str())
-                                    ) {
-                                        ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                                        ::std::ops::ControlFlow::Break(e) => return ::std::result::Result::Err(e),
-                                    };
-                                    ::deserr::FieldState::Err
-                                }
-                            };
+                    #key_patterns => {
+                        let deserr_value_location__ = deserr_location__.push_key(deserr_key__);
+                        #duplicate_key_checks
+                        #deprecation_warnings
+                        #field_assignments
                     }
                 )*
                 // For an unknown key, use the precomputed #unknown_key token stream
@@ -66,23 +107,287 @@ pub fn generate_named_fields_impl(
                 }
             }
         }
-        // Now we check whether any field was missing
+        // Now we either give their default value to the missing fields, or return an error
+        #missing_fields
+
+        if let Some(deserr_error__) = deserr_error__ {
+            ::core::result::Result::Err(deserr_error__)
+        } else {
+            // If the deserialization was successful, then all #field_vars are `Some(..)`
+            // Otherwise, an error was thrown earlier
+            ::core::result::Result::Ok(#create {
+                #(
+                    #field_names : #field_vars.map(#field_maps).unwrap(),
+                )*
+            })
+        }
+    }
+}
+
+/// Return the code deserializing the fields of a tuple struct or of a struct with the
+/// `from_sequence` attribute from the owned sequence `deserr_seq__`, by giving its elements
+/// to the fields in their declaration order.
+pub fn generate_sequence_fields_impl(
+    fields: &NamedFieldsInfo,
+    err_ty: &syn::Type,
+    create: TokenStream,
+) -> TokenStream {
+    let NamedFieldsInfo {
+        field_names,
+        field_vars,
+        field_maps,
+        key_names,
+        deprecation_warnings,
+        tuple_struct,
+        ..
+    } = fields;
+
+    // The skipped fields are at the end and don't take an element of the sequence
+    let len = key_names.len();
+    let indexes = 0..len;
+    let field_assignments = field_assignments(fields, err_ty, MapMode::Owned);
+    let missing_fields = missing_fields(fields);
+
+    // A tuple struct needs all of its elements, while the missing trailing elements of a
+    // struct with the `from_sequence` attribute are handled like missing fields
+    let len_check = if *tuple_struct {
+        quote! {
+            if deserr_len__ != #len {
+                return ::core::result::Result::Err(
+                    ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<::core::convert::Infallible>(
+                        None,
+                        ::deserr::ErrorKind::Unexpected {
+                            msg: ::deserr::describe_sequence_length(#len, false, deserr_len__),
+                        },
+                        deserr_location__
+                    ))
+                );
+            }
+        }
+    } else {
+        quote! {
+            if deserr_len__ > #len {
+                deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<::core::convert::Infallible>(
+                    deserr_error__,
+                    ::deserr::ErrorKind::Unexpected {
+                        msg: ::deserr::describe_sequence_length(#len, true, deserr_len__),
+                    },
+                    deserr_location__
+                ) {
+                    ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                    ::core::ops::ControlFlow::Break(e) => return ::core::result::Result::Err(e),
+                };
+            }
+        }
+    };
+
+    quote! {
         #(
-            if #field_names .is_missing() {
-                #missing_field_errors
+            #[allow(non_snake_case)]
+            let mut #field_vars : ::deserr::FieldState<_> = ::deserr::FieldState::Missing ;
+        )*
+        let deserr_len__ = ::deserr::Sequence::len(&deserr_seq__);
+        #len_check
+        let mut deserr_elements__ = ::deserr::Sequence::into_iter(deserr_seq__);
+        // Give the elements to the fields in order, the missing trailing elements are
+        // handled like missing fields
+        #(
+            if let ::core::option::Option::Some(deserr_value__) = deserr_elements__.next() {
+                let deserr_value_location__ = deserr_location__.push_index(#indexes);
+                #deprecation_warnings
+                #field_assignments
             }
         )*
+        #missing_fields
 
         if let Some(deserr_error__) = deserr_error__ {
-            ::std::result::Result::Err(deserr_error__)
+            ::core::result::Result::Err(deserr_error__)
         } else {
-            // If the deserialization was successful, then all #field_names are `Some(..)`
-            // Otherwise, an error was thrown earlier
-            ::std::result::Result::Ok(#create {
+            ::core::result::Result::Ok(#create {
                 #(
-                    #field_names : #field_names.map(#field_maps).unwrap(),
+                    #field_names : #field_vars.map(#field_maps).unwrap(),
                 )*
             })
         }
     }
 }
+
+/// Return the code running the handler of each field that is still missing once the
+/// input was traversed.
+fn missing_fields(fields: &NamedFieldsInfo) -> TokenStream {
+    let NamedFieldsInfo {
+        field_vars,
+        missing_field_handlers,
+        container_default,
+        ..
+    } = fields;
+
+    let missing_fields = quote! {
+        #(
+            if #field_vars .is_missing() {
+                #missing_field_handlers
+            }
+        )*
+    };
+    match container_default {
+        // The default value of the struct is only evaluated if one of its fields is missing,
+        // which then takes its value in it
+        Some(container_default) => quote! {
+            if #( #field_vars .is_missing() )||* {
+                let deserr_default__: Self = #container_default;
+                #missing_fields
+            }
+        },
+        None => missing_fields,
+    }
+}
+
+/// Return the code deserializing the value `deserr_value__` of each field into its state.
+///
+/// With the `null_is_none` attribute, an explicit `null` is ignored, so that the field is
+/// handled like a missing field.
+fn field_assignments(
+    fields: &NamedFieldsInfo,
+    err_ty: &syn::Type,
+    mode: MapMode,
+) -> Vec<TokenStream> {
+    let kind = match mode {
+        MapMode::Owned => quote! { ::deserr::IntoValue::kind(&deserr_value__) },
+        MapMode::Borrowed => quote! { ::deserr::IntoValueRef::kind(&deserr_value__) },
+    };
+    fields
+        .field_vars
+        .iter()
+        .zip(field_deserializations(fields, mode))
+        .zip(&fields.field_from_fns)
+        .zip(&fields.null_is_none)
+        .map(|(((field_var, field_deserialization), field_from_fn), null_is_none)| {
+            let assignment = quote! {
+                #field_var = match #field_deserialization {
+                    ::core::result::Result::Ok(x) => {
+                        #field_from_fn
+                    },
+                    ::core::result::Result::Err(e) => {
+                        deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
+                            deserr_error__,
+                            e,
+                            deserr_value_location__
+                        ) {
+                            ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                            ::core::ops::ControlFlow::Break(e) => return ::core::result::Result::Err(e),
+                        };
+                        ::deserr::FieldState::Err
+                    }
+                };
+            };
+            if *null_is_none {
+                quote! {
+                    if #kind != ::deserr::ValueKind::Null {
+                        #assignment
+                    }
+                }
+            } else {
+                assignment
+            }
+        })
+        .collect()
+}
+
+/// Return the code deserializing the value of each field from `deserr_value__`, at the
+/// location `deserr_value_location__`. With the `empty_string_as_none` attribute, an empty
+/// string is replaced by `null` before being deserialized. With the `double_option` attribute,
+/// an explicit `null` is deserialized as `Some(None)`. With the `deny_null` attribute, the `T`
+/// of an `Option<T>` is deserialized, so that it refuses an explicit `null`. With the
+/// `saturating` attribute, the value is deserialized as a `Saturating` integer, which clamps it
+/// to the bounds of the type.
+fn field_deserializations(fields: &NamedFieldsInfo, mode: MapMode) -> Vec<TokenStream> {
+    fields
+        .field_tys
+        .iter()
+        .zip(&fields.field_errs)
+        .zip(&fields.empty_string_as_none)
+        .zip(&fields.double_option)
+        .zip(&fields.deny_null)
+        .zip(&fields.saturating)
+        .map(|(((((field_ty, field_err), empty_string_as_none), double_option), deny_null), saturating)| {
+            // With the `deny_null` attribute, the `T` of the `Option<T>` is deserialized instead
+            let (from_value, from_value_ref) = match (deny_null, saturating) {
+                (true, _) => (
+                    quote! { ::deserr::deserialize_non_null::<_, _, #field_err> },
+                    quote! { ::deserr::deserialize_non_null_ref::<_, _, #field_err> },
+                ),
+                (false, true) => (
+                    quote! { <::core::num::Saturating<#field_ty> as ::deserr::Deserr<#field_err>>::deserialize_from_value },
+                    quote! { <::core::num::Saturating<#field_ty> as ::deserr::Deserr<#field_err>>::deserialize_from_value_ref },
+                ),
+                (false, false) => (
+                    quote! { <#field_ty as ::deserr::Deserr<#field_err>>::deserialize_from_value },
+                    quote! { <#field_ty as ::deserr::Deserr<#field_err>>::deserialize_from_value_ref },
+                ),
+            };
+            let deserialization = match (*empty_string_as_none, mode) {
+                (false, MapMode::Owned) => quote! {
+                    #from_value(
+                        ::deserr::IntoValue::into_value(deserr_value__),
+                        deserr_value_location__
+                    )
+                },
+                (false, MapMode::Borrowed) => quote! {
+                    #from_value_ref(
+                        deserr_value__,
+                        deserr_value_location__
+                    )
+                },
+                (true, MapMode::Owned) => quote! {
+                    #from_value(
+                        match ::deserr::IntoValue::into_value(deserr_value__) {
+                            ::deserr::Value::String(deserr_string__) if deserr_string__.is_empty() => ::deserr::Value::Null,
+                            deserr_value__ => deserr_value__,
+                        },
+                        deserr_value_location__
+                    )
+                },
+                // The borrowed value is consumed to look at the string, so the other values
+                // are deserialized from their owned version
+                (true, MapMode::Borrowed) => quote! {
+                    match ::deserr::IntoValueRef::into_value_ref(deserr_value__) {
+                        ::deserr::ValueRef::String("") => #from_value(
+                            ::deserr::Value::<::core::convert::Infallible>::Null,
+                            deserr_value_location__
+                        ),
+                        deserr_value__ => #from_value(
+                            deserr_value__.into_owned(),
+                            deserr_value_location__
+                        ),
+                    }
+                },
+            };
+            let deserialization = match (deny_null, saturating) {
+                (true, _) => quote! {
+                    {
+                        let deserr_result__: ::core::result::Result<#field_ty, #field_err> = #deserialization;
+                        deserr_result__
+                    }
+                },
+                (false, true) => quote! {
+                    ::core::result::Result::map(#deserialization, |x: ::core::num::Saturating<_>| x.0)
+                },
+                (false, false) => deserialization,
+            };
+            if !*double_option {
+                return deserialization;
+            }
+            let kind = match mode {
+                MapMode::Owned => quote! { ::deserr::IntoValue::kind(&deserr_value__) },
+                MapMode::Borrowed => quote! { ::deserr::IntoValueRef::kind(&deserr_value__) },
+            };
+            quote! {
+                if #kind == ::deserr::ValueKind::Null {
+                    ::core::result::Result::Ok(::core::option::Option::Some(::core::option::Option::None))
+                } else {
+                    #deserialization
+                }
+            }
+        })
+        .collect()
+}
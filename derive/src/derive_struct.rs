@@ -1,7 +1,10 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::parse_type::{CommonDerivedTypeInfo, NamedFieldsInfo};
+use crate::{
+    parse_type::{CommonDerivedTypeInfo, NamedFieldsInfo, PartialTypeInfo},
+    MapMode,
+};
 
 /// Return a token stream that implements `Deserr<E>` for the given derived struct with named fields
 pub fn generate_derive_struct_impl(
@@ -12,34 +15,263 @@ pub fn generate_derive_struct_impl(
         impl_trait_tokens,
         err_ty,
         validate,
+        default_when_missing,
+        partial,
+        try_from_json: _,
+        known_fields: _,
     } = info;
 
-    let fields_impl = crate::generate_named_fields_impl(&fields, &err_ty, quote! { Self });
+    let partial_impl = match partial {
+        Some(partial) => generate_partial_struct(partial, &fields, &err_ty),
+        None => quote! {},
+    };
+
+    // The value is either a map, or a sequence with the `from_sequence` attribute
+    let (fields_impl, accepted_kind) = if fields.from_sequence {
+        let fields_impl = crate::generate_sequence_fields_impl(&fields, &err_ty, quote! { Self });
+        (
+            quote! {
+                ::deserr::Value::Sequence(deserr_seq__) => {
+                    let mut deserr_error__ = None;
+                    #fields_impl
+                }
+            },
+            quote! { ::deserr::ValueKind::Sequence },
+        )
+    } else {
+        let fields_impl =
+            crate::generate_named_fields_impl(&fields, &err_ty, quote! { Self }, MapMode::Owned);
+        (
+            quote! {
+                ::deserr::Value::Map(deserr_map__) => {
+                    let mut deserr_error__ = None;
+                    #fields_impl
+                }
+            },
+            quote! { ::deserr::ValueKind::Map },
+        )
+    };
+
+    // The leftover entries given to a flattened field must be owned, so we keep the
+    // default implementation that converts the borrowed value to an owned one.
+    // The elements of a sequence are always deserialized from their owned version.
+    let deserialize_from_value_ref = if fields.has_flattened_field || fields.from_sequence {
+        quote! {}
+    } else {
+        let fields_ref_impl =
+            crate::generate_named_fields_impl(&fields, &err_ty, quote! { Self }, MapMode::Borrowed);
+        quote! {
+            fn deserialize_from_value_ref<'deserr_a__, V: ::deserr::IntoValueRef<'deserr_a__>>(deserr_value__: V, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
+                let deserr_final__ = match ::deserr::IntoValueRef::into_value_ref(deserr_value__) {
+                    // The keys of the map are borrowed, so we don't need to allocate them
+                    ::deserr::ValueRef::Map(deserr_map__) => {
+                        let mut deserr_error__ = None;
+                        #fields_ref_impl
+                    }
+                    // Anything else is an error, which is best handled by the owned implementation
+                    v => {
+                        return <Self as ::deserr::Deserr<#err_ty>>::deserialize_from_value(v.into_owned(), deserr_location__);
+                    }
+                }?;
+                #validate
+            }
+        }
+    };
 
     quote! {
          #impl_trait_tokens {
-            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
                 let deserr_final__ = match deserr_value__ {
-                    // The value must always be a map
+                    // The value must always be a map, or a sequence with the `from_sequence` attribute
+                    #fields_impl
+                    // this is the case where the value has another kind
+                    v => {
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<V>(
+                                None,
+                                v,
+                                &[#accepted_kind],
+                                ::core::any::type_name::<Self>(),
+                                deserr_location__
+                            ))
+                        )
+                    }
+                }?;
+                #validate
+            }
+
+            #deserialize_from_value_ref
+        }
+
+        #partial_impl
+    }
+}
+
+/// Return a token stream that implements `Deserr<E>` for the given derived struct with the
+/// `transparent` attribute, by deserializing its single field at the location of the struct.
+pub fn generate_derive_transparent_impl(
+    info: CommonDerivedTypeInfo,
+    member: syn::Member,
+    field_ty: syn::Type,
+) -> TokenStream {
+    let CommonDerivedTypeInfo {
+        impl_trait_tokens,
+        err_ty,
+        validate,
+        default_when_missing,
+        ..
+    } = info;
+
+    quote! {
+         #impl_trait_tokens {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
+                let deserr_final__ = Self {
+                    #member: <#field_ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value(deserr_value__, deserr_location__)?,
+                };
+                #validate
+            }
+
+            fn deserialize_from_value_ref<'deserr_a__, V: ::deserr::IntoValueRef<'deserr_a__>>(deserr_value__: V, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
+                let deserr_final__ = Self {
+                    #member: <#field_ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value_ref(deserr_value__, deserr_location__)?,
+                };
+                #validate
+            }
+        }
+    }
+}
+
+/// Return a token stream that declares the companion struct of the `partial` attribute,
+/// implements `Deserr<E>` for it, and provides its `merge_into` method.
+///
+/// The companion struct is deserialized like the derived struct, except that a missing field
+/// is `None` instead of being an error or getting its default value, and the `validate`
+/// function isn't called since the struct is incomplete.
+fn generate_partial_struct(
+    partial: PartialTypeInfo,
+    fields: &NamedFieldsInfo,
+    err_ty: &syn::Type,
+) -> TokenStream {
+    let PartialTypeInfo {
+        ident,
+        vis,
+        derives,
+        generics,
+        base_ident,
+        impl_trait_tokens,
+    } = partial;
+
+    // The skipped fields are at the end and aren't part of the companion struct
+    let len = fields.key_names.len();
+    let field_names = &fields.field_names[..len];
+    let partial_field_decls = &fields.partial_field_decls;
+
+    let partial_fields = NamedFieldsInfo {
+        field_names: field_names.to_vec(),
+        field_vars: fields.field_vars[..len].to_vec(),
+        field_tys: fields.field_tys[..len].to_vec(),
+        field_errs: fields.field_errs.clone(),
+        // The deserialized value is mapped right away, so the state of the field holds the
+        // final `Option` and can be `None` when the field is missing
+        field_from_fns: fields
+            .field_from_fns
+            .iter()
+            .zip(&fields.field_maps)
+            .map(|(from_fn, map)| {
+                quote! {
+                    let deserr_state__: ::deserr::FieldState<_> = { #from_fn };
+                    deserr_state__.map(|x| ::core::option::Option::Some((#map)(x)))
+                }
+            })
+            .collect(),
+        field_from_errors: fields.field_from_errors.clone(),
+        field_maps: vec![quote! { ::core::convert::identity }; len],
+        missing_field_handlers: fields.field_vars[..len]
+            .iter()
+            .map(|field_var| {
+                quote! {
+                    #field_var = ::deserr::FieldState::Some(::core::option::Option::None);
+                }
+            })
+            .collect(),
+        key_names: fields.key_names.clone(),
+        aliases: fields.aliases.clone(),
+        key_patterns: fields.key_patterns.clone(),
+        duplicate_key_checks: fields.duplicate_key_checks.clone(),
+        alias_key_vars: fields.alias_key_vars.clone(),
+        deprecation_warnings: fields.deprecation_warnings.clone(),
+        empty_string_as_none: fields.empty_string_as_none.clone(),
+        null_is_none: fields.null_is_none.clone(),
+        double_option: fields.double_option.clone(),
+        deny_null: fields.deny_null.clone(),
+        saturating: fields.saturating.clone(),
+        needs_predicate: fields.needs_predicate[..len].to_vec(),
+        unknown_key: fields.unknown_key.clone(),
+        has_flattened_field: false,
+        flattened_ty: None,
+        container_default: None,
+        from_sequence: false,
+        tuple_struct: false,
+        partial_field_decls: vec![],
+    };
+    let fields_impl =
+        crate::generate_named_fields_impl(&partial_fields, err_ty, quote! { Self }, MapMode::Owned);
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let doc = format!(
+        "The fields of [`{base_ident}`] given by a partial update, which are applied with [`{ident}::merge_into`]."
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(#(#derives),*)]
+        #vis struct #ident #generics #where_clause {
+            #(#partial_field_decls),*
+        }
+
+        impl #impl_generics ::core::default::Default for #ident #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_names : ::core::option::Option::None),*
+                }
+            }
+        }
+
+        #impl_trait_tokens {
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
+                match deserr_value__ {
                     ::deserr::Value::Map(deserr_map__) => {
                         let mut deserr_error__ = None;
                         #fields_impl
                     }
-                    // this is the case where the value is not a map
                     v => {
-                        ::std::result::Result::Err(
-                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::error::<V>(
+                        ::core::result::Result::Err(
+                            ::deserr::take_cf_content(<#err_ty as ::deserr::DeserializeError>::incorrect_value_kind_error::<V>(
                                 None,
-                                ::deserr::ErrorKind::IncorrectValueKind {
-                                    actual: v,
-                                    accepted: &[::deserr::ValueKind::Map],
-                                },
+                                v,
+                                &[::deserr::ValueKind::Map],
+                                ::core::any::type_name::<Self>(),
                                 deserr_location__
                             ))
                         )
                     }
-                }?;
-                #validate
+                }
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Overwrite the fields of `base` that are given by this partial update, and keep the others.
+            #[allow(dead_code)]
+            pub fn merge_into(self, base: &mut #base_ident #ty_generics) {
+                #(
+                    if let ::core::option::Option::Some(x) = self.#field_names {
+                        base.#field_names = x;
+                    }
+                )*
             }
         }
     }
@@ -1,6 +1,6 @@
 use crate::{
     attribute_parser::{AttributeFrom, AttributeTryFrom, FunctionReturningError},
-    parse_type::CommonDerivedTypeInfo,
+    parse_type::{callable_function, CommonDerivedTypeInfo},
 };
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -9,11 +9,14 @@ use quote::quote;
 pub fn generate_derive_try_from_user_function(
     info: CommonDerivedTypeInfo,
     from_attr: AttributeTryFrom,
+    try_from_error: Option<syn::ExprPath>,
 ) -> TokenStream {
     let CommonDerivedTypeInfo {
         impl_trait_tokens,
         err_ty,
         validate,
+        default_when_missing,
+        ..
     } = info;
 
     let AttributeTryFrom {
@@ -27,23 +30,33 @@ pub fn generate_derive_try_from_user_function(
         ..
     } = from_attr;
 
+    let function = callable_function(&function, &from_ty, is_ref);
     let function_call = if is_ref {
-        quote! { #function (&deserr_from__) }
+        quote! { (#function)(&deserr_from__) }
     } else {
-        quote! { #function (deserr_from__) }
+        quote! { (#function)(deserr_from__) }
+    };
+
+    let map_error = match try_from_error {
+        Some(try_from_error) => quote! { #try_from_error(e, deserr_location__) },
+        None => quote! {
+            ::deserr::take_cf_content(
+                <#err_ty as ::deserr::MergeWithError<#function_error_ty>>::merge(None, e, deserr_location__)
+            )
+        },
     };
 
     quote! {
          #impl_trait_tokens {
-            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
                 // first create the intermediate from_ty
                 let deserr_from__ = <#from_ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value(deserr_value__, deserr_location__)?;
                 // then apply the function to it
                 let deserr_final__ = #function_call.map_err(|e| {
                     // then map the error to the final error type
-                    ::deserr::take_cf_content(
-                        <#err_ty as ::deserr::MergeWithError<#function_error_ty>>::merge(None, e, deserr_location__)
-                    )
+                    #map_error
                 })?;
                 #validate
             }
@@ -60,6 +73,8 @@ pub fn generate_derive_from_user_function(
         impl_trait_tokens,
         err_ty,
         validate,
+        default_when_missing,
+        ..
     } = info;
 
     let AttributeFrom {
@@ -69,15 +84,18 @@ pub fn generate_derive_from_user_function(
         ..
     } = from_attr;
 
+    let function = callable_function(&function, &from_ty, is_ref);
     let function_call = if is_ref {
-        quote! { #function (&deserr_from__) }
+        quote! { (#function)(&deserr_from__) }
     } else {
-        quote! { #function (deserr_from__) }
+        quote! { (#function)(deserr_from__) }
     };
 
     quote! {
          #impl_trait_tokens {
-            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::std::result::Result<Self, #err_ty> {
+            #default_when_missing
+
+            fn deserialize_from_value<V: ::deserr::IntoValue>(deserr_value__: ::deserr::Value<V>, deserr_location__: ::deserr::ValuePointerRef) -> ::core::result::Result<Self, #err_ty> {
                 // first create the intermediate from_ty
                 let deserr_from__ = <#from_ty as ::deserr::Deserr<#err_ty>>::deserialize_from_value(deserr_value__, deserr_location__)?;
                 // then apply the function to it
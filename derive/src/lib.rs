@@ -8,60 +8,100 @@ mod derive_user_provided_function;
 mod parse_type;
 
 use attribute_parser::TagType;
-use derive_named_fields::generate_named_fields_impl;
+use derive_named_fields::{generate_named_fields_impl, generate_sequence_fields_impl, MapMode};
 use parse_type::{DerivedTypeInfo, TraitImplementationInfo, VariantData};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
+use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
+/// The error of an enum with data that has neither the `tag` nor the `untagged` attribute
+const EXTERNALLY_TAGGED_ENUM_ERROR: &str = r#"Externally tagged enums are not supported yet by deserr. Add #[deserr(tag = "some_tag_key")]"#;
+
 #[proc_macro_derive(Deserr, attributes(deserr, serde))]
 pub fn derive_deserialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    match DerivedTypeInfo::parse(input) {
-        Ok(derived_type_info) => match derived_type_info.data {
-            TraitImplementationInfo::Struct(fields) => {
-                derive_struct::generate_derive_struct_impl(derived_type_info.common, fields).into()
-            }
-            TraitImplementationInfo::Enum { tag, variants } => match tag {
-                TagType::Internal(tag_key) => derive_enum::generate_derive_tagged_enum_impl(
+    let derived_type_info = match DerivedTypeInfo::parse(input) {
+        Ok(derived_type_info) => derived_type_info,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    // The `TryFrom<serde_json::Value>` and `KnownFields` implementations are generated aside
+    let try_from_json = derived_type_info.common.try_from_json.clone();
+    let known_fields = derived_type_info.common.known_fields.clone();
+
+    let deserr_impl: proc_macro2::TokenStream = match derived_type_info.data {
+        TraitImplementationInfo::Struct(fields) => {
+            derive_struct::generate_derive_struct_impl(derived_type_info.common, fields)
+        }
+        TraitImplementationInfo::Enum {
+            tag,
+            content,
+            unknown_key,
+            variants,
+            case_insensitive,
+        } => match tag {
+            TagType::Internal(tag_key) => match content {
+                Some(content_key) => derive_enum::generate_derive_adjacently_tagged_enum_impl(
                     derived_type_info.common,
                     tag_key,
+                    content_key,
+                    unknown_key,
                     variants,
-                )
-                .into(),
-                TagType::External
-                    if variants
-                        .iter()
-                        .all(|variant| matches!(variant.data, VariantData::Unit)) =>
-                {
-                    derive_enum::generate_derive_untagged_enum_impl(
-                        derived_type_info.common,
-                        variants,
-                    )
-                    .into()
-                }
-                TagType::External =>
-                    syn::Error::new(
-                        Span::call_site(),
-                        r#"Externally tagged enums are not supported yet by deserr. Add #[deserr(tag = "some_tag_key")]"#,
-                ).to_compile_error().into()
+                    case_insensitive,
+                ),
+                None => derive_enum::generate_derive_tagged_enum_impl(
+                    derived_type_info.common,
+                    tag_key,
+                    variants,
+                    case_insensitive,
+                ),
             },
-            TraitImplementationInfo::UnfallibleUserProvidedFunction { from_attr } => {
-                derive_user_provided_function::generate_derive_from_user_function(
+            TagType::External
+                if variants
+                    .iter()
+                    .all(|variant| matches!(variant.data, VariantData::Unit)) =>
+            {
+                derive_enum::generate_derive_unit_enum_impl(
                     derived_type_info.common,
-                    from_attr,
+                    variants,
+                    case_insensitive,
                 )
-                .into()
             }
-            TraitImplementationInfo::FallibleUserProvidedFunction { try_from_attr } => {
-                derive_user_provided_function::generate_derive_try_from_user_function(
-                    derived_type_info.common,
-                    try_from_attr,
-                )
-                .into()
+            TagType::Untagged => {
+                derive_enum::generate_derive_untagged_enum_impl(derived_type_info.common, variants)
+            }
+            TagType::External => {
+                syn::Error::new(Span::call_site(), EXTERNALLY_TAGGED_ENUM_ERROR).to_compile_error()
             }
         },
-        Err(e) => e.to_compile_error().into(),
+        TraitImplementationInfo::Transparent { member, field_ty } => {
+            derive_struct::generate_derive_transparent_impl(
+                derived_type_info.common,
+                member,
+                field_ty,
+            )
+        }
+        TraitImplementationInfo::UnfallibleUserProvidedFunction { from_attr } => {
+            derive_user_provided_function::generate_derive_from_user_function(
+                derived_type_info.common,
+                from_attr,
+            )
+        }
+        TraitImplementationInfo::FallibleUserProvidedFunction {
+            try_from_attr,
+            try_from_error,
+        } => derive_user_provided_function::generate_derive_try_from_user_function(
+            derived_type_info.common,
+            try_from_attr,
+            try_from_error,
+        ),
+    };
+
+    quote! {
+        #deserr_impl
+        #try_from_json
+        #known_fields
     }
+    .into()
 }
@@ -7,9 +7,9 @@ use crate::attribute_parser::{
 use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
 use proc_macro2::{Ident, Span};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::spanned::Spanned;
-use syn::{parse_quote, Data, DeriveInput, WherePredicate};
+use syn::{parse_quote, punctuated::Punctuated, Data, DeriveInput, Token, WherePredicate};
 
 /// Contains all the information needed to generate a
 /// `Deserr` implementation for the derived type,
@@ -35,6 +35,35 @@ pub struct CommonDerivedTypeInfo {
     pub err_ty: syn::Type,
 
     pub validate: TokenStream,
+    /// The implementation of `Deserr::default_when_missing` given by the `default` attribute,
+    /// or an empty token stream.
+    pub default_when_missing: TokenStream,
+    /// The companion struct to generate, if the `partial` attribute was used.
+    pub partial: Option<PartialTypeInfo>,
+    /// The implementation of `TryFrom<serde_json::Value>` given by the `try_from_json`
+    /// attribute, or an empty token stream.
+    pub try_from_json: TokenStream,
+    /// The implementation of `KnownFields` given by the `known_fields` attribute, or an
+    /// empty token stream.
+    pub known_fields: TokenStream,
+}
+
+/// Contains the information needed to generate the companion struct of the `partial`
+/// attribute, whose fields are the optional fields of the derived struct.
+pub struct PartialTypeInfo {
+    /// The identifier of the companion struct, e.g. `PartialSearch` for `Search`
+    pub ident: Ident,
+    /// The visibility of the derived struct, which is also given to the companion struct
+    pub vis: syn::Visibility,
+    /// The traits to derive on the companion struct, given by `partial(derive(..))`
+    pub derives: Vec<syn::Path>,
+    /// The original generics and where clause of the derived struct
+    pub generics: syn::Generics,
+    /// The identifier of the derived struct, which is updated by the companion struct
+    pub base_ident: Ident,
+    /// A token stream representing the `impl<..> Deserr for #ident .. where ..` line
+    /// of the companion struct.
+    pub impl_trait_tokens: TokenStream,
 }
 
 /// The subset of [`DerivedTypeInfo`] that contains information
@@ -44,14 +73,29 @@ pub enum TraitImplementationInfo {
     Struct(NamedFieldsInfo),
     Enum {
         tag: TagType,
+        /// The key of the content of the variants, when the enum is adjacently tagged
+        content: Option<String>,
+        /// The code handling the keys of an adjacently tagged enum that are neither its tag
+        /// nor its content, see [`unknown_key_handler`]
+        unknown_key: TokenStream,
         variants: Vec<VariantInfo>,
+        /// Whether the variants are matched while ignoring the ASCII case
+        case_insensitive: bool,
     },
     FallibleUserProvidedFunction {
         try_from_attr: AttributeTryFrom,
+        /// The function converting the error of the `try_from` function, if any
+        try_from_error: Option<syn::ExprPath>,
     },
     UnfallibleUserProvidedFunction {
         from_attr: AttributeFrom,
     },
+    /// The struct has the `transparent` attribute, and is deserialized as its single field
+    Transparent {
+        /// The name or index of the field
+        member: syn::Member,
+        field_ty: syn::Type,
+    },
 }
 
 /// Contains all the information needed to generate the deserialization code
@@ -79,6 +123,11 @@ pub enum VariantData {
 
     /// The variant is a variant with named fields, such as `Position { line: usize, col: usize }`
     Named(NamedFieldsInfo),
+
+    /// The variant wraps a single unnamed field, such as `Some(T)`.
+    ///
+    /// It isn't supported by the externally tagged enums.
+    Newtype(syn::Type),
 }
 
 impl DerivedTypeInfo {
@@ -102,6 +151,7 @@ impl DerivedTypeInfo {
             // is, we just call the provided function to deserialise it
             TraitImplementationInfo::FallibleUserProvidedFunction {
                 try_from_attr: try_from.clone(),
+                try_from_error: attrs.try_from_error.clone(),
             }
         } else if let Some(from) = &attrs.from {
             // if there was a container `from` attribute, then it doesn't matter what the derived input
@@ -109,18 +159,53 @@ impl DerivedTypeInfo {
             TraitImplementationInfo::UnfallibleUserProvidedFunction {
                 from_attr: from.clone(),
             }
+        } else if attrs.transparent {
+            // the attribute was validated, so the container is a struct with a single field
+            let Data::Struct(s) = &input.data else {
+                unreachable!()
+            };
+            let field = s.fields.iter().next().unwrap();
+            TraitImplementationInfo::Transparent {
+                member: match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(0.into()),
+                },
+                field_ty: field.ty.clone(),
+            }
         } else {
             // Otherwise, we parse derive information specific to structs or enums
             match input.data {
                 Data::Struct(s) => match s.fields {
                     syn::Fields::Named(fields) => TraitImplementationInfo::Struct(
-                        NamedFieldsInfo::parse(fields, &attrs, &err_ty)?,
+                        NamedFieldsInfo::parse(fields.named, &attrs, &err_ty)?,
                     ),
                     syn::Fields::Unnamed(fields) => {
-                        return Err(syn::Error::new(
-                            fields.span(),
-                            "Tuple structs aren't supported by the Deserr derive macro",
-                        ))
+                        if attrs.partial {
+                            return Err(syn::Error::new(
+                                fields.span(),
+                                "The `partial` attribute can't be used on tuple structs",
+                            ));
+                        }
+                        if attrs.deny_unknown_fields.is_some() {
+                            return Err(syn::Error::new(
+                                fields.span(),
+                                "The `deny_unknown_fields` attribute can't be used on tuple structs",
+                            ));
+                        }
+                        if fields.unnamed.len() == 1 && !attrs.from_sequence {
+                            // a newtype is deserialized as its single field
+                            TraitImplementationInfo::Transparent {
+                                member: syn::Member::Unnamed(0.into()),
+                                field_ty: fields.unnamed[0].ty.clone(),
+                            }
+                        } else {
+                            // the other tuple structs are deserialized from a sequence
+                            TraitImplementationInfo::Struct(NamedFieldsInfo::parse(
+                                fields.unnamed,
+                                &attrs,
+                                &err_ty,
+                            )?)
+                        }
                     }
                     syn::Fields::Unit => {
                         return Err(syn::Error::new(
@@ -151,7 +236,10 @@ impl DerivedTypeInfo {
                         // Parse derive info for the content of the variants
                         let data = match variant.fields {
                         syn::Fields::Named(fields) => {
-                            VariantData::Named(NamedFieldsInfo::parse(fields, &effective_container_attrs, &err_ty)?)
+                            VariantData::Named(NamedFieldsInfo::parse(fields.named, &effective_container_attrs, &err_ty)?)
+                        }
+                        syn::Fields::Unnamed(u) if !matches!(attrs.tag, TagType::External) && u.unnamed.len() == 1 => {
+                            VariantData::Newtype(u.unnamed[0].ty.clone())
                         }
                         syn::Fields::Unnamed(u) => return Err(syn::Error::new(
                             u.span(),
@@ -165,9 +253,21 @@ impl DerivedTypeInfo {
                             data,
                         });
                     }
+                    // The keys of an adjacently tagged enum are its tag and its content
+                    let unknown_key = match (&attrs.tag, &attrs.content) {
+                        (TagType::Internal(tag), Some(content)) => unknown_key_handler(
+                            &attrs.deny_unknown_fields,
+                            &err_ty,
+                            &[tag.clone(), content.clone()],
+                        ),
+                        _ => TokenStream::new(),
+                    };
                     TraitImplementationInfo::Enum {
                         tag: attrs.tag,
+                        content: attrs.content,
+                        unknown_key,
                         variants: parsed_variants,
+                        case_insensitive: attrs.case_insensitive,
                     }
                 }
                 Data::Union(u) => {
@@ -186,7 +286,7 @@ impl DerivedTypeInfo {
         // ```
         // The generics and where clause are given by the original generics and where clause of the derived type,
         // with the additional requirement that each generic parameter implements `Deserr<err_ty>`
-        let impl_trait_tokens = {
+        let (impl_trait_tokens, partial, try_from_json, known_fields) = {
             // The goal of creating these simple bindings is to be able to reference them in a quote! macro
             let ident = input.ident;
 
@@ -218,10 +318,16 @@ impl DerivedTypeInfo {
                         ))
                     }
                 }
-                TraitImplementationInfo::Enum { variants, .. } => {
+                TraitImplementationInfo::Enum { tag, variants, .. } => {
+                    // The error of an untagged enum describes the error of each of its variants
+                    if matches!(tag, TagType::Untagged) {
+                        new_predicates.push(parse_quote!(
+                            #err_ty : ::core::fmt::Display
+                        ));
+                    }
                     for variant in variants {
                         match &variant.data {
-                            VariantData::Unit => continue,
+                            VariantData::Unit | VariantData::Newtype(_) => continue,
                             VariantData::Named(variant_info) => {
                                 for field_from_error in
                                     variant_info.field_from_errors.iter().flatten()
@@ -236,10 +342,12 @@ impl DerivedTypeInfo {
                 }
                 TraitImplementationInfo::FallibleUserProvidedFunction { .. } => {}
                 TraitImplementationInfo::UnfallibleUserProvidedFunction { .. } => {}
+                TraitImplementationInfo::Transparent { .. } => {}
             }
 
-            // Add MergeWithError<FromFunctionError> requirement
-            if let Some(try_from) = &attrs.try_from {
+            // Add MergeWithError<FromFunctionError> requirement, unless the error is converted
+            // by the `try_from_error` function
+            if let (Some(try_from), None) = (&attrs.try_from, &attrs.try_from_error) {
                 let from_error = &try_from.function.error_ty;
                 new_predicates.push(parse_quote!(
                     #err_ty : ::deserr::MergeWithError<#from_error>
@@ -278,6 +386,9 @@ impl DerivedTypeInfo {
                     TraitImplementationInfo::UnfallibleUserProvidedFunction { .. } => {
                         vec![]
                     }
+                    TraitImplementationInfo::Transparent { field_ty, .. } => {
+                        vec![field_ty.clone()]
+                    }
                 };
                 for field_ty in all_fields_needing_pred {
                     new_predicates.push(parse_quote! {
@@ -306,9 +417,89 @@ impl DerivedTypeInfo {
                 .predicates
                 .extend(attrs.where_predicates.clone());
 
-            quote! {
-                impl #impl_generics ::deserr::Deserr<#err_ty> for #ident #ty_generics #bounded_where_clause
-            }
+            // The companion struct has the same generics and needs the same bounds
+            let partial = attrs.partial.then(|| {
+                let partial_ident = format_ident!("Partial{}", ident);
+                PartialTypeInfo {
+                    impl_trait_tokens: quote! {
+                        impl #impl_generics ::deserr::Deserr<#err_ty> for #partial_ident #ty_generics #bounded_where_clause
+                    },
+                    ident: partial_ident,
+                    vis: input.vis.clone(),
+                    derives: attrs.partial_derives.clone(),
+                    generics: input.generics.clone(),
+                    base_ident: ident.clone(),
+                }
+            });
+
+            // #[deserr(try_from_json)] => deserialize a `serde_json::Value` with the error type of
+            // the `error` attribute, or with `JsonError`
+            let try_from_json = if attrs.try_from_json {
+                let json_err_ty = user_provided_err_ty
+                    .cloned()
+                    .unwrap_or_else(|| parse_quote!(::deserr::errors::JsonError));
+                let mut generics = input.generics.clone();
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(Self : ::deserr::Deserr<#json_err_ty>));
+                let (impl_generics, _, where_clause) = generics.split_for_impl();
+                quote! {
+                    impl #impl_generics ::core::convert::TryFrom<::deserr::serde_json_crate::Value> for #ident #ty_generics #where_clause {
+                        type Error = #json_err_ty;
+
+                        fn try_from(deserr_value__: ::deserr::serde_json_crate::Value) -> ::core::result::Result<Self, Self::Error> {
+                            ::deserr::deserialize::<Self, _, #json_err_ty>(deserr_value__)
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            // #[deserr(known_fields)] => list the keys of the fields and their aliases, followed
+            // by the keys of the flattened field
+            let known_fields = match &data {
+                TraitImplementationInfo::Struct(fields) if attrs.known_fields => {
+                    let keys =
+                        fields.key_names.iter().zip(&fields.aliases).flat_map(
+                            |(key_name, aliases)| std::iter::once(key_name).chain(aliases),
+                        );
+                    let mut generics = input.generics.clone();
+                    let known_fields = match &fields.flattened_ty {
+                        Some(flattened_ty) => {
+                            generics
+                                .make_where_clause()
+                                .predicates
+                                .push(parse_quote!(#flattened_ty : ::deserr::KnownFields));
+                            quote! {
+                                let mut deserr_known_fields__ = ::deserr::alloc_crate::vec![#(#keys),*];
+                                deserr_known_fields__.extend(<#flattened_ty as ::deserr::KnownFields>::known_fields());
+                                deserr_known_fields__
+                            }
+                        }
+                        None => quote! { ::deserr::alloc_crate::vec![#(#keys),*] },
+                    };
+                    let (impl_generics, _, where_clause) = generics.split_for_impl();
+                    quote! {
+                        impl #impl_generics ::deserr::KnownFields for #ident #ty_generics #where_clause {
+                            fn known_fields() -> ::deserr::alloc_crate::vec::Vec<&'static str> {
+                                #known_fields
+                            }
+                        }
+                    }
+                }
+                _ => quote! {},
+            };
+
+            (
+                quote! {
+                    impl #impl_generics ::deserr::Deserr<#err_ty> for #ident #ty_generics #bounded_where_clause
+                },
+                partial,
+                try_from_json,
+                known_fields,
+            )
         };
 
         let validate = if let Some(validate_func) = attrs.validate {
@@ -331,11 +522,32 @@ impl DerivedTypeInfo {
             quote! { Ok(deserr_final__) }
         };
 
+        let default_when_missing = match attrs.default {
+            Some(default) => {
+                let default = match default {
+                    DefaultFieldAttribute::DefaultTrait => {
+                        quote! { ::core::default::Default::default() }
+                    }
+                    DefaultFieldAttribute::Function(expr) => quote! { #expr },
+                };
+                quote! {
+                    fn default_when_missing() -> ::core::option::Option<Self> {
+                        ::core::option::Option::Some(#default)
+                    }
+                }
+            }
+            None => quote! {},
+        };
+
         Ok(Self {
             common: CommonDerivedTypeInfo {
                 impl_trait_tokens,
                 err_ty,
                 validate,
+                default_when_missing,
+                partial,
+                try_from_json,
+                known_fields,
             },
             data,
         })
@@ -359,45 +571,123 @@ impl DerivedTypeInfo {
 /// ```
 #[derive(Debug)]
 pub struct NamedFieldsInfo {
-    pub field_names: Vec<syn::Ident>,
+    pub field_names: Vec<syn::Member>,
+    /// The local variables holding the state of each field during the deserialization.
+    ///
+    /// They don't use the name of the field to avoid shadowing the items used by the
+    /// user-provided expressions, such as the `default` ones.
+    pub field_vars: Vec<syn::Ident>,
     pub field_tys: Vec<syn::Type>,
-    pub field_defaults: Vec<TokenStream>,
     pub field_errs: Vec<syn::Type>,
 
     pub field_from_fns: Vec<TokenStream>,
     pub field_from_errors: Vec<Option<syn::Type>>,
 
     pub field_maps: Vec<TokenStream>,
-    pub missing_field_errors: Vec<TokenStream>,
+    /// The code to run after the whole map was traversed if the field is still missing.
+    ///
+    /// It either evaluates the default value of the field, or accumulates a missing field error.
+    pub missing_field_handlers: Vec<TokenStream>,
+    /// The key of each field, influenced by the `rename` and `rename_all` attributes.
     pub key_names: Vec<String>,
+    /// The aliases of each field, given by the `alias` and `alias_all` attributes.
+    pub aliases: Vec<Vec<String>>,
+    /// The patterns matching the key of each field, i.e. its key name and its aliases.
+    pub key_patterns: Vec<TokenStream>,
+    /// The code to run when the key of a field is found, before deserializing it.
+    ///
+    /// It returns an error if the field was already given under one of its aliases.
+    pub duplicate_key_checks: Vec<TokenStream>,
+    /// The local variables holding the first key found for each field that has aliases.
+    pub alias_key_vars: Vec<syn::Ident>,
+    /// The code to run when the key of a field is found, to report that it is deprecated.
+    ///
+    /// It is empty for the fields without the `deprecated` attribute.
+    pub deprecation_warnings: Vec<TokenStream>,
+    /// Whether each field has the `empty_string_as_none` attribute, in which case an empty
+    /// string is deserialized like `null`.
+    pub empty_string_as_none: Vec<bool>,
+    /// Whether each field has the `null_is_none` attribute, in which case an explicit `null`
+    /// is handled like a missing field.
+    pub null_is_none: Vec<bool>,
+    /// Whether each field has the `double_option` attribute, in which case an explicit `null`
+    /// is deserialized as `Some(None)`.
+    pub double_option: Vec<bool>,
+    /// Whether each field has the `deny_null` attribute, in which case an explicit `null`
+    /// is refused by the `T` of its `Option<T>`.
+    pub deny_null: Vec<bool>,
+    /// Whether each field has the `saturating` attribute, in which case the out-of-range
+    /// integers are clamped to the bounds of its type.
+    pub saturating: Vec<bool>,
 
     pub needs_predicate: Vec<bool>,
     /// A token stream representing the code to handle an unknown field key.
     ///
-    /// It is relevant to the `deny_unknown_fields` attribute.
+    /// It is relevant to the `deny_unknown_fields` and `flatten` attributes.
     pub unknown_key: TokenStream,
+    /// Whether a field has the `flatten` attribute, in which case the unknown keys
+    /// are collected in `deserr_flattened__` instead of being handled by `unknown_key`.
+    pub has_flattened_field: bool,
+    /// The type of the field with the `flatten` attribute, if there is one.
+    pub flattened_ty: Option<syn::Type>,
+    /// Whether the struct has the `from_sequence` attribute, in which case the fields are
+    /// deserialized from the elements of a sequence instead of the entries of a map.
+    pub from_sequence: bool,
+    /// Whether the fields are the unnamed fields of a tuple struct, in which case they are
+    /// always deserialized from a sequence that must contain exactly one element per field.
+    pub tuple_struct: bool,
+    /// The default value of the struct, given by its `fill_from_default` attribute, if one of
+    /// its missing fields takes its value in it.
+    pub container_default: Option<TokenStream>,
+    /// The declaration of each field in the companion struct of the `partial` attribute,
+    /// e.g. `pub limit: Option<usize>`. The skipped fields are not included.
+    pub partial_field_decls: Vec<TokenStream>,
 }
 
 impl NamedFieldsInfo {
+    /// Parse the named fields of a struct or of an enum variant, or the unnamed fields of a
+    /// tuple struct, which are then named by their index.
     fn parse(
-        fields: syn::FieldsNamed,
+        fields: Punctuated<syn::Field, Token![,]>,
         data_attrs: &ContainerAttributesInfo,
         err_ty: &syn::Type,
     ) -> syn::Result<Self> {
         // the identifier of the field
         let mut field_names = vec![];
+        // the local variable holding the state of the field
+        let mut field_vars = vec![];
         // the type of the field or the type of the `from` if there was one
         let mut field_tys = vec![];
         // the key (in the serialised value) corresponding to the field
         // influenced by the `rename` and `rename_all` attributes
         let mut key_names = vec![];
-        // the token stream that give the optional value of the field when its key is missing
-        // influenced by the `default` attribute
+        // the aliases of the field, influenced by the `alias` and `alias_all` attributes
+        let mut field_aliases = vec![];
+        // the patterns matching the key name and the aliases of the field
+        let mut key_patterns = vec![];
+        // the code checking that a field with aliases is only given once
+        let mut duplicate_key_checks = vec![];
+        let mut alias_key_vars = vec![];
+        // the code reporting a warning when a field with the `deprecated` attribute is given
+        let mut deprecation_warnings = vec![];
+        // `true` iff the field has the empty_string_as_none attribute
+        let mut empty_string_as_none = vec![];
+        // `true` iff the field has the null_is_none attribute
+        let mut null_is_none = vec![];
+        // `true` iff the field has the double_option attribute
+        let mut double_option = vec![];
+        // `true` iff the field has the deny_null attribute
+        let mut deny_null = vec![];
+        // `true` iff the field has the saturating attribute
+        let mut saturating = vec![];
+        // the optional expression giving the value of the field when its key is missing
+        // influenced by the `default` and `skip` attributes
         let mut field_defaults = vec![];
         // the type of the error used to deserialize the field
         let mut field_errs = vec![];
-        // the token stream representing the error to return when the field is missing and has no default value
-        let mut missing_field_errors = vec![];
+        // the token stream either evaluating the default value of the field or returning
+        // an error when the field is missing
+        let mut missing_field_handlers = vec![];
         // an Option of token stream which maps the deserialised field value from one type to another
         let mut field_from_fns = vec![];
         // The list of error types that can be returned by the `from` clauses
@@ -406,40 +696,95 @@ impl NamedFieldsInfo {
         let mut field_maps = vec![];
         // `true` iff the field has the needs_predicate attribute
         let mut needs_predicate = vec![];
+        // the declaration of the field in the companion struct of the `partial` attribute
+        let mut partial_field_decls = vec![];
+        // `true` iff a missing field takes its value in the default value of the struct
+        let mut uses_container_default = false;
 
+        let tuple_struct = fields.iter().any(|field| field.ident.is_none());
         let mut fields_extra = fields
-            .named
             .into_iter()
-            .map(|field| {
+            .enumerate()
+            .map(|(index, field)| {
                 let attrs = read_deserr_field_attributes(&field.attrs)?;
-                Ok((field, attrs))
+                let member = match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(index.into()),
+                };
+                Ok((field, attrs, member))
             })
             .collect::<Result<Vec<_>, syn::Error>>()?;
 
-        // We put all the non-skipped fields at the beginning, so that when we iterate
-        // over the non-skipped key names, we can access their corresponding field names
+        // We put all the non-skipped and non-flattened fields at the beginning, so that when
+        // we iterate over the key names, we can access their corresponding field names
         // using the same index.
-        fields_extra.sort_by_key(|x| x.1.skipped);
+        fields_extra.sort_by_key(|x| x.1.skipped || x.1.flatten);
+
+        // The leftover entries can't be given to more than one field
+        let mut flattened_fields = fields_extra.iter().filter(|x| x.1.flatten);
+        let flattened_ty = flattened_fields.next().map(|x| x.0.ty.clone());
+        let has_flattened_field = flattened_ty.is_some();
+        if let Some((field, _, _)) = flattened_fields.next() {
+            return Err(syn::Error::new(
+                field.span(),
+                "The `flatten` attribute can only be used on a single field",
+            ));
+        }
+        // The outer struct can't know which keys are accepted by the flattened field,
+        // so it's the flattened type that decides whether the unknown keys are denied.
+        if has_flattened_field && data_attrs.deny_unknown_fields.is_some() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Cannot use the `deny_unknown_fields` attribute together with a `flatten` field. \
+                 Use `deny_unknown_fields` on the type of the flattened field instead",
+            ));
+        }
+        if has_flattened_field && data_attrs.partial {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Cannot use the `partial` attribute together with a `flatten` field",
+            ));
+        }
+        if has_flattened_field && data_attrs.from_sequence {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Cannot use the `from_sequence` attribute together with a `flatten` field",
+            ));
+        }
+        if has_flattened_field && tuple_struct {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "The `flatten` attribute can't be used on the fields of a tuple struct",
+            ));
+        }
 
-        for (field, attrs) in fields_extra.iter() {
-            let field_name = field.ident.clone().unwrap();
+        for (field, attrs, field_name) in fields_extra.iter() {
             let field_ty = &field.ty;
 
+            // The default value is only evaluated if the field is missing, see `missing_field_handlers`
             let field_default = if let Some(default) = &attrs.default {
                 match default {
                     // #[deserr(default)] => use the Default trait
                     DefaultFieldAttribute::DefaultTrait => {
-                        quote! { ::deserr::FieldState::Some(::std::default::Default::default()) }
+                        Some(quote! { ::core::default::Default::default() })
                     }
                     // #[deserr(default = expr)] => use the given expression
-                    DefaultFieldAttribute::Function(expr) => {
-                        quote! { ::deserr::FieldState::Some(#expr) }
-                    }
+                    DefaultFieldAttribute::Function(expr) => Some(quote! { #expr }),
                 }
+            } else if attrs.default_from.is_some() {
+                // #[deserr(default_from = field)] => see `default_from_handler`
+                None
+            } else if data_attrs.fill_from_default.is_some() && !attrs.flatten {
+                // #[deserr(fill_from_default)] on the struct => take the field of its default value
+                uses_container_default = true;
+                Some(quote! { deserr_default__.#field_name })
             } else if attrs.skipped {
-                quote! { ::deserr::FieldState::Some(::std::default::Default::default()) }
+                Some(quote! { ::core::default::Default::default() })
+            } else if attrs.double_option || attrs.deny_null {
+                // #[deserr(double_option)] or #[deserr(deny_null)] => a missing field is `None`
+                Some(quote! { ::core::option::Option::None })
             } else {
-                quote! { ::deserr::FieldState::Missing }
+                None
             };
 
             let field_ty = match (&attrs.try_from, &attrs.from) {
@@ -450,34 +795,77 @@ impl NamedFieldsInfo {
                 (None, None) => field_ty.clone(),
             };
 
-            let field_map = match &attrs.map {
-                Some(func) => {
+            let field_map = match (&attrs.map, attrs.dedup) {
+                (Some(func), false) => {
                     quote! {
                         #func
                     }
                 }
-                None => {
-                    quote! { ::std::convert::identity }
+                // #[deserr(dedup)] => the duplicates are removed before the value is mapped
+                (Some(func), true) => {
+                    quote! { |x| (#func)(::deserr::dedup(x)) }
+                }
+                (None, true) => {
+                    quote! { ::deserr::dedup }
+                }
+                (None, false) => {
+                    quote! { ::core::convert::identity }
                 }
             };
 
-            field_names.push(field_name);
+            field_vars.push(format_ident!("deserr_field_{}__", field_name));
+            field_names.push(field_name.clone());
             field_tys.push(field_ty.clone());
             field_defaults.push(field_default);
             field_maps.push(field_map);
             needs_predicate.push(attrs.needs_predicate);
         }
 
-        for (field, attrs) in fields_extra.into_iter().filter(|x| !x.1.skipped) {
+        for (index, ((field, attrs, field_name), field_default)) in
+            fields_extra.into_iter().zip(field_defaults).enumerate()
+        {
             let field_ty = &field.ty;
-            let field_name = field.ident.clone().unwrap();
+            let field_var = format_ident!("deserr_field_{}__", field_name);
+
+            // #[deserr(default_from = field)] => a missing field takes a copy of the final value
+            // of the other field, whose missing field handler already ran. This field can't
+            // have a conversion or a map of its own, so its state holds the type of the field.
+            let default_from_handler = match &attrs.default_from {
+                Some(other) => {
+                    let Some(other_index) =
+                        field_names[..index].iter().position(|name| name == other)
+                    else {
+                        return Err(syn::Error::new_spanned(
+                            other,
+                            "The `default_from` attribute must name a field declared before this one. \
+                             A skipped or flattened field can only be named by a skipped field",
+                        ));
+                    };
+                    let other_var = &field_vars[other_index];
+                    let other_map = &field_maps[other_index];
+                    Some(quote! {
+                        #field_var = match &#other_var {
+                            ::deserr::FieldState::Some(x) => ::deserr::FieldState::Some((#other_map)(::core::clone::Clone::clone(x))),
+                            // the error of the other field was already reported
+                            _ => ::deserr::FieldState::Err,
+                        };
+                    })
+                }
+                None => None,
+            };
+
+            if let (true, Some(default_from_handler)) = (attrs.skipped, &default_from_handler) {
+                missing_field_handlers.push(default_from_handler.clone());
+                continue;
+            }
+            if attrs.skipped {
+                // a skipped field is never in the map, so it always uses its default value
+                missing_field_handlers.push(quote! {
+                    #field_var = ::deserr::FieldState::Some(#field_default);
+                });
+                continue;
+            }
 
-            let renamed = attrs.rename.as_ref().map(|i| i.value());
-            let key_name = key_name_for_ident(
-                field_name.to_string(),
-                data_attrs.rename_all.as_ref(),
-                renamed.as_deref(),
-            );
             let error = match attrs.error {
                 Some(error) => error,
                 None => data_attrs
@@ -486,6 +874,50 @@ impl NamedFieldsInfo {
                     .unwrap_or_else(|| parse_quote!(__Deserr_E)),
             };
 
+            if attrs.flatten {
+                // a flattened field has no key, it is deserialized from the leftover entries once
+                // the whole map was traversed
+                missing_field_handlers.push(quote! {
+                    #field_var = match <#field_ty as ::deserr::Deserr<#error>>::deserialize_from_value(
+                        ::deserr::IntoValue::into_value(::deserr::FlattenedValue::Leftovers(deserr_flattened__)),
+                        deserr_location__
+                    ) {
+                        ::core::result::Result::Ok(x) => ::deserr::FieldState::Some(x),
+                        ::core::result::Result::Err(e) => {
+                            deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
+                                deserr_error__,
+                                e,
+                                deserr_location__
+                            ) {
+                                ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                                ::core::ops::ControlFlow::Break(e) => return ::core::result::Result::Err(e),
+                            };
+                            ::deserr::FieldState::Err
+                        }
+                    };
+                });
+                continue;
+            }
+
+            let field_vis = &field.vis;
+            let field_docs = field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc"));
+            partial_field_decls.push(quote! {
+                #(#field_docs)*
+                #field_vis #field_name : ::core::option::Option<#field_ty>
+            });
+
+            let renamed = attrs.rename.as_ref().map(|i| i.value());
+            let key_name = key_name_for_ident(
+                match &field_name {
+                    syn::Member::Named(ident) => ident.to_string(),
+                    syn::Member::Unnamed(index) => index.index.to_string(),
+                },
+                data_attrs.rename_all.as_ref(),
+                renamed.as_deref(),
+            );
             let field_ty = match (&attrs.try_from, &attrs.from) {
                 (Some(_), Some(_)) => unreachable!("Can't use a try_for + a for together."),
                 (Some(try_from), _) => try_from.try_from_ty.clone(),
@@ -496,42 +928,52 @@ impl NamedFieldsInfo {
             let field_from_fn = match (&attrs.try_from, &attrs.from) {
                 (Some(_), Some(_)) => unreachable!("Can't use a try_for + a for together."),
                 (Some(try_from), _) => {
-                    let fun = &try_from.function.function;
+                    let fun =
+                        callable_function(&try_from.function.function, &field_ty, try_from.is_ref);
                     let fun_call = if try_from.is_ref {
-                        quote! { |val: #field_ty | #fun(&val) }
+                        quote! { |val: #field_ty | (#fun)(&val) }
                     } else {
                         quote! { #fun }
                     };
 
+                    let convert_error = match &attrs.try_from_error {
+                        Some(try_from_error) => quote! {
+                            let tmp_deserr_error__: #error = #try_from_error(e, deserr_value_location__);
+                        },
+                        None => quote! {
+                            let tmp_deserr_error__ = match <#error as ::deserr::MergeWithError<_>>::merge(
+                                None,
+                                e,
+                                deserr_value_location__
+                            ) {
+                                ::core::ops::ControlFlow::Continue(e) => e,
+                                ::core::ops::ControlFlow::Break(e) => {
+                                    return ::core::result::Result::Err(
+                                        ::deserr::take_cf_content(<#err_ty as ::deserr::MergeWithError<_>>::merge(
+                                            deserr_error__,
+                                            e,
+                                            deserr_value_location__
+                                        ))
+                                    )
+                                }
+                            };
+                        },
+                    };
+
                     quote!(
                         match (#fun_call)(x) {
-                            ::std::result::Result::Ok(x) => {
+                            ::core::result::Result::Ok(x) => {
                                 ::deserr::FieldState::Some(x)
                             }
-                            ::std::result::Result::Err(e) => {
-                                let tmp_deserr_error__ = match <#error as ::deserr::MergeWithError<_>>::merge(
-                                    None,
-                                    e,
-                                    deserr_location__.push_key(deserr_key__.as_str())
-                                ) {
-                                    ::std::ops::ControlFlow::Continue(e) => e,
-                                    ::std::ops::ControlFlow::Break(e) => {
-                                        return ::std::result::Result::Err(
-                                            ::deserr::take_cf_content(<#err_ty as ::deserr::MergeWithError<_>>::merge(
-                                                deserr_error__,
-                                                e,
-                                                deserr_location__.push_key(deserr_key__.as_str())
-                                            ))
-                                        )
-                                    }
-                                };
+                            ::core::result::Result::Err(e) => {
+                                #convert_error
                                 deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
                                     deserr_error__,
                                     tmp_deserr_error__,
-                                    deserr_location__.push_key(deserr_key__.as_str())
+                                    deserr_value_location__
                                 ) {
-                                    ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                                    ::std::ops::ControlFlow::Break(e) => return ::std::result::Result::Err(e),
+                                    ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                                    ::core::ops::ControlFlow::Break(e) => return ::core::result::Result::Err(e),
                                 };
                                 ::deserr::FieldState::Err
                             }
@@ -539,9 +981,9 @@ impl NamedFieldsInfo {
                     )
                 }
                 (_, Some(from)) => {
-                    let fun = &from.function;
+                    let fun = callable_function(&from.function, &field_ty, from.is_ref);
                     let fun_call = if from.is_ref {
-                        quote! { |val: #field_ty | #fun(&val) }
+                        quote! { |val: #field_ty | (#fun)(&val) }
                     } else {
                         quote! { #fun }
                     };
@@ -553,109 +995,315 @@ impl NamedFieldsInfo {
                 },
             };
 
-            let field_from_error = attrs
-                .try_from
-                .as_ref()
-                .map(|from| from.function.error_ty.clone());
+            // The constraints on the deserialized value, which are checked before the `from`
+            // and `try_from` functions are called
+            let mut constraints = vec![];
+            if let Some(range) = &attrs.range {
+                constraints.push(range_constraint(range));
+            }
+            if let Some(min_len) = &attrs.min_len {
+                constraints.push((
+                    quote! { x.chars().count() >= #min_len },
+                    quote! {
+                        ::deserr::alloc_crate::format!("expected at least {} characters, but found {}", #min_len, x.chars().count())
+                    },
+                ));
+            }
+            if let Some(max_len) = &attrs.max_len {
+                constraints.push((
+                    quote! { x.chars().count() <= #max_len },
+                    quote! {
+                        ::deserr::alloc_crate::format!("expected at most {} characters, but found {}", #max_len, x.chars().count())
+                    },
+                ));
+            }
+            if let Some(pattern) = &attrs.pattern {
+                // The regex is only compiled once, the first time the field is deserialized
+                constraints.push((
+                    quote! {
+                        {
+                            fn deserr_pattern__() -> &'static ::deserr::regex::Regex {
+                                static DESERR_PATTERN__: ::std::sync::OnceLock<::deserr::regex::Regex> = ::std::sync::OnceLock::new();
+                                DESERR_PATTERN__.get_or_init(|| {
+                                    ::deserr::regex::Regex::new(#pattern).expect("The `pattern` attribute is checked by the derive macro")
+                                })
+                            }
+                            deserr_pattern__().is_match(&x)
+                        }
+                    },
+                    quote! {
+                        ::deserr::alloc_crate::format!("expected a string matching the pattern `{}`, but found `{}`", #pattern, x)
+                    },
+                ));
+            }
+            let field_from_fn = if constraints.is_empty() {
+                field_from_fn
+            } else {
+                let (conditions, messages): (Vec<_>, Vec<_>) = constraints.into_iter().unzip();
+                quote! {
+                    // The ranges are checked with comparisons so that their bounds get the type of the field
+                    #[allow(clippy::manual_range_contains)]
+                    let deserr_violation__: ::core::option::Option<::deserr::alloc_crate::string::String> =
+                        #( if !(#conditions) { ::core::option::Option::Some(#messages) } else )*
+                        { ::core::option::Option::None };
+                    match deserr_violation__ {
+                        ::core::option::Option::Some(deserr_msg__) => {
+                            deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<::core::convert::Infallible>(
+                                deserr_error__,
+                                ::deserr::ErrorKind::Unexpected {
+                                    msg: deserr_msg__,
+                                },
+                                deserr_value_location__
+                            ) {
+                                ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                                ::core::ops::ControlFlow::Break(e) => return ::core::result::Result::Err(e),
+                            };
+                            ::deserr::FieldState::Err
+                        }
+                        ::core::option::Option::None => {
+                            #field_from_fn
+                        }
+                    }
+                }
+            };
 
-            let missing_field_error = match &attrs.missing_field_error {
-                Some(error_function) => {
+            // The error of the `try_from` function is merged into the error of the field, unless
+            // it is converted by the `try_from_error` function
+            let field_from_error = match &attrs.try_from_error {
+                Some(_) => None,
+                None => attrs
+                    .try_from
+                    .as_ref()
+                    .map(|from| from.function.error_ty.clone()),
+            };
+
+            // With the `missing_field_policy` attribute, a required field may take its default
+            // value instead of being an error
+            let or_default_missing_field = |missing_field_error: TokenStream| {
+                if data_attrs.missing_field_policy {
+                    quote! {
+                        if ::deserr::default_missing_field(deserr_location__.push_key(#key_name)) {
+                            #field_var = ::deserr::FieldState::Some(::core::default::Default::default());
+                        } else {
+                            #missing_field_error
+                        }
+                    }
+                } else {
+                    missing_field_error
+                }
+            };
+            let missing_field_handler = match (field_default, &attrs.missing_field_error) {
+                _ if default_from_handler.is_some() => default_from_handler.unwrap(),
+                (Some(field_default), _) => {
                     quote! {
+                        #field_var = ::deserr::FieldState::Some(#field_default);
+                    }
+                }
+                (None, Some(error_function)) => or_default_missing_field(quote! {
                         let deserr_e__ = #error_function ( #key_name, deserr_location__ ) ;
                         deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
                             deserr_error__,
                             deserr_e__,
                             deserr_location__
                         ) {
-                            ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                            ::std::ops::ControlFlow::Break(e) => {
-                                return ::std::result::Result::Err(e)
+                            ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                            ::core::ops::ControlFlow::Break(e) => {
+                                return ::core::result::Result::Err(e)
                             }
                         };
-                    }
-                }
-                None => {
-                    quote! {
-                        deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<V>(
+                }),
+                (None, None) => {
+                    let missing_field_error = quote! {
+                        deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<::core::convert::Infallible>(
                             deserr_error__,
                             ::deserr::ErrorKind::MissingField {
                                 field: #key_name,
                             },
                             deserr_location__
                         ) {
-                            ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                            ::std::ops::ControlFlow::Break(e) => {
-                                return ::std::result::Result::Err(e)
+                            ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                            ::core::ops::ControlFlow::Break(e) => {
+                                return ::core::result::Result::Err(e)
                             }
                         };
+                    };
+                    let missing_field_error = or_default_missing_field(missing_field_error);
+                    // The type of the field may provide a default value, unless it is deserialized
+                    // from another type
+                    if attrs.from.is_none() && attrs.try_from.is_none() {
+                        quote! {
+                            match <#field_ty as ::deserr::Deserr<#error>>::default_when_missing() {
+                                ::core::option::Option::Some(x) => {
+                                    #field_var = ::deserr::FieldState::Some(x);
+                                }
+                                ::core::option::Option::None => {
+                                    #missing_field_error
+                                }
+                            }
+                        }
+                    } else {
+                        missing_field_error
                     }
                 }
             };
 
+            let mut aliases = attrs
+                .aliases
+                .iter()
+                .map(|alias| alias.value())
+                .collect::<Vec<_>>();
+            // #[deserr(alias_all = casing)] => the field is also accepted under its name in
+            // this casing, unless it was renamed explicitly
+            if let (Some(alias_all), None, syn::Member::Named(ident)) =
+                (&data_attrs.alias_all, &renamed, &field_name)
+            {
+                let alias = key_name_for_ident(ident.to_string(), Some(alias_all), None);
+                if alias != key_name && !aliases.contains(&alias) {
+                    aliases.push(alias);
+                }
+            }
+            let duplicate_key_check = if aliases.is_empty() {
+                quote! {}
+            } else {
+                let alias_key_var = format_ident!("deserr_key_{}__", field_name);
+                let duplicate_key_check = quote! {
+                    if !#field_var.is_missing() {
+                        deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<::core::convert::Infallible>(
+                            deserr_error__,
+                            ::deserr::ErrorKind::Unexpected {
+                                msg: ::deserr::alloc_crate::format!(
+                                    "the field `{}` is given twice, as `{}` and as `{}`",
+                                    #key_name,
+                                    #alias_key_var,
+                                    deserr_key__
+                                ),
+                            },
+                            deserr_location__
+                        ) {
+                            ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                            ::core::ops::ControlFlow::Break(e) => return ::core::result::Result::Err(e),
+                        };
+                        continue;
+                    }
+                    #alias_key_var = [#key_name, #(#aliases),*]
+                        .into_iter()
+                        .find(|key| *key == deserr_key__)
+                        .unwrap_or_default();
+                };
+                alias_key_vars.push(alias_key_var);
+                duplicate_key_check
+            };
+
+            let deprecation_warning = match &attrs.deprecated {
+                None => quote! {},
+                Some(msg) => {
+                    let msg = match msg {
+                        Some(msg) => {
+                            quote! { ::core::option::Option::Some(::deserr::alloc_crate::string::String::from(#msg)) }
+                        }
+                        None => quote! { ::core::option::Option::None },
+                    };
+                    quote! {
+                        ::deserr::warn(
+                            deserr_value_location__,
+                            ::deserr::WarningKind::Deprecated { msg: #msg },
+                        );
+                    }
+                }
+            };
+
+            key_patterns.push(quote! { #key_name #(| #aliases)* });
+            deprecation_warnings.push(deprecation_warning);
+            empty_string_as_none.push(attrs.empty_string_as_none);
+            null_is_none.push(attrs.null_is_none);
+            double_option.push(attrs.double_option);
+            deny_null.push(attrs.deny_null);
+            saturating.push(attrs.saturating);
+            duplicate_key_checks.push(duplicate_key_check);
             key_names.push(key_name.clone());
+            field_aliases.push(aliases);
             field_errs.push(error);
             field_from_fns.push(field_from_fn);
             field_from_errors.push(field_from_error);
-            missing_field_errors.push(missing_field_error);
+            missing_field_handlers.push(missing_field_handler);
         }
 
-        // Create the token stream representing the code to handle an unknown field key.
-        // By default, we ignore unknown keys, so the token stream is empty.
+        // Create the token stream representing the code to handle an unknown field key, see
+        // `unknown_key_handler`.
         //
-        // If the #[deserr(deny_unknown_fields)] or #[deserr(deny_unknown_fields = func)] attribute exists,
-        // we return an error: either the default error, or an error created by the custom function given by
-        // the user.
-        let unknown_key = match &data_attrs.deny_unknown_fields {
-            Some(DenyUnknownFields::DefaultError) => {
-                // Here we must give as argument the accepted keys
-                quote! {
-                    deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<V>(
-                        deserr_error__,
-                        ::deserr::ErrorKind::UnknownKey {
-                            key: deserr_key__,
-                            accepted: &[#(#key_names),*],
-                        },
-                        deserr_location__
-                    ) {
-                        ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                        ::std::ops::ControlFlow::Break(e) => {
-                            return ::std::result::Result::Err(e)
-                        }
-                    };
-                }
+        // If a field has the #[deserr(flatten)] attribute, the unknown keys are instead kept for later,
+        // to deserialize the flattened field.
+        let unknown_key = if has_flattened_field {
+            quote! {
+                deserr_flattened__.push((::deserr::alloc_crate::string::String::from(deserr_key__), deserr_value__));
             }
-            Some(DenyUnknownFields::Function(func)) => quote! {
-                let deserr_e__ = #func (deserr_key__, &[#(#key_names),*], deserr_location__) ;
-                deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
-                    deserr_error__,
-                    deserr_e__,
-                    deserr_location__,
-                ) {
-                    ::std::ops::ControlFlow::Continue(e) => ::std::option::Option::Some(e),
-                    ::std::ops::ControlFlow::Break(e) => {
-                        return ::std::result::Result::Err(e)
-                    }
-                };
-            },
-            None => quote! {},
+        } else {
+            unknown_key_handler(&data_attrs.deny_unknown_fields, err_ty, &key_names)
+        };
+
+        let container_default = match &data_attrs.fill_from_default {
+            Some(DefaultFieldAttribute::DefaultTrait) if uses_container_default => {
+                Some(quote! { ::core::default::Default::default() })
+            }
+            Some(DefaultFieldAttribute::Function(expr)) if uses_container_default => {
+                Some(quote! { #expr })
+            }
+            _ => None,
         };
 
         Ok(Self {
             field_names,
+            field_vars,
             field_tys,
             key_names,
-            field_defaults,
+            aliases: field_aliases,
+            key_patterns,
+            duplicate_key_checks,
+            alias_key_vars,
+            deprecation_warnings,
+            empty_string_as_none,
+            null_is_none,
+            double_option,
+            deny_null,
+            saturating,
             field_errs,
             field_from_fns,
             field_from_errors,
             field_maps,
             needs_predicate,
-            missing_field_errors,
+            missing_field_handlers,
             unknown_key,
+            has_flattened_field,
+            flattened_ty,
+            container_default,
+            from_sequence: data_attrs.from_sequence || tuple_struct,
+            tuple_struct,
+            partial_field_decls,
         })
     }
 }
 
+/// Return the condition that the deserialized value `x` must satisfy to be contained in
+/// the given range, and the error message used when it doesn't.
+fn range_constraint(range: &syn::ExprRange) -> (TokenStream, TokenStream) {
+    let lower = range.start.as_ref().map(|start| quote! { x >= #start });
+    let upper = range.end.as_ref().map(|end| match range.limits {
+        syn::RangeLimits::HalfOpen(_) => quote! { x < #end },
+        syn::RangeLimits::Closed(_) => quote! { x <= #end },
+    });
+    let condition = match (lower, upper) {
+        (Some(lower), Some(upper)) => quote! { #lower && #upper },
+        (Some(bound), None) | (None, Some(bound)) => bound,
+        (None, None) => quote! { true },
+    };
+    // `1 ..= 65535` is displayed as `1..=65535`
+    let range = quote! { #range }.to_string().replace(' ', "");
+    let message = quote! {
+        ::deserr::alloc_crate::format!("expected a value in the range `{}`, but found `{}`", #range, x)
+    };
+    (condition, message)
+}
+
 /// Transforms the given `ident` string according to the rules of the `rename` and `rename_all` attributes
 fn key_name_for_ident(
     ident: String,
@@ -667,7 +1315,88 @@ fn key_name_for_ident(
         None => match rename_all {
             Some(RenameAll::CamelCase) => ident.to_case(Case::Camel),
             Some(RenameAll::LowerCase) => ident.to_lowercase(),
+            Some(RenameAll::UpperCase) => ident.to_uppercase(),
+            Some(RenameAll::PascalCase) => ident.to_case(Case::Pascal),
+            Some(RenameAll::SnakeCase) => ident.to_case(Case::Snake),
+            Some(RenameAll::ScreamingSnakeCase) => ident.to_case(Case::ScreamingSnake),
+            Some(RenameAll::KebabCase) => ident.to_case(Case::Kebab),
+            Some(RenameAll::ScreamingKebabCase) => ident.to_case(Case::UpperKebab),
             None => ident,
         },
     }
 }
+
+/// Return an expression that calls `function`, the function of a `from` or `try_from`
+/// attribute, with a value of type `from_ty`, or a reference to it if `is_ref` is true.
+///
+/// The type of the parameter of a closure can't be inferred from the call, so the closure is
+/// coerced to a function pointer with the right parameter type.
+pub fn callable_function(function: &syn::Expr, from_ty: &syn::Type, is_ref: bool) -> TokenStream {
+    match function {
+        syn::Expr::Closure(_) => {
+            let param_ty = if is_ref {
+                quote! { &#from_ty }
+            } else {
+                quote! { #from_ty }
+            };
+            quote! {{
+                let deserr_function__: fn(#param_ty) -> _ = #function;
+                deserr_function__
+            }}
+        }
+        function => quote! { #function },
+    }
+}
+
+/// Return the code handling the key `deserr_key__` of a map that isn't one of the given keys,
+/// at the location `deserr_location__` of the map.
+///
+/// By default, the unknown keys are ignored, and only reported by a warning that is dropped
+/// unless the value is deserialized with `deserialize_with_warnings`. With the
+/// `deny_unknown_fields` attribute, they are an error: either the default error, or the error
+/// created by the custom function given by the user.
+pub fn unknown_key_handler(
+    deny_unknown_fields: &Option<DenyUnknownFields>,
+    err_ty: &syn::Type,
+    key_names: &[String],
+) -> TokenStream {
+    match deny_unknown_fields {
+        Some(DenyUnknownFields::DefaultError) => {
+            // Here we must give as argument the accepted keys
+            quote! {
+                deserr_error__ = match <#err_ty as ::deserr::DeserializeError>::error::<::core::convert::Infallible>(
+                    deserr_error__,
+                    ::deserr::ErrorKind::UnknownKey {
+                        key: deserr_key__,
+                        accepted: &[#(#key_names),*],
+                    },
+                    deserr_location__
+                ) {
+                    ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                    ::core::ops::ControlFlow::Break(e) => {
+                        return ::core::result::Result::Err(e)
+                    }
+                };
+            }
+        }
+        Some(DenyUnknownFields::Function(func)) => quote! {
+            let deserr_e__ = #func (deserr_key__, &[#(#key_names),*], deserr_location__) ;
+            deserr_error__ = match <#err_ty as ::deserr::MergeWithError<_>>::merge(
+                deserr_error__,
+                deserr_e__,
+                deserr_location__,
+            ) {
+                ::core::ops::ControlFlow::Continue(e) => ::core::option::Option::Some(e),
+                ::core::ops::ControlFlow::Break(e) => {
+                    return ::core::result::Result::Err(e)
+                }
+            };
+        },
+        None => quote! {
+            ::deserr::warn(
+                deserr_location__.push_key(deserr_key__),
+                ::deserr::WarningKind::UnknownKeyIgnored,
+            );
+        },
+    }
+}
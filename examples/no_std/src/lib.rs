@@ -0,0 +1,95 @@
+//! Derive `Deserr` in a `no_std` crate that only has an allocator.
+//!
+//! It is built for a target without `std` in the CI to make sure deserr, and the code
+//! generated by its derive proc macro, keep working without `std`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, ops::ControlFlow};
+
+use deserr::{
+    deserialize, BufferedValue, DeserializeError, Deserr, ErrorKind, IntoValue, MergeWithError,
+    ValuePointerRef,
+};
+
+#[derive(Debug, Deserr)]
+#[deserr(error = Error, rename_all = camelCase)]
+pub struct Config {
+    #[deserr(min_len = 1, max_len = 32, alias = "name")]
+    pub device_name: String,
+    #[deserr(default = 9600, range = 300..=115200)]
+    pub baud_rate: u32,
+    #[deserr(flatten)]
+    pub network: Network,
+    #[deserr(default)]
+    pub sensors: Vec<Sensor>,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = Error, rename_all = camelCase, deny_unknown_fields)]
+pub struct Network {
+    #[deserr(default)]
+    pub address: Option<u16>,
+    #[deserr(default, deprecated = "use `address` instead")]
+    pub id: Option<u16>,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = Error, tag = "kind", rename_all = lowercase)]
+pub enum Sensor {
+    Temperature { pin: u8 },
+    Humidity { pin: u8, threshold: Threshold },
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = Error, untagged)]
+pub enum Threshold {
+    Fixed(u8),
+    Range { min: u8, max: u8 },
+}
+
+/// An error that only keeps its message, with the location of the invalid value.
+#[derive(Debug)]
+pub struct Error(pub String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl MergeWithError<Error> for Error {
+    fn merge(
+        _self_: Option<Self>,
+        other: Error,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl DeserializeError for Error {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let msg = match error {
+            ErrorKind::MissingField { field } => format!("missing field `{field}`"),
+            ErrorKind::UnknownKey { key, .. } => format!("unknown field `{key}`"),
+            ErrorKind::UnknownValue { value, .. } => format!("unknown value `{value}`"),
+            ErrorKind::Unexpected { msg } => msg,
+            ErrorKind::IncorrectValueKind { .. } => String::from("incorrect type"),
+        };
+        ControlFlow::Break(Error(format!("{msg} at `{}`", location.to_owned())))
+    }
+}
+
+/// Deserialize the configuration of a device, e.g. one that was received over a serial port
+/// and parsed into a [`BufferedValue`].
+pub fn parse_config(value: BufferedValue) -> Result<Config, Error> {
+    deserialize(value)
+}
@@ -1,11 +1,11 @@
 //! A module to parse query parameter as String with deserr
 
+use std::future::{ready, Ready};
 use std::marker::PhantomData;
 use std::{fmt, ops};
 
 use crate::{DeserializeError, Deserr};
 use actix_http::Payload;
-use actix_utils::future::{err, ok, Ready};
 use actix_web::web::Query;
 use actix_web::{FromRequest, HttpRequest, ResponseError};
 
@@ -64,8 +64,6 @@ where
 
     #[inline]
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        AwebQueryParameter::from_query(req.query_string())
-            .map(ok)
-            .unwrap_or_else(err)
+        ready(AwebQueryParameter::from_query(req.query_string()))
     }
 }
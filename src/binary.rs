@@ -0,0 +1,288 @@
+//! Wrapper types to deserialize binary data from strings.
+//!
+//! A `Vec<u8>` is deserialized from a sequence of integers, which is rarely how binary data is
+//! sent in a JSON payload. The types of this module decode it from a base64 or a hexadecimal
+//! string instead.
+
+use alloc::{format, string::String, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// Bytes deserialized from a base64 string, such as a `Vec<u8>`.
+///
+/// The string uses the standard alphabet, with or without its `=` padding. Any other
+/// character, including the whitespace, is an error.
+///
+/// ```
+/// use deserr::{binary::Base64, deserialize, errors::JsonError, Deserr};
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserr)]
+/// struct Upload {
+///     content: Base64<Vec<u8>>,
+/// }
+///
+/// let upload = deserialize::<Upload, _, JsonError>(json!({ "content": "ZG9nZ28=" })).unwrap();
+/// assert_eq!(*upload.content, b"doggo");
+///
+/// let error = deserialize::<Upload, _, JsonError>(json!({ "content": "ZG9n*28=" }))
+///     .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid value at `.content`: could not decode the base64 string: invalid character `*` at index 4",
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base64<T = Vec<u8>>(pub T);
+
+/// Bytes deserialized from a hexadecimal string, such as a `Vec<u8>`.
+///
+/// Each byte is given by two digits, in lowercase or in uppercase.
+///
+/// ```
+/// use deserr::{binary::Hex, deserialize, errors::JsonError, Deserr};
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserr)]
+/// struct Commit {
+///     hash: Hex<Vec<u8>>,
+/// }
+///
+/// let commit = deserialize::<Commit, _, JsonError>(json!({ "hash": "c0FFee" })).unwrap();
+/// assert_eq!(*commit.hash, [0xc0, 0xff, 0xee]);
+///
+/// let error = deserialize::<Commit, _, JsonError>(json!({ "hash": "c0f" })).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid value at `.hash`: could not decode the hexadecimal string: its length of 3 is odd",
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex<T = Vec<u8>>(pub T);
+
+macro_rules! impl_bytes_wrapper {
+    ($wrapper:ident, $encoding:literal, $decode:ident) => {
+        impl<T> $wrapper<T> {
+            /// Return the wrapped bytes.
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $wrapper<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T> DerefMut for $wrapper<T> {
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+
+        impl<T, E> Deserr<E> for $wrapper<T>
+        where
+            T: From<Vec<u8>>,
+            E: DeserializeError,
+        {
+            fn deserialize_from_value<V: IntoValue>(
+                value: Value<V>,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                match value {
+                    Value::String(s) => match $decode(&s) {
+                        Ok(bytes) => Ok($wrapper(T::from(bytes))),
+                        Err(e) => Err(take_cf_content(E::error::<V>(
+                            None,
+                            ErrorKind::Unexpected {
+                                msg: format!(
+                                    concat!("could not decode the ", $encoding, " string: {}"),
+                                    e
+                                ),
+                            },
+                            location,
+                        ))),
+                    },
+                    value => Err(take_cf_content(E::incorrect_value_kind_error(
+                        None,
+                        value,
+                        &[ValueKind::String],
+                        core::any::type_name::<Self>(),
+                        location,
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+impl_bytes_wrapper!(Base64, "base64", decode_base64);
+impl_bytes_wrapper!(Hex, "hexadecimal", decode_hex);
+
+/// Decode a base64 string of the standard alphabet, whose padding is optional.
+fn decode_base64(s: &str) -> Result<Vec<u8>, String> {
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    // The bits that weren't put in a byte yet, and their number
+    let (mut buffer, mut bits) = (0u32, 0);
+    for (index, c) in data.char_indices() {
+        let sextet = match c {
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' => 62,
+            '/' => 63,
+            _ => return Err(format!("invalid character `{c}` at index {index}")),
+        };
+        buffer = buffer << 6 | sextet;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    if padding > 0 && (s.len() % 4 != 0 || padding > 2) {
+        return Err(format!("its padding of {padding} `=` is invalid"));
+    }
+    if data.len() % 4 == 1 {
+        return Err(format!(
+            "its length of {} can't be the length of a base64 string",
+            data.len()
+        ));
+    }
+    // The bits that are left must be zeroes, otherwise the last character isn't canonical
+    if buffer != 0 {
+        return Err(String::from(
+            "its last character has unused bits that aren't zeroes",
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Decode a hexadecimal string, in lowercase or uppercase.
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    if let Some((index, c)) = s.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(format!("invalid character `{c}` at index {index}"));
+    }
+    if s.len() % 2 != 0 {
+        return Err(format!("its length of {} is odd", s.len()));
+    }
+    // All the characters are ASCII digits, so each of them is a byte of the string
+    let digit = |b: u8| (b as char).to_digit(16).unwrap_or_default() as u8;
+    Ok(s.as_bytes()
+        .chunks(2)
+        .map(|pair| digit(pair[0]) << 4 | digit(pair[1]))
+        .collect())
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod test {
+    use super::*;
+    use crate::errors::JsonError;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Debug, crate::Deserr)]
+    struct Upload {
+        content: Base64,
+        checksum: Hex<Box<[u8]>>,
+        #[deserr(default)]
+        raw: Vec<u8>,
+    }
+
+    #[test]
+    fn deserialize_bytes() {
+        let upload = crate::deserialize::<Upload, _, JsonError>(json!({
+            "content": "ZG9nZ28",
+            "checksum": "00aBfF",
+            "raw": [1, 2],
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(upload, @r###"
+        Upload {
+            content: Base64(
+                [
+                    100,
+                    111,
+                    103,
+                    103,
+                    111,
+                ],
+            ),
+            checksum: Hex(
+                [
+                    0,
+                    171,
+                    255,
+                ],
+            ),
+            raw: [
+                1,
+                2,
+            ],
+        }
+        "###);
+
+        for (encoded, decoded) in [
+            ("", ""),
+            ("Zg==", "f"),
+            ("Zm8=", "fo"),
+            ("Zm9v", "foo"),
+            ("Zm9vYg", "foob"),
+            ("Zm9vYmE=", "fooba"),
+            ("Zm9vYmFy", "foobar"),
+            ("+/+/", "\u{fb}\u{ff}\u{bf}"),
+        ] {
+            let bytes = decode_base64(encoded).unwrap();
+            assert_eq!(bytes, decoded.chars().map(|c| c as u8).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let error = crate::deserialize::<Upload, _, JsonError>(json!({
+            "content": "ZG9nZ28=",
+            "checksum": "00aBfG",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.checksum`: could not decode the hexadecimal string: invalid character `G` at index 5");
+
+        let error = crate::deserialize::<Upload, _, JsonError>(json!({
+            "content": "ZG9nZ28=",
+            "checksum": "é0",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.checksum`: could not decode the hexadecimal string: invalid character `é` at index 0");
+
+        let error = crate::deserialize::<Upload, _, JsonError>(json!({
+            "content": "ZG9n Z28=",
+            "checksum": "",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.content`: could not decode the base64 string: invalid character ` ` at index 4");
+
+        let error = crate::deserialize::<Upload, _, JsonError>(json!({
+            "content": [100, 111],
+            "checksum": "",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type at `.content`: expected a string, but found an array: `[100,111]`");
+
+        let error = decode_base64("ZG9nZ28==").unwrap_err();
+        insta::assert_snapshot!(error, @"its padding of 2 `=` is invalid");
+        let error = decode_base64("ZG9nZ").unwrap_err();
+        insta::assert_snapshot!(error, @"its length of 5 can't be the length of a base64 string");
+        let error = decode_base64("ZG9nZ29=").unwrap_err();
+        insta::assert_snapshot!(error, @"its last character has unused bits that aren't zeroes");
+    }
+}
@@ -0,0 +1,298 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{IntoValue, Map, Sequence, Value, ValueKind};
+
+/// An owned copy of a [`Value`] that can be deserialized as many times as needed.
+///
+/// A `Value<V>` is consumed by [`Deserr::deserialize_from_value`](crate::Deserr::deserialize_from_value),
+/// which is a problem when we don't know in advance which type a value should be
+/// deserialized to. The value is buffered once, and every attempt is made on a clone
+/// of the buffer. This is how the variants of the `#[deserr(untagged)]` enums are tried.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BufferedValue {
+    Null,
+    Boolean(bool),
+    Integer(u64),
+    NegativeInteger(i64),
+    Float(f64),
+    String(String),
+    Sequence(BufferedSequence),
+    Map(BufferedMap),
+}
+
+impl BufferedValue {
+    /// Recursively copy the given value into a buffer.
+    pub fn from_value<V: IntoValue>(value: Value<V>) -> Self {
+        match value {
+            Value::Null => BufferedValue::Null,
+            Value::Boolean(b) => BufferedValue::Boolean(b),
+            Value::Integer(n) => BufferedValue::Integer(n),
+            Value::NegativeInteger(n) => BufferedValue::NegativeInteger(n),
+            Value::Float(n) => BufferedValue::Float(n),
+            Value::String(s) => BufferedValue::String(s),
+            Value::Sequence(seq) => BufferedValue::Sequence(BufferedSequence(
+                seq.into_iter()
+                    .map(|v| BufferedValue::from_value(v.into_value()))
+                    .collect(),
+            )),
+            Value::Map(map) => BufferedValue::Map(BufferedMap(
+                map.into_iter()
+                    .map(|(k, v)| (k, BufferedValue::from_value(v.into_value())))
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Overlay `other` on top of `self`, e.g. to apply the configuration given by the
+    /// environment variables to the configuration read from a file, before deserializing it.
+    ///
+    /// The maps are merged recursively: the entries of `other` are merged with the entries
+    /// of `self` that have the same key, and the other entries of `other` are added at the
+    /// end. Any other value of `other`, including `null`, replaces the value of `self`.
+    ///
+    /// The sequences are merged according to `sequences`: by default, the sequence of
+    /// `other` replaces the sequence of `self`, as any other value. With
+    /// [`SequenceMerge::Concatenate`], the elements of `other` are added after the elements
+    /// of `self` instead. The elements themselves are never merged.
+    ///
+    /// ## Example
+    /// ```
+    /// use deserr::{BufferedValue, IntoValue, SequenceMerge};
+    /// use serde_json::json;
+    ///
+    /// let file = json!({ "db": { "host": "localhost", "port": 5432 }, "tags": ["a"] });
+    /// let env = json!({ "db": { "port": 6543 }, "tags": ["b"] });
+    /// let file = BufferedValue::from_value(file.into_value());
+    /// let env = BufferedValue::from_value(env.into_value());
+    ///
+    /// let merged = file.clone().merge(env.clone(), SequenceMerge::Replace);
+    /// let expected = json!({ "db": { "host": "localhost", "port": 6543 }, "tags": ["b"] });
+    /// assert_eq!(merged, BufferedValue::from_value(expected.into_value()));
+    ///
+    /// let merged = file.merge(env, SequenceMerge::Concatenate);
+    /// let expected = json!({ "db": { "host": "localhost", "port": 6543 }, "tags": ["a", "b"] });
+    /// assert_eq!(merged, BufferedValue::from_value(expected.into_value()));
+    /// ```
+    pub fn merge(self, other: BufferedValue, sequences: SequenceMerge) -> Self {
+        match (self, other) {
+            (
+                BufferedValue::Map(BufferedMap(mut entries)),
+                BufferedValue::Map(BufferedMap(other)),
+            ) => {
+                for (key, value) in other {
+                    match entries.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, entry)) => {
+                            let previous = core::mem::replace(entry, BufferedValue::Null);
+                            *entry = previous.merge(value, sequences);
+                        }
+                        None => entries.push((key, value)),
+                    }
+                }
+                BufferedValue::Map(BufferedMap(entries))
+            }
+            (
+                BufferedValue::Sequence(BufferedSequence(mut elements)),
+                BufferedValue::Sequence(BufferedSequence(other)),
+            ) if sequences == SequenceMerge::Concatenate => {
+                elements.extend(other);
+                BufferedValue::Sequence(BufferedSequence(elements))
+            }
+            (_, other) => other,
+        }
+    }
+}
+
+/// How the sequences are merged by [`BufferedValue::merge`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SequenceMerge {
+    /// The sequence of the overlay replaces the base sequence.
+    #[default]
+    Replace,
+    /// The elements of the overlay are added after the elements of the base sequence.
+    Concatenate,
+}
+
+impl BufferedValue {
+    /// Return a wrapper displaying the value as an indented, JSON-like tree, where each
+    /// value is annotated with its [`ValueKind`]. It is meant for debugging, e.g. to log the
+    /// payloads that couldn't be deserialized.
+    ///
+    /// ## Example
+    /// ```
+    /// use deserr::{BufferedValue, IntoValue};
+    /// use serde_json::json;
+    ///
+    /// let value = json!({ "age": 2, "doggo": "bork", "tags": [null, 1.5], "toys": [] });
+    /// let value = BufferedValue::from_value(value.into_value());
+    /// assert_eq!(
+    ///     value.prettyprint().to_string(),
+    ///     r#"Map {
+    ///   "age": Integer 2,
+    ///   "doggo": String "bork",
+    ///   "tags": Sequence [
+    ///     Null,
+    ///     Float 1.5,
+    ///   ],
+    ///   "toys": Sequence [],
+    /// }"#
+    /// );
+    /// ```
+    pub fn prettyprint(&self) -> PrettyPrint<'_> {
+        PrettyPrint(self)
+    }
+
+    fn from_entries<V: IntoValue>(entries: impl IntoIterator<Item = (String, V)>) -> Self {
+        BufferedValue::Map(BufferedMap(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k, BufferedValue::from_value(v.into_value())))
+                .collect(),
+        ))
+    }
+}
+
+impl IntoValue for BufferedValue {
+    type Sequence = BufferedSequence;
+    type Map = BufferedMap;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            BufferedValue::Null => ValueKind::Null,
+            BufferedValue::Boolean(_) => ValueKind::Boolean,
+            BufferedValue::Integer(_) => ValueKind::Integer,
+            BufferedValue::NegativeInteger(_) => ValueKind::NegativeInteger,
+            BufferedValue::Float(_) => ValueKind::Float,
+            BufferedValue::String(_) => ValueKind::String,
+            BufferedValue::Sequence(_) => ValueKind::Sequence,
+            BufferedValue::Map(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            BufferedValue::Null => Value::Null,
+            BufferedValue::Boolean(b) => Value::Boolean(b),
+            BufferedValue::Integer(n) => Value::Integer(n),
+            BufferedValue::NegativeInteger(n) => Value::NegativeInteger(n),
+            BufferedValue::Float(n) => Value::Float(n),
+            BufferedValue::String(s) => Value::String(s),
+            BufferedValue::Sequence(seq) => Value::Sequence(seq),
+            BufferedValue::Map(map) => Value::Map(map),
+        }
+    }
+}
+
+/// Buffer the elements of a vector, so that native Rust data can be deserialized
+/// without being converted to another format first.
+impl<V: IntoValue> From<Vec<V>> for BufferedValue {
+    fn from(elements: Vec<V>) -> Self {
+        BufferedValue::Sequence(BufferedSequence(
+            IntoIterator::into_iter(elements)
+                .map(|v| BufferedValue::from_value(v.into_value()))
+                .collect(),
+        ))
+    }
+}
+
+/// Buffer the entries of a map. They are kept in the iteration order of the map, which
+/// is arbitrary for a [`HashMap`].
+#[cfg(feature = "std")]
+impl<V: IntoValue> From<HashMap<String, V>> for BufferedValue {
+    fn from(entries: HashMap<String, V>) -> Self {
+        BufferedValue::from_entries(entries)
+    }
+}
+
+/// Buffer the entries of a map, sorted by key.
+impl<V: IntoValue> From<BTreeMap<String, V>> for BufferedValue {
+    fn from(entries: BTreeMap<String, V>) -> Self {
+        BufferedValue::from_entries(entries)
+    }
+}
+
+/// The sequence of a [`BufferedValue`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferedSequence(pub(crate) Vec<BufferedValue>);
+
+impl Sequence for BufferedSequence {
+    type Value = BufferedValue;
+    type Iter = alloc::vec::IntoIter<BufferedValue>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        IntoIterator::into_iter(self.0)
+    }
+}
+
+/// The map of a [`BufferedValue`]. The entries are kept in their original order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferedMap(pub(crate) Vec<(String, BufferedValue)>);
+
+impl Map for BufferedMap {
+    type Value = BufferedValue;
+    type Iter = alloc::vec::IntoIter<(String, BufferedValue)>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(index).1)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        IntoIterator::into_iter(self.0)
+    }
+}
+
+/// Displays a [`BufferedValue`] as an indented tree, see [`BufferedValue::prettyprint`].
+pub struct PrettyPrint<'a>(&'a BufferedValue);
+
+impl Display for PrettyPrint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_pretty(f, self.0, 0)
+    }
+}
+
+/// Write the value, whose first line is already indented, with its nested values
+/// indented by two more spaces than `depth`.
+fn write_pretty(f: &mut fmt::Formatter<'_>, value: &BufferedValue, depth: usize) -> fmt::Result {
+    let kind = value.kind();
+    match value {
+        BufferedValue::Null => write!(f, "{kind}"),
+        BufferedValue::Boolean(b) => write!(f, "{kind} {b}"),
+        BufferedValue::Integer(n) => write!(f, "{kind} {n}"),
+        BufferedValue::NegativeInteger(n) => write!(f, "{kind} {n}"),
+        BufferedValue::Float(n) => write!(f, "{kind} {n:?}"),
+        BufferedValue::String(s) => write!(f, "{kind} {s:?}"),
+        BufferedValue::Sequence(BufferedSequence(elements)) if elements.is_empty() => {
+            write!(f, "{kind} []")
+        }
+        BufferedValue::Sequence(BufferedSequence(elements)) => {
+            writeln!(f, "{kind} [")?;
+            for element in elements {
+                write!(f, "{:1$}", "", (depth + 1) * 2)?;
+                write_pretty(f, element, depth + 1)?;
+                writeln!(f, ",")?;
+            }
+            write!(f, "{:1$}]", "", depth * 2)
+        }
+        BufferedValue::Map(BufferedMap(entries)) if entries.is_empty() => write!(f, "{kind} {{}}"),
+        BufferedValue::Map(BufferedMap(entries)) => {
+            writeln!(f, "{kind} {{")?;
+            for (key, value) in entries {
+                write!(f, "{:1$}{key:?}: ", "", (depth + 1) * 2)?;
+                write_pretty(f, value, depth + 1)?;
+                writeln!(f, ",")?;
+            }
+            write!(f, "{:1$}}}", "", depth * 2)
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Implements [`Deserr`] for the [`chrono`] date and time types.
+//!
+//! The datetimes are parsed from strings following [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339),
+//! e.g. `1996-12-19T16:39:57-08:00`, and the dates from strings such as `1996-12-19`.
+
+use std::convert::Infallible;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// Deserialize a string with the given parsing function, returning an [`ErrorKind::Unexpected`]
+/// containing the string if it can't be parsed.
+fn deserialize_with<T, V, E, PE>(
+    value: Value<V>,
+    location: ValuePointerRef,
+    parse: impl FnOnce(&str) -> Result<T, PE>,
+) -> Result<T, E>
+where
+    V: IntoValue,
+    E: DeserializeError,
+    PE: std::fmt::Display,
+{
+    match value {
+        Value::String(s) => parse(&s).map_err(|e| {
+            take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!("{e}: `{s}`"),
+                },
+                location,
+            ))
+        }),
+        v => Err(take_cf_content(E::incorrect_value_kind_error(
+            None,
+            v,
+            &[ValueKind::String],
+            std::any::type_name::<T>(),
+            location,
+        ))),
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for DateTime<Utc> {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        deserialize_with(value, location, |s| {
+            DateTime::parse_from_rfc3339(s).map(|datetime| datetime.to_utc())
+        })
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for DateTime<FixedOffset> {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        deserialize_with(value, location, DateTime::parse_from_rfc3339)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for NaiveDate {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        deserialize_with(value, location, str::parse::<NaiveDate>)
+    }
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod test {
+    use super::*;
+    use crate::errors::JsonError;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Debug, crate::Deserr)]
+    struct Event {
+        #[deserr(default)]
+        at: Option<DateTime<Utc>>,
+        local: DateTime<FixedOffset>,
+        day: NaiveDate,
+    }
+
+    #[test]
+    fn deserialize_dates() {
+        let event = crate::deserialize::<Event, _, JsonError>(json!({
+            "at": "1996-12-19T16:39:57-08:00",
+            "local": "1996-12-19T16:39:57-08:00",
+            "day": "1996-12-19",
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(event, @r###"
+        Event {
+            at: Some(
+                1996-12-20T00:39:57Z,
+            ),
+            local: 1996-12-19T16:39:57-08:00,
+            day: 1996-12-19,
+        }
+        "###);
+
+        let event = crate::deserialize::<Event, _, JsonError>(json!({
+            "at": null,
+            "local": "1996-12-19T16:39:57Z",
+            "day": "1996-12-19",
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(event.at, @"None");
+
+        let event = crate::deserialize::<Event, _, JsonError>(json!({
+            "local": "1996-12-19T16:39:57Z",
+            "day": "1996-12-19",
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(event.at, @"None");
+    }
+
+    #[test]
+    fn invalid_dates() {
+        let error = crate::deserialize::<Event, _, JsonError>(json!({
+            "at": "1996-12-19 16:39:57",
+            "local": "1996-12-19T16:39:57Z",
+            "day": "1996-12-19",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.at`: premature end of input: `1996-12-19 16:39:57`");
+
+        let error = crate::deserialize::<Event, _, JsonError>(json!({
+            "local": "1996-12-19T16:39:57Z",
+            "day": "1996-02-30",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.day`: input is out of range: `1996-02-30`");
+
+        let error = crate::deserialize::<Event, _, JsonError>(json!({
+            "local": 851042397,
+            "day": "1996-12-19",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type at `.local`: expected a string, but found a positive integer: `851042397`");
+    }
+}
@@ -0,0 +1,386 @@
+//! Implements the value traits for [`ciborium::Value`].
+//!
+//! CBOR has a few types that don't exist in the deserr data model:
+//! - byte strings are surfaced as a [`Value::Sequence`] of integers, one per byte
+//! - tags are ignored and only the value they tag is seen
+//! - integers that fit neither in a `u64` nor in an `i64` are surfaced as a [`Value::Float`]
+//! - [`ciborium::Value`] is non exhaustive: a kind of value added by a future version of
+//!   ciborium is surfaced as a [`Value::Null`] until deserr supports it
+//!
+//! Deserializing into a [`ciborium::Value`] always produces text map keys, arrays instead
+//! of byte strings and no tags.
+
+use std::ops::ControlFlow;
+
+use crate::value::{key_matches, stringify_key, unsupported_key};
+use crate::{
+    DeserializeError, Deserr, IntoValue, Map, Sequence, Value, ValueKind, ValuePointerRef,
+};
+use ciborium::value::{Integer, Value as CValue};
+
+/// The map of a [`ciborium::Value`], which is a list of entries with arbitrary keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CMap(Vec<(CValue, CValue)>);
+
+/// Iterator over the entries of a CBOR map, whose keys are converted to strings as described
+/// by [`Map`].
+pub struct CMapIter {
+    iter: std::vec::IntoIter<(CValue, CValue)>,
+}
+
+impl Iterator for CMapIter {
+    type Item = (String, CValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the keys that can't be converted are refused beforehand, see `Map::unsupported_key`
+        self.iter
+            .by_ref()
+            .find_map(|(k, v)| Some((stringify_key(k).ok()?, v)))
+    }
+}
+
+impl Map for CMap {
+    type Value = CValue;
+    type Iter = CMapIter;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self.0.iter().position(|(k, _)| key_matches(k, key))?;
+        Some(self.0.remove(index).1)
+    }
+    fn into_iter(self) -> Self::Iter {
+        CMapIter {
+            iter: self.0.into_iter(),
+        }
+    }
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        unsupported_key(self.0.iter().map(|(k, _)| k))
+    }
+}
+
+impl IntoValue for CValue {
+    type Sequence = Vec<CValue>;
+    type Map = CMap;
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            CValue::Null => Value::Null,
+            CValue::Bool(b) => Value::Boolean(b),
+            CValue::Integer(n) => {
+                if let Ok(n) = u64::try_from(n) {
+                    Value::Integer(n)
+                } else if let Ok(n) = i64::try_from(n) {
+                    Value::NegativeInteger(n)
+                } else {
+                    Value::Float(i128::from(n) as f64)
+                }
+            }
+            CValue::Float(n) => Value::Float(n),
+            CValue::Text(x) => Value::String(x),
+            CValue::Bytes(x) => Value::Sequence(
+                x.into_iter()
+                    .map(|byte| CValue::Integer(byte.into()))
+                    .collect(),
+            ),
+            CValue::Array(x) => Value::Sequence(x),
+            CValue::Map(x) => Value::Map(CMap(x)),
+            CValue::Tag(_, x) => x.into_value(),
+            // `ciborium::Value` is non exhaustive, see the module documentation
+            _ => Value::Null,
+        }
+    }
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            CValue::Null => ValueKind::Null,
+            CValue::Bool(_) => ValueKind::Boolean,
+            CValue::Integer(n) => {
+                if u64::try_from(*n).is_ok() {
+                    ValueKind::Integer
+                } else if i64::try_from(*n).is_ok() {
+                    ValueKind::NegativeInteger
+                } else {
+                    ValueKind::Float
+                }
+            }
+            CValue::Float(_) => ValueKind::Float,
+            CValue::Text(_) => ValueKind::String,
+            CValue::Bytes(_) => ValueKind::Sequence,
+            CValue::Array(_) => ValueKind::Sequence,
+            CValue::Map(_) => ValueKind::Map,
+            CValue::Tag(_, x) => x.kind(),
+            // see `into_value`
+            _ => ValueKind::Null,
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for CValue {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let mut error: Option<E> = None;
+        Ok(match value {
+            Value::Null => CValue::Null,
+            Value::Boolean(b) => CValue::Bool(b),
+            Value::Integer(x) => CValue::Integer(Integer::from(x)),
+            Value::NegativeInteger(x) => CValue::Integer(Integer::from(x)),
+            Value::Float(f) => CValue::Float(f),
+            Value::String(s) => CValue::Text(s),
+            Value::Sequence(seq) => {
+                let mut cseq = Vec::with_capacity(seq.len());
+                for (index, value) in seq.into_iter().enumerate() {
+                    let result = Self::deserialize_from_value(
+                        value.into_value(),
+                        location.push_index(index),
+                    );
+                    match result {
+                        Ok(value) => {
+                            cseq.push(value);
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_index(index)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    CValue::Array(cseq)
+                }
+            }
+            Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
+                let mut cmap = Vec::with_capacity(map.len());
+                for (key, value) in map.into_iter() {
+                    let result =
+                        Self::deserialize_from_value(value.into_value(), location.push_key(&key));
+                    match result {
+                        Ok(value) => {
+                            cmap.push((CValue::Text(key), value));
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_key(&key)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    CValue::Map(cmap)
+                }
+            }
+        })
+    }
+}
+
+impl<V: IntoValue> From<Value<V>> for CValue {
+    fn from(value: Value<V>) -> Self {
+        match value {
+            Value::Null => CValue::Null,
+            Value::Boolean(b) => CValue::Bool(b),
+            Value::Integer(n) => CValue::Integer(Integer::from(n)),
+            Value::NegativeInteger(i) => CValue::Integer(Integer::from(i)),
+            Value::Float(f) => CValue::Float(f),
+            Value::String(s) => CValue::Text(s),
+            Value::Sequence(s) => CValue::Array(
+                s.into_iter()
+                    .map(IntoValue::into_value)
+                    .map(CValue::from)
+                    .collect(),
+            ),
+            Value::Map(m) => CValue::Map(
+                m.into_iter()
+                    .map(|(k, v)| (CValue::Text(k), CValue::from(v.into_value())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_value_to_deserr_and_back() {
+        let value = CValue::Map(vec![
+            (CValue::Text("The".into()), CValue::Text("best".into())),
+            (
+                CValue::Text("doggos".into()),
+                CValue::Array(vec![CValue::Text("are".into())]),
+            ),
+            (
+                CValue::Text("the".into()),
+                CValue::Map(vec![
+                    (
+                        CValue::Text("bernese".into()),
+                        CValue::Text("mountain".into()),
+                    ),
+                    (
+                        CValue::Text("age".into()),
+                        CValue::Integer(Integer::from(-3)),
+                    ),
+                ]),
+            ),
+        ]);
+        let deserr = value.clone().into_value();
+
+        insta::assert_debug_snapshot!(deserr, @r#"
+        Map(
+            CMap(
+                [
+                    (
+                        Text(
+                            "The",
+                        ),
+                        Text(
+                            "best",
+                        ),
+                    ),
+                    (
+                        Text(
+                            "doggos",
+                        ),
+                        Array(
+                            [
+                                Text(
+                                    "are",
+                                ),
+                            ],
+                        ),
+                    ),
+                    (
+                        Text(
+                            "the",
+                        ),
+                        Map(
+                            [
+                                (
+                                    Text(
+                                        "bernese",
+                                    ),
+                                    Text(
+                                        "mountain",
+                                    ),
+                                ),
+                                (
+                                    Text(
+                                        "age",
+                                    ),
+                                    Integer(
+                                        Integer(
+                                            -3,
+                                        ),
+                                    ),
+                                ),
+                            ],
+                        ),
+                    ),
+                ],
+            ),
+        )
+        "#);
+
+        let deserr: CValue = deserr.into();
+        insta::assert_debug_snapshot!(deserr, @r#"
+        Map(
+            [
+                (
+                    Text(
+                        "The",
+                    ),
+                    Text(
+                        "best",
+                    ),
+                ),
+                (
+                    Text(
+                        "doggos",
+                    ),
+                    Array(
+                        [
+                            Text(
+                                "are",
+                            ),
+                        ],
+                    ),
+                ),
+                (
+                    Text(
+                        "the",
+                    ),
+                    Map(
+                        [
+                            (
+                                Text(
+                                    "bernese",
+                                ),
+                                Text(
+                                    "mountain",
+                                ),
+                            ),
+                            (
+                                Text(
+                                    "age",
+                                ),
+                                Integer(
+                                    Integer(
+                                        -3,
+                                    ),
+                                ),
+                            ),
+                        ],
+                    ),
+                ),
+            ],
+        )
+        "#);
+
+        assert_eq!(value, deserr);
+    }
+
+    #[test]
+    fn bytes_tags_and_non_text_keys() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            #[deserr(rename = "1")]
+            one: Vec<u8>,
+            #[deserr(rename = "true")]
+            truth: String,
+        }
+
+        let value = CValue::Map(vec![
+            (
+                CValue::Integer(Integer::from(1)),
+                CValue::Bytes(vec![0xde, 0xad]),
+            ),
+            (
+                CValue::Bool(true),
+                CValue::Tag(32, Box::new(CValue::Text("https://doggo.rs".into()))),
+            ),
+        ]);
+
+        let doggo: Doggo =
+            crate::deserialize::<_, _, crate::errors::QueryParamError>(value).unwrap();
+        insta::assert_debug_snapshot!(doggo, @r#"
+        Doggo {
+            one: [
+                222,
+                173,
+            ],
+            truth: "https://doggo.rs",
+        }
+        "#);
+    }
+}
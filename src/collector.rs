@@ -0,0 +1,70 @@
+//! The lists of items, such as the warnings, collected on the current thread by a scoped call
+//! to one of the `deserialize_with_*` functions.
+//!
+//! Each list lives in a thread local that is `None` outside of such a call. A call keeps the
+//! list of an outer call aside, in case the type it deserializes calls the same function
+//! itself, and a guard restores it when the call returns or unwinds.
+
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use std::thread::LocalKey;
+
+/// The thread local holding the list of items of the scoped call in progress, if any.
+pub(crate) type Collector<T> = LocalKey<RefCell<Option<Vec<T>>>>;
+
+/// Restores the list of the outer call when dropped.
+struct RestoreGuard<T: 'static> {
+    collector: &'static Collector<T>,
+    outer: Option<Vec<T>>,
+}
+
+impl<T: 'static> Drop for RestoreGuard<T> {
+    fn drop(&mut self) {
+        let outer = self.outer.take();
+        // The thread local may already be destroyed if the thread is exiting
+        let _ = self.collector.try_with(|items| items.replace(outer));
+    }
+}
+
+/// Run `f` with a new list of items if `collect` is `true`, or without any list otherwise,
+/// and return the items collected during the call.
+pub(crate) fn scope<T: 'static, R>(
+    collector: &'static Collector<T>,
+    collect: bool,
+    f: impl FnOnce() -> R,
+) -> (R, Vec<T>) {
+    let state = collect.then(Vec::new);
+    let _guard = RestoreGuard {
+        collector,
+        outer: collector.with(|items| items.replace(state)),
+    };
+    let result = f();
+    let items = collector.with(|items| items.take());
+    (result, items.unwrap_or_default())
+}
+
+/// Push an item to the list of the scoped call in progress, and return `false` if there is
+/// none.
+pub(crate) fn push<T: 'static>(collector: &'static Collector<T>, item: T) -> bool {
+    collector.with(|items| match items.borrow_mut().as_mut() {
+        Some(items) => {
+            items.push(item);
+            true
+        }
+        None => false,
+    })
+}
+
+/// Return the number of items collected so far.
+pub(crate) fn checkpoint<T: 'static>(collector: &'static Collector<T>) -> usize {
+    collector.with(|items| items.borrow().as_ref().map_or(0, Vec::len))
+}
+
+/// Drop the items collected since the given [`checkpoint`].
+pub(crate) fn rollback<T: 'static>(collector: &'static Collector<T>, checkpoint: usize) {
+    collector.with(|items| {
+        if let Some(items) = items.borrow_mut().as_mut() {
+            items.truncate(checkpoint);
+        }
+    });
+}
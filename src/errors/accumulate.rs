@@ -0,0 +1,85 @@
+//! This module implements an error type that never stops the deserialization and
+//! instead collects every error it encounters.
+//!
+//! It is used by [`deserialize_accumulate`](crate::deserialize_accumulate), but can
+//! also be used directly as the error type of a [`Deserr`](crate::Deserr) implementation.
+
+use alloc::vec::Vec;
+use core::{fmt::Display, ops::ControlFlow};
+
+use crate::{take_cf_content, DeserializeError, ErrorKind, IntoValue, MergeWithError};
+use deserr::ValuePointerRef;
+
+/// A list of errors of type `E`, in the order in which they were encountered.
+///
+/// Each error is created by `E` with the full location of the value that caused it.
+/// Since the errors are always merged with [`ControlFlow::Continue`], the deserialization
+/// goes through the whole value and reports everything that is wrong with it, which
+/// is handy to validate forms.
+#[derive(Debug, Clone)]
+pub struct AccumulatedErrors<E>(Vec<E>);
+
+impl<E> AccumulatedErrors<E> {
+    /// Return the errors in the order in which they were encountered.
+    pub fn errors(&self) -> &[E] {
+        &self.0
+    }
+
+    /// Return the errors in the order in which they were encountered.
+    pub fn into_errors(self) -> Vec<E> {
+        self.0
+    }
+}
+
+impl<E: Display> Display for AccumulatedErrors<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: DeserializeError> DeserializeError for AccumulatedErrors<E> {
+    fn error<V: IntoValue>(
+        self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let mut errors = self_.map_or_else(Vec::new, |s| s.0);
+        errors.push(take_cf_content(E::error(None, error, location)));
+        ControlFlow::Continue(AccumulatedErrors(errors))
+    }
+}
+
+impl<E> MergeWithError<AccumulatedErrors<E>> for AccumulatedErrors<E> {
+    fn merge(
+        self_: Option<Self>,
+        other: AccumulatedErrors<E>,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let mut errors = self_.map_or_else(Vec::new, |s| s.0);
+        errors.extend(other.0);
+        ControlFlow::Continue(AccumulatedErrors(errors))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E, T> MergeWithError<T> for AccumulatedErrors<E>
+where
+    E: MergeWithError<T>,
+    T: std::error::Error,
+{
+    fn merge(
+        self_: Option<Self>,
+        other: T,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let mut errors = self_.map_or_else(Vec::new, |s| s.0);
+        errors.push(take_cf_content(E::merge(None, other, merge_location)));
+        ControlFlow::Continue(AccumulatedErrors(errors))
+    }
+}
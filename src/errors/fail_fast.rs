@@ -0,0 +1,69 @@
+//! This module implements an error type that stops the deserialization at the first error,
+//! whatever the error type it wraps would have done.
+//!
+//! It is the counterpart of [`AccumulatedErrors`](crate::errors::AccumulatedErrors), and is
+//! used by [`deserialize_with_mode`](crate::deserialize_with_mode) to run the same error type
+//! in both modes.
+
+use core::{fmt::Display, ops::ControlFlow};
+
+use crate::{take_cf_content, DeserializeError, ErrorKind, IntoValue, MergeWithError};
+use deserr::ValuePointerRef;
+
+/// The first error of type `E` encountered during the deserialization.
+///
+/// The errors are always merged with [`ControlFlow::Break`], which means the deserialization
+/// stops as soon as something is wrong, even if `E` would have asked to continue.
+#[derive(Debug, Clone)]
+pub struct FailFast<E>(E);
+
+impl<E> FailFast<E> {
+    /// Return the wrapped error.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<E: Display> Display for FailFast<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<E: DeserializeError> DeserializeError for FailFast<E> {
+    fn error<V: IntoValue>(
+        self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let error = take_cf_content(E::error(self_.map(|s| s.0), error, location));
+        ControlFlow::Break(FailFast(error))
+    }
+}
+
+impl<E: DeserializeError> MergeWithError<FailFast<E>> for FailFast<E> {
+    fn merge(
+        self_: Option<Self>,
+        other: FailFast<E>,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let error = take_cf_content(E::merge(self_.map(|s| s.0), other.0, merge_location));
+        ControlFlow::Break(FailFast(error))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E, T> MergeWithError<T> for FailFast<E>
+where
+    E: MergeWithError<T>,
+    T: std::error::Error,
+{
+    fn merge(
+        self_: Option<Self>,
+        other: T,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let error = take_cf_content(E::merge(self_.map(|s| s.0), other, merge_location));
+        ControlFlow::Break(FailFast(error))
+    }
+}
@@ -5,6 +5,10 @@ use strsim::damerau_levenshtein;
 
 /// Compute a did you mean message from a received string and a list of
 /// accepted strings.
+///
+/// The number of typos allowed grows with the length of the received string.
+/// Nothing is suggested when several accepted strings are equally close, to
+/// avoid guessing between them.
 pub fn did_you_mean(received: &str, accepted: &[&str]) -> String {
     let typo_allowed = match received.len() {
         // no typos are allowed, we can early return
@@ -15,13 +19,27 @@ pub fn did_you_mean(received: &str, accepted: &[&str]) -> String {
         18..=24 => 4,
         _ => 5,
     };
-    match accepted
+    let mut candidates = accepted
         .iter()
         .map(|accepted| (accepted, damerau_levenshtein(received, accepted)))
-        .filter(|(_, distance)| distance <= &typo_allowed)
-        .min_by(|(_, d1), (_, d2)| d1.cmp(d2))
-    {
-        None => String::new(),
-        Some((accepted, _)) => format!("did you mean `{}`? ", accepted),
+        .filter(|(_, distance)| distance <= &typo_allowed);
+
+    let Some(mut best) = candidates.next() else {
+        return String::new();
+    };
+    let mut tie = false;
+    for candidate in candidates {
+        if candidate.1 < best.1 {
+            best = candidate;
+            tie = false;
+        } else if candidate.1 == best.1 {
+            tie = true;
+        }
+    }
+
+    if tie {
+        String::new()
+    } else {
+        format!("did you mean `{}`? ", best.0)
     }
 }
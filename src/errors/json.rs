@@ -4,26 +4,99 @@
 //! We also provides some helpers if you need to reuse some component for your error
 //! messages.
 
-use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+use core::{
+    convert::Infallible,
+    fmt::{Debug, Display},
+    ops::ControlFlow,
+};
 
-use deserr::{ErrorKind, IntoValue, ValueKind, ValuePointerRef};
+use deserr::{
+    ErrorKind, ErrorKindTag, IntoValue, ValueKind, ValuePointer, ValuePointerComponent,
+    ValuePointerRef,
+};
 
 use crate::{DeserializeError, MergeWithError};
 
-use super::helpers::did_you_mean;
+use super::localized::ErrorMessages;
 
-#[derive(Debug, Clone)]
-pub struct JsonError(String);
+#[derive(Clone)]
+pub struct JsonError {
+    msg: String,
+    kind: ErrorKindTag,
+    location: ValuePointer,
+    // Boxed to keep the error small, the value being rarely attached
+    value: Option<Box<serde_json::Value>>,
+}
 
 impl Display for JsonError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+// The location is already described by the message
+impl Debug for JsonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("JsonError").field(&self.msg).finish()
     }
 }
 
 impl JsonError {
-    fn new(msg: String) -> Self {
-        JsonError(msg)
+    fn new(msg: String, kind: ErrorKindTag, location: ValuePointerRef) -> Self {
+        JsonError {
+            msg,
+            kind,
+            location: location.to_owned(),
+            value: None,
+        }
+    }
+
+    /// Return the kind of error that occurred, e.g. [`ErrorKindTag::MissingField`].
+    ///
+    /// The errors merged from another error type are [`ErrorKindTag::Unexpected`].
+    pub fn kind(&self) -> ErrorKindTag {
+        self.kind
+    }
+
+    /// Return the location of the value that caused the error.
+    pub fn location(&self) -> &ValuePointer {
+        &self.location
+    }
+
+    /// Attach a copy of the value located at the [location](Self::location) of the error
+    /// in the given document, which can then be retrieved with [`value`](Self::value).
+    ///
+    /// Nothing is copied unless this method is called, so it is meant to be used when
+    /// debugging or logging an error, e.g. with a document deserialized by
+    /// [`deserialize_ref`](crate::deserialize_ref).
+    #[must_use]
+    pub fn with_value(mut self, document: &serde_json::Value) -> Self {
+        self.value = self
+            .location
+            .path
+            .iter()
+            .try_fold(document, |value, component| match component {
+                ValuePointerComponent::Key(key) => value.get(key),
+                ValuePointerComponent::Index(index) => value.get(index),
+            })
+            .cloned()
+            .map(Box::new);
+        self
+    }
+
+    /// Prefix the message with the line of the input at which the error happened, see
+    /// [`from_ndjson`](crate::from_ndjson).
+    pub(crate) fn on_line(mut self, line: usize) -> Self {
+        self.msg = format!("Line {line}: {}", self.msg);
+        self
+    }
+
+    /// Return the value attached by [`with_value`](Self::with_value), if any.
+    ///
+    /// It is the value that caused the error, or the object that contains the missing or
+    /// unknown field.
+    pub fn value(&self) -> Option<&serde_json::Value> {
+        self.value.as_deref()
     }
 }
 
@@ -46,6 +119,22 @@ pub fn location_json_description(location: ValuePointerRef, article: &str) -> St
     }
 }
 
+/// Return the location as a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901),
+/// e.g. `/key1/8/key2`. The origin is an empty string.
+pub fn location_json_pointer(location: ValuePointerRef) -> String {
+    match location {
+        ValuePointerRef::Origin => String::new(),
+        ValuePointerRef::Key { key, prev } => format!(
+            "{}/{}",
+            location_json_pointer(*prev),
+            key.replace('~', "~0").replace('/', "~1")
+        ),
+        ValuePointerRef::Index { index, prev } => {
+            format!("{}/{index}", location_json_pointer(*prev))
+        }
+    }
+}
+
 /// Return a description of the list of value kinds for a Json payload.
 pub fn value_kinds_description_json(kinds: &[ValueKind]) -> String {
     // Rank each value kind so that they can be sorted (and deduplicated)
@@ -140,61 +229,19 @@ pub fn value_description_with_kind_json(v: &serde_json::Value) -> String {
     }
 }
 
+// The default messages are the ones of `JsonError`
+impl ErrorMessages for JsonError {}
+
 impl DeserializeError for JsonError {
     fn error<V: IntoValue>(
         _self_: Option<Self>,
         error: deserr::ErrorKind<V>,
         location: ValuePointerRef,
     ) -> ControlFlow<Self, Self> {
-        let mut message = String::new();
+        let kind = error.tag();
+        let message = Self::message(error, location);
 
-        message.push_str(&match error {
-            ErrorKind::IncorrectValueKind { actual, accepted } => {
-                let expected = value_kinds_description_json(accepted);
-                let received = value_description_with_kind_json(&serde_json::Value::from(actual));
-
-                let location = location_json_description(location, " at");
-
-                format!("Invalid value type{location}: expected {expected}, but found {received}")
-            }
-            ErrorKind::MissingField { field } => {
-                let location = location_json_description(location, " inside");
-                format!("Missing field `{field}`{location}")
-            }
-            ErrorKind::UnknownKey { key, accepted } => {
-                let location = location_json_description(location, " inside");
-
-                format!(
-                    "Unknown field `{}`{location}: {}expected one of {}",
-                    key,
-                    did_you_mean(key, accepted),
-                    accepted
-                        .iter()
-                        .map(|accepted| format!("`{}`", accepted))
-                        .collect::<Vec<String>>()
-                        .join(", ")
-                )
-            }
-            ErrorKind::UnknownValue { value, accepted } => {
-                let location = location_json_description(location, " at");
-                format!(
-                    "Unknown value `{}`{location}: {}expected one of {}",
-                    value,
-                    did_you_mean(value, accepted),
-                    accepted
-                        .iter()
-                        .map(|accepted| format!("`{}`", accepted))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                )
-            }
-            ErrorKind::Unexpected { msg } => {
-                let location = location_json_description(location, " at");
-                format!("Invalid value{location}: {msg}")
-            }
-        });
-
-        ControlFlow::Break(JsonError::new(message))
+        ControlFlow::Break(JsonError::new(message, kind, location))
     }
 }
 
@@ -344,11 +391,110 @@ mod tests {
         }
         let value = json!({ "me": [2] });
         let err = deserr::deserialize::<UnexpectedTuple, _, JsonError>(value).unwrap_err();
-        insta::assert_snapshot!(err, @"Invalid value at `.me`: the sequence should have exactly 2 elements");
+        insta::assert_snapshot!(err, @"Invalid value at `.me`: expected a sequence of 2 elements but instead found a sequence of 1 element");
 
         let value = json!({ "me": [2, 3, 4] });
         let err = deserr::deserialize::<UnexpectedTuple, _, JsonError>(value).unwrap_err();
-        insta::assert_snapshot!(err, @"Invalid value at `.me`: the sequence should have exactly 2 elements");
+        insta::assert_snapshot!(err, @"Invalid value at `.me`: expected a sequence of 2 elements but instead found a sequence of 3 elements");
+    }
+
+    #[test]
+    fn error_location() {
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        struct Named {
+            name: String,
+        }
+
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        struct Nested {
+            me: Vec<Named>,
+        }
+        let value = json!({ "me": [{ "name": "a" }, { "name": 2 }] });
+        let err = deserr::deserialize::<Nested, _, JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(err.location(), @".me[1].name");
+
+        let value = json!([]);
+        let err = deserr::deserialize::<Nested, _, JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(err.location(), @"");
+    }
+
+    #[test]
+    fn error_value() {
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        struct Named {
+            name: String,
+        }
+
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        struct Nested {
+            me: Vec<Named>,
+        }
+        let value = json!({ "me": [{ "name": "a" }, { "name": ["b", 2] }] });
+        let err = deserr::deserialize_ref::<Nested, _, JsonError>(&value).unwrap_err();
+        assert_eq!(err.value(), None);
+        let err = err.with_value(&value);
+        insta::assert_snapshot!(err.location(), @".me[1].name");
+        insta::assert_json_snapshot!(err.value(), @r###"
+        [
+          "b",
+          2
+        ]
+        "###);
+
+        let value = json!({ "me": [{ "name": "a" }, { "nom": "b" }] });
+        let err = deserr::deserialize_ref::<Nested, _, JsonError>(&value)
+            .unwrap_err()
+            .with_value(&value);
+        insta::assert_json_snapshot!(err.value(), @r###"
+        {
+          "nom": "b"
+        }
+        "###);
+    }
+
+    #[test]
+    fn error_kind() {
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        #[deserr(deny_unknown_fields)]
+        struct Search {
+            q: String,
+            mode: Mode,
+            #[deserr(default, try_from(&String) = core::str::FromStr::from_str -> core::num::ParseIntError)]
+            limit: usize,
+        }
+
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        enum Mode {
+            Fast,
+            Exact,
+        }
+
+        let kind = |value| {
+            deserr::deserialize::<Search, _, JsonError>(value)
+                .unwrap_err()
+                .kind()
+        };
+        insta::assert_debug_snapshot!([
+            kind(json!({ "mode": "Fast" })),
+            kind(json!({ "q": 2, "mode": "Fast" })),
+            kind(json!({ "q": "", "mode": "Fast", "doggo": true })),
+            kind(json!({ "q": "", "mode": "Slow" })),
+            kind(json!({ "q": "", "mode": "Fast", "limit": "ten" })),
+        ], @r###"
+        [
+            MissingField,
+            IncorrectValueKind,
+            UnknownKey,
+            UnknownValue,
+            Unexpected,
+        ]
+        "###);
     }
 
     #[test]
@@ -442,4 +588,62 @@ mod tests {
         let err = deserr::deserialize::<DidYouMean, _, JsonError>(value).unwrap_err();
         insta::assert_snapshot!(err, @"Unknown value `sortable` at `.q`: expected one of `q`, `filter`, `sort`, `attributesToHighLight`");
     }
+
+    #[test]
+    fn error_did_you_mean_with_several_candidates() {
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        #[deserr(deny_unknown_fields)]
+        struct Animals {
+            cats: String,
+            bats: String,
+            doggos: String,
+        }
+
+        // `cats` and `bats` are both one typo away
+        let value = json!({ "rats": "doggo" });
+        let err = deserr::deserialize::<Animals, _, JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(err, @"Unknown field `rats`: expected one of `cats`, `bats`, `doggos`");
+
+        // `doggos` is closer than `cats` and `bats`
+        let value = json!({ "doggo": "doggo" });
+        let err = deserr::deserialize::<Animals, _, JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(err, @"Unknown field `doggo`: did you mean `doggos`? expected one of `cats`, `bats`, `doggos`");
+    }
+
+    #[test]
+    fn localized_json_messages() {
+        use crate::errors::LocalizedError;
+
+        /// Describes the locations with JSON pointers, and keeps the messages of `JsonError`
+        /// for the other kinds.
+        struct Localized;
+
+        impl ErrorMessages for Localized {
+            fn location(location: ValuePointerRef, _article: &str) -> String {
+                format!(" (at `{}`)", location_json_pointer(location))
+            }
+
+            fn unknown_key(key: &str, _accepted: &[&str], location: ValuePointerRef) -> String {
+                format!("Champ inconnu `{key}`{}", Self::location(location, ""))
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        #[deserr(deny_unknown_fields)]
+        struct Doggo {
+            name: String,
+        }
+
+        type Doggos = std::collections::HashMap<String, Vec<Doggo>>;
+
+        let value = json!({ "a/b~c": [{ "name": "Kefir", "age": 2 }] });
+        let err = deserr::deserialize::<Doggos, _, LocalizedError<Localized>>(value).unwrap_err();
+        insta::assert_snapshot!(err, @"Champ inconnu `age` (at `/a~1b~0c/0`)");
+
+        let value = json!({ "a/b~c": [{ "name": 2 }] });
+        let err = deserr::deserialize::<Doggos, _, LocalizedError<Localized>>(value).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value type (at `/a~1b~0c/0/name`): expected a string, but found a positive integer: `2`");
+    }
 }
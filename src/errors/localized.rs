@@ -0,0 +1,243 @@
+//! This module implements an error type whose messages are built by a set of
+//! [`ErrorMessages`], e.g. to write them in the language of the client.
+//!
+//! The default messages are the ones of [`JsonError`](super::JsonError), so a localized set
+//! only overrides the messages it translates. [`QueryParamError`](super::QueryParamError) is
+//! also a set of [`ErrorMessages`], which a localized set can fall back to instead.
+
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    ops::ControlFlow,
+};
+
+use super::{
+    helpers::did_you_mean,
+    json::{
+        location_json_description, value_description_with_kind_json, value_kinds_description_json,
+    },
+};
+use crate::{
+    DeserializeError, ErrorKind, ErrorKindTag, IntoValue, MergeWithError, Value, ValueKind,
+    ValuePointer, ValuePointerRef,
+};
+
+/// The messages of the errors, with a method to build the message of each kind of
+/// [`ErrorKind`].
+///
+/// Each method returns the message of [`JsonError`](super::JsonError) unless it is overridden.
+/// The default messages describe the location of the error with [`location`](Self::location),
+/// so overriding it changes all of them.
+///
+/// Only the human-readable text is built by these methods: the keys, the values and the
+/// locations they are given are the ones of the deserialized value, and they should be
+/// quoted as is.
+///
+/// ## Example
+/// ```
+/// use deserr::{
+///     deserialize,
+///     errors::{json::location_json_pointer, localized::ErrorMessages, LocalizedError},
+///     Deserr, ValuePointerRef,
+/// };
+/// use serde_json::json;
+///
+/// /// The messages in French, which keep the English messages of the unlikely errors.
+/// struct French;
+///
+/// impl ErrorMessages for French {
+///     fn location(location: ValuePointerRef, _article: &str) -> String {
+///         format!(" (à `{}`)", location_json_pointer(location))
+///     }
+///
+///     fn missing_field(field: &str, location: ValuePointerRef) -> String {
+///         format!("Le champ `{field}` est manquant{}", Self::location(location, ""))
+///     }
+/// }
+///
+/// #[derive(Deserr, Debug)]
+/// struct Doggo {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let error = deserialize::<Vec<Doggo>, _, LocalizedError<French>>(json!([{ "name": "Kefir" }]))
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "Le champ `age` est manquant (à `/0`)");
+///
+/// let error =
+///     deserialize::<Vec<Doggo>, _, LocalizedError<French>>(json!([{ "name": 2, "age": 2 }]))
+///         .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid value type (à `/0/name`): expected a string, but found a positive integer: `2`",
+/// );
+/// ```
+pub trait ErrorMessages {
+    /// Describe the location of an error, preceded by the given article such as `" at"`,
+    /// or return an empty string if it is the origin, see [`location_json_description`].
+    fn location(location: ValuePointerRef, article: &str) -> String {
+        location_json_description(location, article)
+    }
+
+    /// The message of an [`ErrorKind::IncorrectValueKind`].
+    fn incorrect_value_kind<V: IntoValue>(
+        actual: Value<V>,
+        accepted: &[ValueKind],
+        location: ValuePointerRef,
+    ) -> String {
+        let expected = value_kinds_description_json(accepted);
+        let received = value_description_with_kind_json(&serde_json::Value::from(actual));
+        let location = Self::location(location, " at");
+        format!("Invalid value type{location}: expected {expected}, but found {received}")
+    }
+
+    /// The message of an [`ErrorKind::MissingField`].
+    fn missing_field(field: &str, location: ValuePointerRef) -> String {
+        let location = Self::location(location, " inside");
+        format!("Missing field `{field}`{location}")
+    }
+
+    /// The message of an [`ErrorKind::UnknownKey`].
+    fn unknown_key(key: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+        let location = Self::location(location, " inside");
+        format!(
+            "Unknown field `{}`{location}: {}expected one of {}",
+            key,
+            did_you_mean(key, accepted),
+            accepted
+                .iter()
+                .map(|accepted| format!("`{}`", accepted))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+
+    /// The message of an [`ErrorKind::UnknownValue`].
+    fn unknown_value(value: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+        let location = Self::location(location, " at");
+        format!(
+            "Unknown value `{}`{location}: {}expected one of {}",
+            value,
+            did_you_mean(value, accepted),
+            accepted
+                .iter()
+                .map(|accepted| format!("`{}`", accepted))
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    }
+
+    /// The message of an [`ErrorKind::Unexpected`].
+    fn unexpected(msg: &str, location: ValuePointerRef) -> String {
+        let location = Self::location(location, " at");
+        format!("Invalid value{location}: {msg}")
+    }
+
+    /// Build the message of the given error with the method of its kind.
+    fn message<V: IntoValue>(error: ErrorKind<V>, location: ValuePointerRef) -> String {
+        match error {
+            ErrorKind::IncorrectValueKind { actual, accepted } => {
+                Self::incorrect_value_kind(actual, accepted, location)
+            }
+            ErrorKind::MissingField { field } => Self::missing_field(field, location),
+            ErrorKind::UnknownKey { key, accepted } => Self::unknown_key(key, accepted, location),
+            ErrorKind::UnknownValue { value, accepted } => {
+                Self::unknown_value(value, accepted, location)
+            }
+            ErrorKind::Unexpected { msg } => Self::unexpected(&msg, location),
+        }
+    }
+}
+
+/// An error whose message is built by the set of messages `L`, and which stops the
+/// deserialization at the first error.
+pub struct LocalizedError<L> {
+    msg: String,
+    kind: ErrorKindTag,
+    location: ValuePointer,
+    messages: PhantomData<fn() -> L>,
+}
+
+impl<L> LocalizedError<L> {
+    /// Return the kind of error that occurred, e.g. [`ErrorKindTag::MissingField`].
+    ///
+    /// The errors merged from another error type are [`ErrorKindTag::Unexpected`].
+    pub fn kind(&self) -> ErrorKindTag {
+        self.kind
+    }
+
+    /// Return the location of the value that caused the error.
+    pub fn location(&self) -> &ValuePointer {
+        &self.location
+    }
+}
+
+// Implemented by hand to not require `L` to implement these traits
+
+impl<L> Clone for LocalizedError<L> {
+    fn clone(&self) -> Self {
+        LocalizedError {
+            msg: self.msg.clone(),
+            kind: self.kind,
+            location: self.location.clone(),
+            messages: PhantomData,
+        }
+    }
+}
+
+impl<L> Display for LocalizedError<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+// The location is already described by the message
+impl<L> Debug for LocalizedError<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("LocalizedError").field(&self.msg).finish()
+    }
+}
+
+impl<L: ErrorMessages> DeserializeError for LocalizedError<L> {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let kind = error.tag();
+        ControlFlow::Break(LocalizedError {
+            msg: L::message(error, location),
+            kind,
+            location: location.to_owned(),
+            messages: PhantomData,
+        })
+    }
+}
+
+impl<L> MergeWithError<LocalizedError<L>> for LocalizedError<L> {
+    fn merge(
+        _self_: Option<Self>,
+        other: LocalizedError<L>,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+impl<L: ErrorMessages, E: std::error::Error> MergeWithError<E> for LocalizedError<L> {
+    fn merge(
+        self_: Option<Self>,
+        other: E,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        LocalizedError::error::<core::convert::Infallible>(
+            self_,
+            ErrorKind::Unexpected {
+                msg: other.to_string(),
+            },
+            merge_location,
+        )
+    }
+}
@@ -1,11 +1,22 @@
 //! This module holds some pre-made error types to eases your usage of deserr
 
+pub mod accumulate;
+pub mod fail_fast;
+#[cfg(feature = "std")]
 pub mod helpers;
+#[cfg(feature = "std")]
 pub mod query_params;
 
+pub use accumulate::AccumulatedErrors;
+pub use fail_fast::FailFast;
+#[cfg(feature = "std")]
 pub use query_params::QueryParamError;
 
 #[cfg(feature = "serde-json")]
 pub mod json;
 #[cfg(feature = "serde-json")]
 pub use json::JsonError;
+#[cfg(feature = "serde-json")]
+pub mod localized;
+#[cfg(feature = "serde-json")]
+pub use localized::LocalizedError;
@@ -5,23 +5,54 @@
 //! messages.
 
 use crate::{DeserializeError, MergeWithError};
-use deserr::{ErrorKind, IntoValue, ValueKind, ValuePointerRef};
-use std::{convert::Infallible, fmt::Display, ops::ControlFlow};
+use core::{
+    convert::Infallible,
+    fmt::{Debug, Display},
+    ops::ControlFlow,
+};
+use deserr::{ErrorKind, ErrorKindTag, IntoValue, ValueKind, ValuePointer, ValuePointerRef};
 
 use super::helpers::did_you_mean;
 
-#[derive(Debug, Clone)]
-pub struct QueryParamError(String);
+#[derive(Clone)]
+pub struct QueryParamError {
+    msg: String,
+    kind: ErrorKindTag,
+    location: ValuePointer,
+}
 
 impl Display for QueryParamError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+// The location is already described by the message
+impl Debug for QueryParamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("QueryParamError").field(&self.msg).finish()
     }
 }
 
 impl QueryParamError {
-    fn new(msg: String) -> Self {
-        QueryParamError(msg)
+    fn new(msg: String, kind: ErrorKindTag, location: ValuePointerRef) -> Self {
+        QueryParamError {
+            msg,
+            kind,
+            location: location.to_owned(),
+        }
+    }
+
+    /// Return the kind of error that occurred, e.g. [`ErrorKindTag::MissingField`].
+    ///
+    /// The errors merged from another error type are [`ErrorKindTag::Unexpected`].
+    pub fn kind(&self) -> ErrorKindTag {
+        self.kind
+    }
+
+    /// Return the location of the value that caused the error.
+    pub fn location(&self) -> &ValuePointer {
+        &self.location
     }
 }
 
@@ -31,55 +62,99 @@ impl deserr::DeserializeError for QueryParamError {
         error: deserr::ErrorKind<V>,
         location: ValuePointerRef,
     ) -> ControlFlow<Self, Self> {
-        let mut message = String::new();
-
-        message.push_str(&match error {
+        let kind = error.tag();
+        let message = match error {
             ErrorKind::IncorrectValueKind { actual, accepted } => {
-                let expected = value_kinds_description_query_param(accepted);
-                let received = value_description_with_kind_query_param(actual);
-
-                let location = location_query_param_description(location, " for parameter");
-
-                format!("Invalid value type{location}: expected {expected}, but found {received}")
-            }
-            ErrorKind::MissingField { field } => {
-                let location = location_query_param_description(location, " inside");
-                format!("Missing parameter `{field}`{location}")
-            }
-            ErrorKind::UnknownKey { key, accepted } => {
-                let location = location_query_param_description(location, " inside");
-                format!(
-                    "Unknown parameter `{}`{location}: {}expected one of {}",
-                    key,
-                    did_you_mean(key, accepted),
-                    accepted
-                        .iter()
-                        .map(|accepted| format!("`{}`", accepted))
-                        .collect::<Vec<String>>()
-                        .join(", ")
-                )
+                incorrect_value_kind_message(actual, accepted, location)
             }
+            ErrorKind::MissingField { field } => missing_field_message(field, location),
+            ErrorKind::UnknownKey { key, accepted } => unknown_key_message(key, accepted, location),
             ErrorKind::UnknownValue { value, accepted } => {
-                let location = location_query_param_description(location, " for parameter");
-                format!(
-                    "Unknown value `{}`{location}: {}expected one of {}",
-                    value,
-                    did_you_mean(value, accepted),
-                    accepted
-                        .iter()
-                        .map(|accepted| format!("`{}`", accepted))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                )
-            }
-            ErrorKind::Unexpected { msg } => {
-                let location = location_query_param_description(location, " in parameter");
-                format!("Invalid value{location}: {msg}")
+                unknown_value_message(value, accepted, location)
             }
-        });
+            ErrorKind::Unexpected { msg } => unexpected_message(&msg, location),
+        };
+        ControlFlow::Break(QueryParamError::new(message, kind, location))
+    }
+}
+
+/// The messages of [`QueryParamError`], which a localized set can fall back to.
+#[cfg(feature = "serde-json")]
+impl super::localized::ErrorMessages for QueryParamError {
+    fn incorrect_value_kind<V: IntoValue>(
+        actual: deserr::Value<V>,
+        accepted: &[ValueKind],
+        location: ValuePointerRef,
+    ) -> String {
+        incorrect_value_kind_message(actual, accepted, location)
+    }
+
+    fn missing_field(field: &str, location: ValuePointerRef) -> String {
+        missing_field_message(field, location)
+    }
 
-        ControlFlow::Break(QueryParamError::new(message))
+    fn unknown_key(key: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+        unknown_key_message(key, accepted, location)
     }
+
+    fn unknown_value(value: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+        unknown_value_message(value, accepted, location)
+    }
+
+    fn unexpected(msg: &str, location: ValuePointerRef) -> String {
+        unexpected_message(msg, location)
+    }
+}
+
+fn incorrect_value_kind_message<V: IntoValue>(
+    actual: deserr::Value<V>,
+    accepted: &[ValueKind],
+    location: ValuePointerRef,
+) -> String {
+    let expected = value_kinds_description_query_param(accepted);
+    let received = value_description_with_kind_query_param(actual);
+
+    let location = location_query_param_description(location, " for parameter");
+
+    format!("Invalid value type{location}: expected {expected}, but found {received}")
+}
+
+fn missing_field_message(field: &str, location: ValuePointerRef) -> String {
+    let location = location_query_param_description(location, " inside");
+    format!("Missing parameter `{field}`{location}")
+}
+
+fn unknown_key_message(key: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+    let location = location_query_param_description(location, " inside");
+    format!(
+        "Unknown parameter `{}`{location}: {}expected one of {}",
+        key,
+        did_you_mean(key, accepted),
+        accepted
+            .iter()
+            .map(|accepted| format!("`{}`", accepted))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+fn unknown_value_message(value: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+    let location = location_query_param_description(location, " for parameter");
+    format!(
+        "Unknown value `{}`{location}: {}expected one of {}",
+        value,
+        did_you_mean(value, accepted),
+        accepted
+            .iter()
+            .map(|accepted| format!("`{}`", accepted))
+            .collect::<Vec<String>>()
+            .join(", "),
+    )
+}
+
+fn unexpected_message(msg: &str, location: ValuePointerRef) -> String {
+    let location = location_query_param_description(location, " in parameter");
+    format!("Invalid value{location}: {msg}")
 }
 
 /// Return a description of the list of value kinds for query parameters
@@ -280,11 +355,33 @@ mod tests {
         }
         let value = json!({ "me": [2] });
         let err = deserr::deserialize::<UnexpectedTuple, _, QueryParamError>(value).unwrap_err();
-        insta::assert_snapshot!(err, @"Invalid value in parameter `me`: the sequence should have exactly 2 elements");
+        insta::assert_snapshot!(err, @"Invalid value in parameter `me`: expected a sequence of 2 elements but instead found a sequence of 1 element");
 
         let value = json!({ "me": [2, 3, 4] });
         let err = deserr::deserialize::<UnexpectedTuple, _, QueryParamError>(value).unwrap_err();
-        insta::assert_snapshot!(err, @"Invalid value in parameter `me`: the sequence should have exactly 2 elements");
+        insta::assert_snapshot!(err, @"Invalid value in parameter `me`: expected a sequence of 2 elements but instead found a sequence of 3 elements");
+    }
+
+    #[test]
+    fn error_location() {
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        struct Named {
+            name: String,
+        }
+
+        #[allow(dead_code)]
+        #[derive(deserr::Deserr, Debug)]
+        struct Nested {
+            me: Vec<Named>,
+        }
+        let value = json!({ "me": [{ "name": "a" }, { "name": 2 }] });
+        let err = deserr::deserialize::<Nested, _, QueryParamError>(value).unwrap_err();
+        insta::assert_snapshot!(err.location(), @".me[1].name");
+
+        let value = json!([]);
+        let err = deserr::deserialize::<Nested, _, QueryParamError>(value).unwrap_err();
+        insta::assert_snapshot!(err.location(), @"");
     }
 
     #[test]
@@ -0,0 +1,125 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{IntoValue, Map, Sequence, Value, ValueKind};
+
+/// The value given to a field with the `#[deserr(flatten)]` attribute.
+///
+/// The flattened field is deserialized from a map made of all the entries of the
+/// outer map that were not claimed by another field. Everything nested inside this
+/// map is given as is, so a `FlattenedValue<V>` is deserialized exactly like a `V`.
+#[derive(Debug)]
+pub enum FlattenedValue<V> {
+    /// The entries of the outer map that no other field claimed, in their original order.
+    Leftovers(Vec<(String, V)>),
+    /// A value nested inside the leftover entries.
+    Value(V),
+}
+
+impl<V: IntoValue> IntoValue for FlattenedValue<V> {
+    type Sequence = FlattenedSequence<V>;
+    type Map = FlattenedMap<V>;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            FlattenedValue::Leftovers(_) => ValueKind::Map,
+            FlattenedValue::Value(v) => v.kind(),
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        let value = match self {
+            FlattenedValue::Leftovers(entries) => {
+                return Value::Map(FlattenedMap::Leftovers(entries))
+            }
+            FlattenedValue::Value(value) => value.into_value(),
+        };
+        match value {
+            Value::Null => Value::Null,
+            Value::Boolean(b) => Value::Boolean(b),
+            Value::Integer(n) => Value::Integer(n),
+            Value::NegativeInteger(n) => Value::NegativeInteger(n),
+            Value::Float(n) => Value::Float(n),
+            Value::String(s) => Value::String(s),
+            Value::Sequence(seq) => Value::Sequence(FlattenedSequence(seq)),
+            Value::Map(map) => Value::Map(FlattenedMap::Map(map)),
+        }
+    }
+}
+
+/// A sequence nested inside a [`FlattenedValue`].
+#[derive(Debug)]
+pub struct FlattenedSequence<V: IntoValue>(V::Sequence);
+
+impl<V: IntoValue> Sequence for FlattenedSequence<V> {
+    type Value = FlattenedValue<V>;
+    type Iter = core::iter::Map<<V::Sequence as Sequence>::Iter, fn(V) -> FlattenedValue<V>>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        self.0.into_iter().map(FlattenedValue::Value)
+    }
+}
+
+/// The map of a [`FlattenedValue`], either the leftover entries or a map nested inside them.
+#[derive(Debug)]
+pub enum FlattenedMap<V: IntoValue> {
+    Leftovers(Vec<(String, V)>),
+    Map(V::Map),
+}
+
+impl<V: IntoValue> Map for FlattenedMap<V> {
+    type Value = FlattenedValue<V>;
+    type Iter = FlattenedMapIter<V>;
+
+    fn len(&self) -> usize {
+        match self {
+            FlattenedMap::Leftovers(entries) => entries.len(),
+            FlattenedMap::Map(map) => map.len(),
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        match self {
+            FlattenedMap::Leftovers(entries) => {
+                let index = entries.iter().position(|(k, _)| k == key)?;
+                Some(FlattenedValue::Value(entries.remove(index).1))
+            }
+            FlattenedMap::Map(map) => map.remove(key).map(FlattenedValue::Value),
+        }
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        match self {
+            FlattenedMap::Leftovers(entries) => FlattenedMapIter::Leftovers(entries.into_iter()),
+            FlattenedMap::Map(map) => FlattenedMapIter::Map(map.into_iter()),
+        }
+    }
+
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        match self {
+            FlattenedMap::Leftovers(_) => None,
+            FlattenedMap::Map(map) => map.unsupported_key(),
+        }
+    }
+}
+
+/// Iterator over the entries of a [`FlattenedMap`].
+pub enum FlattenedMapIter<V: IntoValue> {
+    Leftovers(alloc::vec::IntoIter<(String, V)>),
+    Map(<V::Map as Map>::Iter),
+}
+
+impl<V: IntoValue> Iterator for FlattenedMapIter<V> {
+    type Item = (String, FlattenedValue<V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = match self {
+            FlattenedMapIter::Leftovers(iter) => iter.next()?,
+            FlattenedMapIter::Map(iter) => iter.next()?,
+        };
+        Some((key, FlattenedValue::Value(value)))
+    }
+}
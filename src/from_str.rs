@@ -0,0 +1,108 @@
+use alloc::string::ToString;
+use core::{
+    fmt::Display,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, IntoValueRef, Value,
+    ValueKind, ValuePointerRef, ValueRef,
+};
+
+/// A value deserialized from a string with the [`FromStr`] implementation of `T`.
+///
+/// It saves a `try_from(&String) = FromStr::from_str -> T::Err` attribute on every field
+/// whose type implements `FromStr`. The error of `from_str` is reported as an
+/// [`ErrorKind::Unexpected`] error, with its [`Display`] text as the message. The values
+/// that aren't strings are rejected.
+///
+/// ```
+/// use deserr::{deserialize, errors::JsonError, Deserr, FromStrWrapper};
+/// use serde_json::json;
+/// use std::net::IpAddr;
+///
+/// #[derive(Debug, Deserr)]
+/// struct Config {
+///     host: FromStrWrapper<IpAddr>,
+/// }
+///
+/// let config = deserialize::<Config, _, JsonError>(json!({ "host": "127.0.0.1" })).unwrap();
+/// assert!(config.host.is_loopback());
+///
+/// let error = deserialize::<Config, _, JsonError>(json!({ "host": "localhost" })).unwrap_err();
+/// assert_eq!(error.to_string(), "Invalid value at `.host`: invalid IP address syntax");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FromStrWrapper<T>(pub T);
+
+impl<T> FromStrWrapper<T> {
+    /// Return the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for FromStrWrapper<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for FromStrWrapper<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Parse the string, or return an error located at `location`.
+fn parse<T, E>(s: &str, location: ValuePointerRef) -> Result<FromStrWrapper<T>, E>
+where
+    T: FromStr,
+    T::Err: Display,
+    E: DeserializeError,
+{
+    T::from_str(s).map(FromStrWrapper).map_err(|e| {
+        take_cf_content(E::error::<core::convert::Infallible>(
+            None,
+            ErrorKind::Unexpected { msg: e.to_string() },
+            location,
+        ))
+    })
+}
+
+impl<T, E> Deserr<E> for FromStrWrapper<T>
+where
+    T: FromStr,
+    T::Err: Display,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::String(s) => parse(&s, location),
+            value => Err(take_cf_content(E::incorrect_value_kind_error::<V>(
+                None,
+                value,
+                &[ValueKind::String],
+                core::any::type_name::<Self>(),
+                location,
+            ))),
+        }
+    }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        // The string is parsed without being copied
+        match value.into_value_ref() {
+            ValueRef::String(s) => parse(s, location),
+            value => Self::deserialize_from_value(value.into_owned(), location),
+        }
+    }
+}
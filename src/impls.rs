@@ -1,18 +1,36 @@
 use crate::{
-    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
-    ValueKind, ValuePointerRef,
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, IntoValueRef, Map, MapRef,
+    Sequence, SequenceRef, Value, ValueKind, ValuePointerRef, ValueRef,
 };
-use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, LinkedList, VecDeque},
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
     convert::{Infallible, TryFrom},
-    hash::Hash,
+    fmt::Display,
     marker::PhantomData,
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
-        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
     },
-    ops::ControlFlow,
+    ops::{ControlFlow, Range, RangeInclusive},
     str::FromStr,
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    hash::Hash,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    path::PathBuf,
 };
 
 impl<T, E> Deserr<E> for PhantomData<T>
@@ -69,12 +87,11 @@ where
     ) -> Result<Self, E> {
         match value {
             Value::Null => Ok(()),
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Null],
-                },
+                v,
+                &[ValueKind::Null],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
@@ -91,12 +108,11 @@ where
     ) -> Result<Self, E> {
         match value {
             Value::Boolean(b) => Ok(b),
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Boolean],
-                },
+                v,
+                &[ValueKind::Boolean],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
@@ -116,12 +132,11 @@ macro_rules! deserialize_impl_integer {
                 use $crate::take_cf_content;
 
                 let err = |value: Value<V>| {
-                    E::error(
+                    E::incorrect_value_kind_error(
                         None,
-                        ErrorKind::IncorrectValueKind {
-                            actual: value,
-                            accepted: &[ValueKind::Integer],
-                        },
+                        value,
+                        &[ValueKind::Integer],
+                        core::any::type_name::<Self>(),
                         location,
                     )
                 };
@@ -166,12 +181,11 @@ macro_rules! deserialize_impl_non_zero_integer {
                 use $crate::take_cf_content;
 
                 let err = |value: Value<V>| {
-                    E::error(
+                    E::incorrect_value_kind_error(
                         None,
-                        ErrorKind::IncorrectValueKind {
-                            actual: value,
-                            accepted: &[ValueKind::Integer],
-                        },
+                        value,
+                        &[ValueKind::Integer],
+                        core::any::type_name::<Self>(),
                         location,
                     )
                 };
@@ -181,10 +195,7 @@ macro_rules! deserialize_impl_non_zero_integer {
                       Err(take_cf_content(E::error::<V>(
                           None,
                           ErrorKind::Unexpected {
-                              msg: format!(
-                                  "a non-zero integer value lower than `{}` was expected, but found a zero",
-                                  <$t>::MAX
-                              ),
+                              msg: String::from("value: `0` is invalid, the value must be non-zero"),
                           },
                           location,
                       )))
@@ -228,12 +239,11 @@ macro_rules! deserialize_impl_negative_integer {
                 use $crate::take_cf_content;
 
                 let err = |value: Value<V>| {
-                    E::error(
+                    E::incorrect_value_kind_error(
                         None,
-                        ErrorKind::IncorrectValueKind {
-                            actual: value,
-                            accepted: &[ValueKind::Integer, ValueKind::NegativeInteger],
-                        },
+                        value,
+                        &[ValueKind::Integer, ValueKind::NegativeInteger],
+                        core::any::type_name::<Self>(),
                         location,
                     )
                 };
@@ -290,12 +300,11 @@ macro_rules! deserialize_impl_non_zero_negative_integer {
                 use $crate::take_cf_content;
 
                 let err = |value: Value<V>| {
-                    E::error(
+                    E::incorrect_value_kind_error(
                         None,
-                        ErrorKind::IncorrectValueKind {
-                            actual: value,
-                            accepted: &[ValueKind::Integer, ValueKind::NegativeInteger],
-                        },
+                        value,
+                        &[ValueKind::Integer, ValueKind::NegativeInteger],
+                        core::any::type_name::<Self>(),
                         location,
                     )
                 };
@@ -305,10 +314,7 @@ macro_rules! deserialize_impl_non_zero_negative_integer {
                       Err(take_cf_content(E::error::<V>(
                           None,
                           ErrorKind::Unexpected {
-                              msg: format!(
-                                  "a non-zero integer value higher than `{}` was expected, but found a zero",
-                                  <$t>::MIN
-                              ),
+                              msg: String::from("value: `0` is invalid, the value must be non-zero"),
                           },
                           location,
                       )))
@@ -317,10 +323,7 @@ macro_rules! deserialize_impl_non_zero_negative_integer {
                       Err(take_cf_content(E::error::<V>(
                           None,
                           ErrorKind::Unexpected {
-                              msg: format!(
-                                  "a non-zero integer value higher than `{}` was expected, but found a zero",
-                                  <$t>::MIN
-                              ),
+                              msg: String::from("value: `0` is invalid, the value must be non-zero"),
                           },
                           location,
                       )))
@@ -363,6 +366,78 @@ deserialize_impl_non_zero_negative_integer!(NonZeroI64);
 deserialize_impl_non_zero_negative_integer!(NonZeroI128);
 deserialize_impl_non_zero_negative_integer!(NonZeroIsize);
 
+// The integers out of the range of the type are clamped to its bounds instead of being an
+// error, which is also what the `saturating` field attribute relies on. The integers that don't
+// fit in a `u64` or an `i64` are given as floats, so the integral floats are clamped too.
+macro_rules! deserialize_impl_saturating_integer {
+    ($t:ty) => {
+        impl<E> Deserr<E> for Saturating<$t>
+        where
+            E: DeserializeError,
+        {
+            fn deserialize_from_value<V: IntoValue>(
+                value: Value<V>,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                match value {
+                    Value::Integer(x) => Ok(Saturating(<$t>::try_from(x).unwrap_or(<$t>::MAX))),
+                    Value::NegativeInteger(x) => {
+                        Ok(Saturating(<$t>::try_from(x).unwrap_or(if x < 0 {
+                            <$t>::MIN
+                        } else {
+                            <$t>::MAX
+                        })))
+                    }
+                    // The casts from a float to an integer saturate
+                    Value::Float(x) if x.is_finite() && x % 1.0 == 0.0 => Ok(Saturating(x as $t)),
+                    v => Err(take_cf_content(E::incorrect_value_kind_error(
+                        None,
+                        v,
+                        &[ValueKind::Integer, ValueKind::NegativeInteger],
+                        core::any::type_name::<Self>(),
+                        location,
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+deserialize_impl_saturating_integer!(u8);
+deserialize_impl_saturating_integer!(u16);
+deserialize_impl_saturating_integer!(u32);
+deserialize_impl_saturating_integer!(u64);
+deserialize_impl_saturating_integer!(u128);
+deserialize_impl_saturating_integer!(usize);
+deserialize_impl_saturating_integer!(i8);
+deserialize_impl_saturating_integer!(i16);
+deserialize_impl_saturating_integer!(i32);
+deserialize_impl_saturating_integer!(i64);
+deserialize_impl_saturating_integer!(i128);
+deserialize_impl_saturating_integer!(isize);
+
+impl<T, E> Deserr<E> for Wrapping<T>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value(value, location).map(Wrapping)
+    }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value_ref(value, location).map(Wrapping)
+    }
+}
+
+// The integers are converted to the nearest float. A finite float that is out of the range
+// of `f32` is an error, while the values too close to zero are rounded to zero.
 macro_rules! deserialize_impl_float {
     ($t:ty) => {
         impl<E> Deserr<E> for $t
@@ -376,17 +451,35 @@ macro_rules! deserialize_impl_float {
                 match value {
                     Value::Integer(x) => Ok(x as $t),
                     Value::NegativeInteger(x) => Ok(x as $t),
+                    // A finite value that doesn't fit in the type is refused instead of becoming infinite
+                    Value::Float(x) if x.is_finite() && (x as $t).is_infinite() => {
+                        let msg = if x > 0.0 {
+                            format!(
+                                "value: `{x:?}` is too large to be deserialized, maximum value authorized is `{:?}`",
+                                <$t>::MAX
+                            )
+                        } else {
+                            format!(
+                                "value: `{x:?}` is too small to be deserialized, minimum value authorized is `{:?}`",
+                                <$t>::MIN
+                            )
+                        };
+                        Err($crate::take_cf_content(E::error::<V>(
+                            None,
+                            ErrorKind::Unexpected { msg },
+                            location,
+                        )))
+                    }
                     Value::Float(x) => Ok(x as $t),
-                    v => Err($crate::take_cf_content(E::error(
+                    v => Err($crate::take_cf_content(E::incorrect_value_kind_error(
                         None,
-                        ErrorKind::IncorrectValueKind {
-                            actual: v,
-                            accepted: &[
+                        v,
+                        &[
                                 ValueKind::Float,
                                 ValueKind::Integer,
                                 ValueKind::NegativeInteger,
                             ],
-                        },
+                        core::any::type_name::<Self>(),
                         location,
                     ))),
                 }
@@ -434,12 +527,11 @@ where
                     )))
                 }
             }
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::String],
-                },
+                v,
+                &[ValueKind::String],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
@@ -456,64 +548,200 @@ where
     ) -> Result<Self, E> {
         match value {
             Value::String(x) => Ok(x),
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::String],
-                },
+                v,
+                &[ValueKind::String],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
     }
 }
 
-impl<T, E> Deserr<E> for Vec<T>
+#[cfg(feature = "std")]
+macro_rules! deserialize_impl_from_str {
+    ($t:ty) => {
+        impl<E> Deserr<E> for $t
+        where
+            E: DeserializeError,
+        {
+            fn deserialize_from_value<V: IntoValue>(
+                value: Value<V>,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                match value {
+                    Value::String(s) => s.parse::<$t>().map_err(|e| {
+                        take_cf_content(E::error::<Infallible>(
+                            None,
+                            ErrorKind::Unexpected {
+                                msg: format!("{e}: `{s}`"),
+                            },
+                            location,
+                        ))
+                    }),
+                    v => Err(take_cf_content(E::incorrect_value_kind_error(
+                        None,
+                        v,
+                        &[ValueKind::String],
+                        core::any::type_name::<Self>(),
+                        location,
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+deserialize_impl_from_str!(IpAddr);
+#[cfg(feature = "std")]
+deserialize_impl_from_str!(Ipv4Addr);
+#[cfg(feature = "std")]
+deserialize_impl_from_str!(Ipv6Addr);
+#[cfg(feature = "std")]
+deserialize_impl_from_str!(SocketAddr);
+#[cfg(feature = "std")]
+deserialize_impl_from_str!(SocketAddrV4);
+#[cfg(feature = "std")]
+deserialize_impl_from_str!(SocketAddrV6);
+
+/// Parse a duration made of an integer followed by a unit, such as `500ms` or `2s`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let unit_start = s.find(|c: char| !c.is_ascii_digit())?;
+    let (n, unit) = s.split_at(unit_start);
+    let n = n.parse::<u64>().ok()?;
+    match unit {
+        "ns" => Some(Duration::from_nanos(n)),
+        "us" => Some(Duration::from_micros(n)),
+        "ms" => Some(Duration::from_millis(n)),
+        "s" => Some(Duration::from_secs(n)),
+        "m" => n.checked_mul(60).map(Duration::from_secs),
+        "h" => n.checked_mul(60 * 60).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// A duration is either an integer number of seconds, or a string made of an integer
+/// followed by one of the units `ns`, `us`, `ms`, `s`, `m`, or `h`.
+impl<E> Deserr<E> for Duration
 where
-    T: Deserr<E>,
     E: DeserializeError,
 {
     fn deserialize_from_value<V: IntoValue>(
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E> {
-        match value {
-            Value::Sequence(seq) => {
-                let mut error = None;
-                let mut vec = Vec::with_capacity(seq.len());
-                for (index, value) in seq.into_iter().enumerate() {
-                    let result =
-                        T::deserialize_from_value(value.into_value(), location.push_index(index));
-                    match result {
-                        Ok(value) => {
-                            vec.push(value);
+        let found = match value {
+            Value::Integer(x) => return Ok(Duration::from_secs(x)),
+            Value::String(s) => match parse_duration(&s) {
+                Some(duration) => return Ok(duration),
+                None => format!("`{s}`"),
+            },
+            v => format!("a value of kind {}", ValueKind::describe_group(&[v.kind()])),
+        };
+        Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "expected an integer number of seconds or a string such as `500ms`, `2s`, or `1m` \
+                     with one of the units `ns`, `us`, `ms`, `s`, `m`, or `h`, but found {found}"
+                ),
+            },
+            location,
+        )))
+    }
+}
+
+macro_rules! deserialize_impl_sequence {
+    ($ty:ident, $new:expr, $push:ident) => {
+        impl<T, E> Deserr<E> for $ty<T>
+        where
+            T: Deserr<E>,
+            E: DeserializeError,
+        {
+            fn deserialize_from_value<V: IntoValue>(
+                value: Value<V>,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                match value {
+                    Value::Sequence(seq) => {
+                        let mut error = None;
+                        let mut collection = ($new)(seq.size_hint().0);
+                        for (index, value) in seq.into_iter().enumerate() {
+                            let result = T::deserialize_from_value(
+                                value.into_value(),
+                                location.push_index(index),
+                            );
+                            match result {
+                                Ok(value) => {
+                                    collection.$push(value);
+                                }
+                                Err(e) => {
+                                    error = match E::merge(error, e, location.push_index(index)) {
+                                        ControlFlow::Continue(e) => Some(e),
+                                        ControlFlow::Break(e) => return Err(e),
+                                    };
+                                }
+                            }
                         }
-                        Err(e) => {
-                            error = match E::merge(error, e, location.push_index(index)) {
-                                ControlFlow::Continue(e) => Some(e),
-                                ControlFlow::Break(e) => return Err(e),
-                            };
+                        if let Some(e) = error {
+                            Err(e)
+                        } else {
+                            Ok(collection)
                         }
                     }
+                    v => Err(take_cf_content(E::incorrect_value_kind_error(
+                        None,
+                        v,
+                        &[ValueKind::Sequence],
+                        core::any::type_name::<Self>(),
+                        location,
+                    ))),
                 }
-                if let Some(e) = error {
-                    Err(e)
-                } else {
-                    Ok(vec)
+            }
+
+            fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+                value: V,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                match value.into_value_ref() {
+                    ValueRef::Sequence(seq) => {
+                        let mut error = None;
+                        let mut collection = ($new)(seq.size_hint().0);
+                        for (index, value) in seq.into_iter().enumerate() {
+                            let result =
+                                T::deserialize_from_value_ref(value, location.push_index(index));
+                            match result {
+                                Ok(value) => {
+                                    collection.$push(value);
+                                }
+                                Err(e) => {
+                                    error = match E::merge(error, e, location.push_index(index)) {
+                                        ControlFlow::Continue(e) => Some(e),
+                                        ControlFlow::Break(e) => return Err(e),
+                                    };
+                                }
+                            }
+                        }
+                        if let Some(e) = error {
+                            Err(e)
+                        } else {
+                            Ok(collection)
+                        }
+                    }
+                    v => Self::deserialize_from_value(v.into_owned(), location),
                 }
             }
-            v => Err(take_cf_content(E::error(
-                None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Sequence],
-                },
-                location,
-            ))),
         }
-    }
+    };
 }
 
+deserialize_impl_sequence!(Vec, Vec::with_capacity, push);
+deserialize_impl_sequence!(VecDeque, VecDeque::with_capacity, push_back);
+// A linked list can't reserve the space of its elements
+deserialize_impl_sequence!(LinkedList, |_| LinkedList::new(), push_back);
+
 impl<T, E> Deserr<E> for Option<T>
 where
     T: Deserr<E>,
@@ -528,6 +756,16 @@ where
             value => T::deserialize_from_value(value, location).map(Some),
         }
     }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value.kind() {
+            ValueKind::Null => Ok(None),
+            _ => T::deserialize_from_value_ref(value, location).map(Some),
+        }
+    }
 }
 
 impl<T, E> Deserr<E> for Box<T>
@@ -541,8 +779,115 @@ where
     ) -> Result<Self, E> {
         T::deserialize_from_value(value, location).map(Box::new)
     }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value_ref(value, location).map(Box::new)
+    }
+}
+
+impl<T, E> Deserr<E> for Rc<T>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value(value, location).map(Rc::new)
+    }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value_ref(value, location).map(Rc::new)
+    }
+}
+
+impl<T, E> Deserr<E> for Arc<T>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value(value, location).map(Arc::new)
+    }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        T::deserialize_from_value_ref(value, location).map(Arc::new)
+    }
+}
+
+/// Capture the outcome of the deserialization of `T` instead of failing.
+///
+/// If `T` can't be deserialized, the rendered error is stored in the `Err` variant and the
+/// deserialization goes on, which lets a struct keep its valid fields while reporting the
+/// invalid ones. Note that a missing field is still an error of the struct itself.
+impl<T, E> Deserr<E> for Result<T, String>
+where
+    T: Deserr<E>,
+    E: DeserializeError + Display,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        Ok(T::deserialize_from_value(value, location).map_err(|e| e.to_string()))
+    }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        Ok(T::deserialize_from_value_ref(value, location).map_err(|e| e.to_string()))
+    }
+}
+
+macro_rules! deserialize_impl_from_string {
+    ($t:ty) => {
+        impl<E> Deserr<E> for $t
+        where
+            E: DeserializeError,
+        {
+            fn deserialize_from_value<V: IntoValue>(
+                value: Value<V>,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                String::deserialize_from_value(value, location).map(<$t>::from)
+            }
+
+            fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+                value: V,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                String::deserialize_from_value_ref(value, location).map(<$t>::from)
+            }
+        }
+    };
 }
 
+deserialize_impl_from_string!(Box<str>);
+deserialize_impl_from_string!(Rc<str>);
+deserialize_impl_from_string!(Arc<str>);
+deserialize_impl_from_string!(Cow<'static, str>);
+#[cfg(feature = "std")]
+deserialize_impl_from_string!(PathBuf);
+#[cfg(feature = "std")]
+deserialize_impl_from_string!(OsString);
+
+/// The keys of the map are parsed with [`FromStr`], so a `HashMap<u32, T>` can be deserialized
+/// from `{ "1": .., "2": .. }`. A key that can't be parsed is reported at its own location.
+#[cfg(feature = "std")]
 impl<Key, T, E> Deserr<E> for HashMap<Key, T>
 where
     Key: FromStr + Hash + Eq,
@@ -555,6 +900,7 @@ where
     ) -> Result<Self, E> {
         match value {
             Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
                 let mut error = None;
                 let mut res = HashMap::with_capacity(map.len());
                 for (string_key, value) in map.into_iter() {
@@ -582,29 +928,85 @@ where
                                 ErrorKind::Unexpected {
                                     msg: format!(
                                     "the key \"{string_key}\" could not be deserialized into the key type `{}`",
-                                    std::any::type_name::<Key>())
+                                    core::any::type_name::<Key>())
                                 },
-                                location) {
+                                location.push_key(&string_key)) {
                                     ControlFlow::Continue(e) => Some(e),
                                     ControlFlow::Break(e) => return Err(e),
                                 };
                         }
                     }
                 }
-                Ok(res)
+                if let Some(e) = error {
+                    Err(e)
+                } else {
+                    Ok(res)
+                }
             }
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Map],
-                },
+                v,
+                &[ValueKind::Map],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
     }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value.into_value_ref() {
+            ValueRef::Map(map) => {
+                let mut error = None;
+                let mut res = HashMap::with_capacity(map.len());
+                for (string_key, value) in map.into_iter() {
+                    match Key::from_str(string_key) {
+                        Ok(key) => {
+                            match T::deserialize_from_value_ref(
+                                value,
+                                location.push_key(string_key),
+                            ) {
+                                Ok(value) => {
+                                    res.insert(key, value);
+                                }
+                                Err(e) => {
+                                    error = match E::merge(error, e, location.push_key(string_key))
+                                    {
+                                        ControlFlow::Continue(e) => Some(e),
+                                        ControlFlow::Break(e) => return Err(e),
+                                    };
+                                }
+                            }
+                        }
+                        Err(_) => {
+                            error = match E::error::<V::Owned>(
+                                error,
+                                ErrorKind::Unexpected {
+                                    msg: format!(
+                                    "the key \"{string_key}\" could not be deserialized into the key type `{}`",
+                                    core::any::type_name::<Key>())
+                                },
+                                location.push_key(string_key)) {
+                                    ControlFlow::Continue(e) => Some(e),
+                                    ControlFlow::Break(e) => return Err(e),
+                                };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    Err(e)
+                } else {
+                    Ok(res)
+                }
+            }
+            v => Self::deserialize_from_value(v.into_owned(), location),
+        }
+    }
 }
 
+/// The keys of the map are parsed with [`FromStr`], see the [`HashMap`] implementation.
 impl<Key, T, E> Deserr<E> for BTreeMap<Key, T>
 where
     Key: FromStr + Ord,
@@ -617,6 +1019,7 @@ where
     ) -> Result<Self, E> {
         match value {
             Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
                 let mut error = None;
                 let mut res = BTreeMap::new();
                 for (string_key, value) in map.into_iter() {
@@ -643,9 +1046,9 @@ where
                                 error,
                                 ErrorKind::Unexpected {
                                     msg: format!("the key \"{string_key}\" could not be deserialized into the key type `{}`",
-                                    std::any::type_name::<Key>())
+                                    core::any::type_name::<Key>())
                                 },
-                                location
+                                location.push_key(&string_key)
                             ) {
                                 ControlFlow::Continue(e) => Some(e),
                                 ControlFlow::Break(e) => return Err(e),
@@ -653,20 +1056,78 @@ where
                         }
                     }
                 }
-                Ok(res)
+                if let Some(e) = error {
+                    Err(e)
+                } else {
+                    Ok(res)
+                }
             }
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Map],
-                },
+                v,
+                &[ValueKind::Map],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
     }
+
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value.into_value_ref() {
+            ValueRef::Map(map) => {
+                let mut error = None;
+                let mut res = BTreeMap::new();
+                for (string_key, value) in map.into_iter() {
+                    match Key::from_str(string_key) {
+                        Ok(key) => {
+                            match T::deserialize_from_value_ref(
+                                value,
+                                location.push_key(string_key),
+                            ) {
+                                Ok(value) => {
+                                    res.insert(key, value);
+                                }
+                                Err(e) => {
+                                    error = match E::merge(error, e, location.push_key(string_key))
+                                    {
+                                        ControlFlow::Continue(e) => Some(e),
+                                        ControlFlow::Break(e) => return Err(e),
+                                    };
+                                }
+                            }
+                        }
+                        Err(_) => {
+                            error = match E::error::<V::Owned>(
+                                error,
+                                ErrorKind::Unexpected {
+                                    msg: format!(
+                                    "the key \"{string_key}\" could not be deserialized into the key type `{}`",
+                                    core::any::type_name::<Key>())
+                                },
+                                location.push_key(string_key)) {
+                                    ControlFlow::Continue(e) => Some(e),
+                                    ControlFlow::Break(e) => return Err(e),
+                                };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    Err(e)
+                } else {
+                    Ok(res)
+                }
+            }
+            v => Self::deserialize_from_value(v.into_owned(), location),
+        }
+    }
 }
 
+/// The duplicate elements of the sequence are reported as errors instead of being
+/// silently ignored, since they usually indicate a mistake in the input.
+#[cfg(feature = "std")]
 impl<T, E> Deserr<E> for HashSet<T>
 where
     T: Deserr<E> + Hash + Eq,
@@ -679,13 +1140,26 @@ where
         match value {
             Value::Sequence(seq) => {
                 let mut error = None;
-                let mut set = HashSet::with_capacity(seq.len());
+                let mut set = HashSet::with_capacity(seq.size_hint().0);
                 for (index, value) in seq.into_iter().enumerate() {
                     let result =
                         T::deserialize_from_value(value.into_value(), location.push_index(index));
                     match result {
                         Ok(value) => {
-                            set.insert(value);
+                            if !set.insert(value) {
+                                error = match E::error::<V>(
+                                    error,
+                                    ErrorKind::Unexpected {
+                                        msg: String::from(
+                                            "the sequence contains this element more than once",
+                                        ),
+                                    },
+                                    location.push_index(index),
+                                ) {
+                                    ControlFlow::Continue(e) => Some(e),
+                                    ControlFlow::Break(e) => return Err(e),
+                                };
+                            }
                         }
                         Err(e) => {
                             error = match E::merge(error, e, location.push_index(index)) {
@@ -701,18 +1175,18 @@ where
                     Ok(set)
                 }
             }
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Sequence],
-                },
+                v,
+                &[ValueKind::Sequence],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
     }
 }
 
+/// As for the [`HashSet`], the duplicate elements of the sequence are reported as errors.
 impl<T, E> Deserr<E> for BTreeSet<T>
 where
     T: Deserr<E> + Ord,
@@ -731,7 +1205,20 @@ where
                         T::deserialize_from_value(value.into_value(), location.push_index(index));
                     match result {
                         Ok(value) => {
-                            set.insert(value);
+                            if !set.insert(value) {
+                                error = match E::error::<V>(
+                                    error,
+                                    ErrorKind::Unexpected {
+                                        msg: String::from(
+                                            "the sequence contains this element more than once",
+                                        ),
+                                    },
+                                    location.push_index(index),
+                                ) {
+                                    ControlFlow::Continue(e) => Some(e),
+                                    ControlFlow::Break(e) => return Err(e),
+                                };
+                            }
                         }
                         Err(e) => {
                             error = match E::merge(error, e, location.push_index(index)) {
@@ -747,12 +1234,11 @@ where
                     Ok(set)
                 }
             }
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Sequence],
-                },
+                v,
+                &[ValueKind::Sequence],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
@@ -775,7 +1261,7 @@ where
                     return Err(take_cf_content(E::error::<V>(
                         None,
                         ErrorKind::Unexpected {
-                            msg: format!("expected a sequence of {} elements but instead found a sequence of {} elements", N, len),
+                            msg: crate::describe_sequence_length(N, false, len),
                         },
                         location,
                     )));
@@ -783,14 +1269,17 @@ where
                 let mut error = None;
                 let iter = seq.into_iter();
 
+                // The elements are collected in a `Vec` before being converted to an array,
+                // so the ones that were already deserialized are dropped if a later one fails.
                 let mut ret = Vec::with_capacity(N);
 
-                for elem in iter {
-                    let a = T::deserialize_from_value(elem.into_value(), location.push_index(0));
+                for (index, elem) in iter.enumerate() {
+                    let a =
+                        T::deserialize_from_value(elem.into_value(), location.push_index(index));
                     match a {
                         Ok(a) => ret.push(a),
                         Err(e) => {
-                            error = match E::merge(error, e, location.push_index(0)) {
+                            error = match E::merge(error, e, location.push_index(index)) {
                                 ControlFlow::Continue(e) => Some(e),
                                 ControlFlow::Break(e) => return Err(e),
                             };
@@ -806,173 +1295,214 @@ where
                     panic!("Could not convert Vec<T> into [T; N]")
                 }
             }
-            v => Err(take_cf_content(E::error(
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Sequence],
-                },
+                v,
+                &[ValueKind::Sequence],
+                core::any::type_name::<Self>(),
                 location,
             ))),
         }
     }
 }
 
-impl<A, B, E> Deserr<E> for (A, B)
-where
-    A: Deserr<E>,
-    B: Deserr<E>,
-    E: DeserializeError,
-{
-    fn deserialize_from_value<V: IntoValue>(
-        value: Value<V>,
-        location: ValuePointerRef,
-    ) -> Result<Self, E> {
-        match value {
-            Value::Sequence(seq) => {
-                let len = seq.len();
-                if len != 2 {
-                    return Err(take_cf_content(E::error::<V>(
+macro_rules! deserialize_impl_tuple {
+    ($len:literal => $($ty:ident $var:ident $index:tt),+) => {
+        impl<$($ty,)+ E> Deserr<E> for ($($ty,)+)
+        where
+            $($ty: Deserr<E>,)+
+            E: DeserializeError,
+        {
+            fn deserialize_from_value<V: IntoValue>(
+                value: Value<V>,
+                location: ValuePointerRef,
+            ) -> Result<Self, E> {
+                match value {
+                    Value::Sequence(seq) => {
+                        let len = seq.len();
+                        if len != $len {
+                            return Err(take_cf_content(E::error::<V>(
+                                None,
+                                ErrorKind::Unexpected {
+                                    msg: crate::describe_sequence_length($len, false, len),
+                                },
+                                location,
+                            )));
+                        }
+                        let mut error = None;
+                        let mut iter = seq.into_iter();
+
+                        $(
+                            let $var = <$ty>::deserialize_from_value(
+                                iter.next().unwrap().into_value(),
+                                location.push_index($index),
+                            );
+                            let $var = match $var {
+                                Ok($var) => Some($var),
+                                Err(e) => {
+                                    error = match E::merge(error, e, location.push_index($index)) {
+                                        ControlFlow::Continue(e) => Some(e),
+                                        ControlFlow::Break(e) => return Err(e),
+                                    };
+                                    None
+                                }
+                            };
+                        )+
+
+                        if let Some(error) = error {
+                            Err(error)
+                        } else {
+                            Ok(($($var.unwrap(),)+))
+                        }
+                    }
+                    v => Err(take_cf_content(E::incorrect_value_kind_error(
                         None,
-                        ErrorKind::Unexpected {
-                            msg: String::from("the sequence should have exactly 2 elements"),
-                        },
+                        v,
+                        &[ValueKind::Sequence],
+                        core::any::type_name::<Self>(),
                         location,
-                    )));
+                    ))),
                 }
-                let mut error = None;
-                let mut iter = seq.into_iter();
-
-                let a = A::deserialize_from_value(
-                    iter.next().unwrap().into_value(),
-                    location.push_index(0),
-                );
-                let a = match a {
-                    Ok(a) => Some(a),
-                    Err(e) => {
-                        error = match E::merge(error, e, location.push_index(0)) {
-                            ControlFlow::Continue(e) => Some(e),
-                            ControlFlow::Break(e) => return Err(e),
-                        };
-                        None
-                    }
-                };
-                let b = B::deserialize_from_value(
-                    iter.next().unwrap().into_value(),
-                    location.push_index(1),
-                );
-                let b = match b {
-                    Ok(b) => Some(b),
-                    Err(e) => {
-                        error = match E::merge(error, e, location.push_index(1)) {
-                            ControlFlow::Continue(e) => Some(e),
-                            ControlFlow::Break(e) => return Err(e),
-                        };
-                        None
-                    }
-                };
+            }
+        }
+    };
+}
 
-                if let Some(error) = error {
-                    Err(error)
-                } else {
-                    Ok((a.unwrap(), b.unwrap()))
+deserialize_impl_tuple!(2 => A a 0, B b 1);
+deserialize_impl_tuple!(3 => A a 0, B b 1, C c 2);
+deserialize_impl_tuple!(4 => A a 0, B b 1, C c 2, D d 3);
+deserialize_impl_tuple!(5 => A a 0, B b 1, C c 2, D d 3, F f 4);
+deserialize_impl_tuple!(6 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5);
+deserialize_impl_tuple!(7 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5, H h 6);
+deserialize_impl_tuple!(8 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5, H h 6, I i 7);
+deserialize_impl_tuple!(9 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5, H h 6, I i 7, J j 8);
+deserialize_impl_tuple!(10 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5, H h 6, I i 7, J j 8, K k 9);
+deserialize_impl_tuple!(11 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5, H h 6, I i 7, J j 8, K k 9, L l 10);
+deserialize_impl_tuple!(12 => A a 0, B b 1, C c 2, D d 3, F f 4, G g 5, H h 6, I i 7, J j 8, K k 9, L l 10, M m 11);
+
+/// Deserialize the bound `key` of a range from its map, see [`deserialize_range_bounds`].
+fn deserialize_range_bound<T, M, E>(
+    map: &mut M,
+    key: &'static str,
+    error: &mut Option<E>,
+    location: ValuePointerRef,
+) -> Result<Option<T>, E>
+where
+    T: Deserr<E>,
+    M: Map,
+    E: DeserializeError,
+{
+    let result = match map.remove(key) {
+        Some(value) => T::deserialize_from_value(value.into_value(), location.push_key(key)),
+        None => {
+            let flow = E::error::<Infallible>(
+                error.take(),
+                ErrorKind::MissingField { field: key },
+                location,
+            );
+            return match flow {
+                ControlFlow::Continue(e) => {
+                    *error = Some(e);
+                    Ok(None)
                 }
+                ControlFlow::Break(e) => Err(e),
+            };
+        }
+    };
+    match result {
+        Ok(bound) => Ok(Some(bound)),
+        Err(e) => match E::merge(error.take(), e, location.push_key(key)) {
+            ControlFlow::Continue(e) => {
+                *error = Some(e);
+                Ok(None)
             }
-            v => Err(take_cf_content(E::error(
-                None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Sequence],
-                },
-                location,
-            ))),
+            ControlFlow::Break(e) => Err(e),
+        },
+    }
+}
+
+/// Deserialize the start and the end of a range, from a map with the `start` and `end` keys
+/// or from a sequence of two elements. `type_name` is the name of the range type.
+fn deserialize_range_bounds<T, V, E>(
+    value: Value<V>,
+    location: ValuePointerRef,
+    type_name: &str,
+) -> Result<(T, T), E>
+where
+    T: Deserr<E>,
+    V: IntoValue,
+    E: DeserializeError,
+{
+    match value {
+        Value::Sequence(seq) => {
+            <(T, T)>::deserialize_from_value(Value::<V>::Sequence(seq), location)
         }
+        Value::Map(mut map) => {
+            crate::check_map_keys::<_, E>(&map, location)?;
+            let mut error = None;
+            let start = deserialize_range_bound(&mut map, "start", &mut error, location)?;
+            let end = deserialize_range_bound(&mut map, "end", &mut error, location)?;
+            for (key, _) in map.into_iter() {
+                error = match E::error::<Infallible>(
+                    error,
+                    ErrorKind::UnknownKey {
+                        key: &key,
+                        accepted: &["start", "end"],
+                    },
+                    location,
+                ) {
+                    ControlFlow::Continue(e) => Some(e),
+                    ControlFlow::Break(e) => return Err(e),
+                };
+            }
+            match (start, end, error) {
+                (Some(start), Some(end), None) => Ok((start, end)),
+                (_, _, Some(e)) => Err(e),
+                // A missing or invalid bound always sets the error
+                _ => unreachable!(),
+            }
+        }
+        v => Err(take_cf_content(E::incorrect_value_kind_error(
+            None,
+            v,
+            &[ValueKind::Map, ValueKind::Sequence],
+            type_name,
+            location,
+        ))),
     }
 }
 
-impl<A, B, C, E> Deserr<E> for (A, B, C)
+/// A range is deserialized from a map with the `start` and `end` keys, such as
+/// `{ "start": 1, "end": 5 }`, or from a sequence of two elements, such as `[1, 5]`.
+///
+/// The bounds aren't compared: a range whose start is greater than its end is accepted,
+/// and is empty.
+impl<T, E> Deserr<E> for Range<T>
 where
-    A: Deserr<E>,
-    B: Deserr<E>,
-    C: Deserr<E>,
+    T: Deserr<E>,
     E: DeserializeError,
 {
     fn deserialize_from_value<V: IntoValue>(
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E> {
-        match value {
-            Value::Sequence(seq) => {
-                let len = seq.len();
-                if len != 3 {
-                    return Err(take_cf_content(E::error::<V>(
-                        None,
-                        ErrorKind::Unexpected {
-                            msg: String::from("the sequence should have exactly 3 elements"),
-                        },
-                        location,
-                    )));
-                }
-                let mut error = None;
-                let mut iter = seq.into_iter();
-
-                let a = A::deserialize_from_value(
-                    iter.next().unwrap().into_value(),
-                    location.push_index(0),
-                );
-                let a = match a {
-                    Ok(a) => Some(a),
-                    Err(e) => {
-                        error = match E::merge(error, e, location.push_index(0)) {
-                            ControlFlow::Continue(e) => Some(e),
-                            ControlFlow::Break(e) => return Err(e),
-                        };
-                        None
-                    }
-                };
-                let b = B::deserialize_from_value(
-                    iter.next().unwrap().into_value(),
-                    location.push_index(1),
-                );
-                let b = match b {
-                    Ok(b) => Some(b),
-                    Err(e) => {
-                        error = match E::merge(error, e, location.push_index(1)) {
-                            ControlFlow::Continue(e) => Some(e),
-                            ControlFlow::Break(e) => return Err(e),
-                        };
-                        None
-                    }
-                };
-                let c = C::deserialize_from_value(
-                    iter.next().unwrap().into_value(),
-                    location.push_index(2),
-                );
-                let c = match c {
-                    Ok(c) => Some(c),
-                    Err(e) => {
-                        error = match E::merge(error, e, location.push_index(2)) {
-                            ControlFlow::Continue(e) => Some(e),
-                            ControlFlow::Break(e) => return Err(e),
-                        };
-                        None
-                    }
-                };
+        deserialize_range_bounds(value, location, core::any::type_name::<Self>())
+            .map(|(start, end)| start..end)
+    }
+}
 
-                if let Some(error) = error {
-                    Err(error)
-                } else {
-                    Ok((a.unwrap(), b.unwrap(), c.unwrap()))
-                }
-            }
-            v => Err(take_cf_content(E::error(
-                None,
-                ErrorKind::IncorrectValueKind {
-                    actual: v,
-                    accepted: &[ValueKind::Sequence],
-                },
-                location,
-            ))),
-        }
+/// As for the [`Range`], a range is deserialized from a map with the `start` and `end` keys
+/// or from a sequence of two elements, and its bounds aren't compared.
+impl<T, E> Deserr<E> for RangeInclusive<T>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        deserialize_range_bounds(value, location, core::any::type_name::<Self>())
+            .map(|(start, end)| start..=end)
     }
 }
@@ -0,0 +1,125 @@
+//! Implements [`Deserr`] for [`IndexMap`].
+//!
+//! An [`IndexMap`] keeps its entries in the order in which they were inserted, so the keys of
+//! the deserialized map stay in the order of the input. A custom value whose maps are
+//! `IndexMap<String, V>` can keep their order by collecting them into an
+//! [`EntriesMap`](crate::EntriesMap). The feature also enables the
+//! `preserve_order` feature of `serde_json`, so that a `serde_json::Value` passed through deserr
+//! keeps the order of its keys too.
+
+use std::{hash::Hash, ops::ControlFlow, str::FromStr};
+
+use indexmap::IndexMap;
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// The entries are inserted in the order of the deserialized map. A key that can't be parsed
+/// is reported at its own location.
+impl<Key, T, E> Deserr<E> for IndexMap<Key, T>
+where
+    Key: FromStr + Hash + Eq,
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
+                let mut error = None;
+                let mut res = IndexMap::with_capacity(map.len());
+                for (string_key, value) in map.into_iter() {
+                    match Key::from_str(&string_key) {
+                        Ok(key) => {
+                            match T::deserialize_from_value(
+                                value.into_value(),
+                                location.push_key(&string_key),
+                            ) {
+                                Ok(value) => {
+                                    res.insert(key, value);
+                                }
+                                Err(e) => {
+                                    error = match E::merge(error, e, location.push_key(&string_key))
+                                    {
+                                        ControlFlow::Continue(e) => Some(e),
+                                        ControlFlow::Break(e) => return Err(e),
+                                    };
+                                }
+                            }
+                        }
+                        Err(_) => {
+                            error = match E::error::<V>(
+                                error,
+                                ErrorKind::Unexpected {
+                                    msg: format!(
+                                        "the key \"{string_key}\" could not be deserialized into the key type `{}`",
+                                        std::any::type_name::<Key>()
+                                    ),
+                                },
+                                location.push_key(&string_key),
+                            ) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                match error {
+                    Some(e) => Err(e),
+                    None => Ok(res),
+                }
+            }
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
+                None,
+                v,
+                &[ValueKind::Map],
+                std::any::type_name::<Self>(),
+                location,
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod test {
+    use super::*;
+    use crate::errors::JsonError;
+
+    #[allow(dead_code)]
+    #[derive(Debug, crate::Deserr)]
+    struct Config {
+        servers: IndexMap<String, u16>,
+        extra: serde_json::Value,
+    }
+
+    #[test]
+    fn keep_key_order() {
+        let input =
+            r#"{"servers":{"zeta":1,"alpha":2,"mu":3},"extra":{"b":[{"z":1,"a":2}],"a":null}}"#;
+        let config = crate::from_json_str::<Config, JsonError>(input).unwrap();
+        insta::assert_debug_snapshot!(config.servers.keys().collect::<Vec<_>>(), @r###"
+        [
+            "zeta",
+            "alpha",
+            "mu",
+        ]
+        "###);
+        insta::assert_snapshot!(serde_json::to_string(&config.extra).unwrap(), @r###"{"b":[{"z":1,"a":2}],"a":null}"###);
+
+        // A passthrough value is written back with its keys in their original order
+        let value = crate::from_json_str::<serde_json::Value, JsonError>(input).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), input);
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let error = crate::from_json_str::<IndexMap<u8, u8>, JsonError>(r#"{"1":1,"doggo":2}"#)
+            .unwrap_err();
+        insta::assert_snapshot!(error, @r###"Invalid value at `.doggo`: the key "doggo" could not be deserialized into the key type `u8`"###);
+    }
+}
@@ -0,0 +1,123 @@
+//! Implements [`Deserr`] for the [`jiff`] date and time types.
+//!
+//! The timestamps are parsed from strings following [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339),
+//! e.g. `1996-12-19T16:39:57-08:00`, and the dates from strings such as `1996-12-19`.
+
+use std::convert::Infallible;
+
+use jiff::{civil::Date, Timestamp};
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// Deserialize a string with the given parsing function, returning an [`ErrorKind::Unexpected`]
+/// containing the string if it can't be parsed.
+fn deserialize_with<T, V, E, PE>(
+    value: Value<V>,
+    location: ValuePointerRef,
+    parse: impl FnOnce(&str) -> Result<T, PE>,
+) -> Result<T, E>
+where
+    V: IntoValue,
+    E: DeserializeError,
+    PE: std::fmt::Display,
+{
+    match value {
+        Value::String(s) => parse(&s).map_err(|e| {
+            take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!("{e}: `{s}`"),
+                },
+                location,
+            ))
+        }),
+        v => Err(take_cf_content(E::incorrect_value_kind_error(
+            None,
+            v,
+            &[ValueKind::String],
+            std::any::type_name::<T>(),
+            location,
+        ))),
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for Timestamp {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        deserialize_with(value, location, str::parse::<Timestamp>)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for Date {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        deserialize_with(value, location, str::parse::<Date>)
+    }
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod test {
+    use super::*;
+    use crate::errors::JsonError;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Debug, crate::Deserr)]
+    struct Event {
+        #[deserr(default)]
+        at: Option<Timestamp>,
+        day: Date,
+    }
+
+    #[test]
+    fn deserialize_dates() {
+        let event = crate::deserialize::<Event, _, JsonError>(json!({
+            "at": "1996-12-19T16:39:57-08:00",
+            "day": "1996-12-19",
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(event, @r###"
+        Event {
+            at: Some(
+                1996-12-20T00:39:57Z,
+            ),
+            day: 1996-12-19,
+        }
+        "###);
+
+        let event = crate::deserialize::<Event, _, JsonError>(json!({
+            "day": "1996-12-19",
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(event.at, @"None");
+    }
+
+    #[test]
+    fn invalid_dates() {
+        let error = crate::deserialize::<Event, _, JsonError>(json!({
+            "at": "1996-12-19 16:39:57",
+            "day": "1996-12-19",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.at`: failed to find offset component, which is required for parsing a timestamp: `1996-12-19 16:39:57`");
+
+        let error = crate::deserialize::<Event, _, JsonError>(json!({
+            "day": "1996-02-30",
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.day`: parsed date is not valid: parameter 'day' for `1996-02` is invalid, must be in range `1..=29`: `1996-02-30`");
+
+        let error = crate::deserialize::<Event, _, JsonError>(json!({
+            "day": 19961219,
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type at `.day`: expected a string, but found a positive integer: `19961219`");
+    }
+}
@@ -1,21 +1,72 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod errors;
+pub mod query_params;
 
 #[cfg(feature = "actix-web")]
 pub mod actix_web;
+#[cfg(feature = "binary")]
+pub mod binary;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+#[cfg(feature = "cbor")]
+pub mod ciborium;
+#[cfg(feature = "preserve_order")]
+pub mod indexmap;
+#[cfg(feature = "jiff")]
+pub mod jiff;
+#[cfg(feature = "rmpv")]
+pub mod rmpv;
+#[cfg(feature = "ron")]
+pub mod ron;
 #[cfg(feature = "serde-cs")]
 pub mod serde_cs;
 #[cfg(feature = "serde-json")]
 pub mod serde_json;
 #[cfg(feature = "serde-yml")]
 pub mod serde_yml;
+#[cfg(feature = "toml")]
+pub mod toml;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "xml")]
+pub mod xml;
 
+/// Used by the derive proc macro to refer to `String`, `Vec` and `format!` without `std`.
+/// Do not use.
+#[doc(hidden)]
+pub extern crate alloc as alloc_crate;
+/// Used by the derive proc macro for the `try_from_json` attribute. Do not use.
+#[cfg(feature = "serde-json")]
+#[doc(hidden)]
+pub use ::serde_json as serde_json_crate;
+/// Used by the derive proc macro for the `pattern` attribute. Do not use.
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+pub use regex;
+
+mod buffered;
+#[cfg(feature = "std")]
+mod collector;
+mod flatten;
+mod from_str;
 mod impls;
+mod limits;
+mod missing_fields;
 mod value;
+mod warnings;
 
 extern crate self as deserr;
 
+#[cfg(feature = "serde-json")]
+pub use crate::serde_json::{
+    from_json_str, from_json_str_strict, from_json_stream, from_ndjson, JsonStream, NdjsonLines,
+};
+#[cfg(feature = "xml")]
+pub use crate::xml::from_xml_str;
 /**
 It is possible to derive the `Deserr` trait for structs and enums with named fields.
 The derive proc macro accept many arguments, explained below:
@@ -77,10 +128,35 @@ will parse the following:
 }
 ```
 */
+pub use buffered::{BufferedMap, BufferedSequence, BufferedValue, PrettyPrint, SequenceMerge};
 pub use deserr_internal::Deserr;
-pub use value::{IntoValue, Map, Sequence, Value, ValueKind, ValuePointer, ValuePointerRef};
+pub use flatten::{FlattenedMap, FlattenedMapIter, FlattenedSequence, FlattenedValue};
+pub use from_str::FromStrWrapper;
+pub use limits::{deserialize_with_limits, Limits};
+#[cfg(feature = "std")]
+pub use missing_fields::deserialize_with_missing_field_policy;
+pub use missing_fields::MissingFieldPolicy;
+#[doc(hidden)]
+pub use missing_fields::{
+    default_missing_field, missing_fields_checkpoint, rollback_missing_fields,
+};
+pub use value::{
+    EntriesMap, IntoValue, IntoValueRef, Map, MapRef, Sequence, SequenceRef, Value, ValueKind,
+    ValuePointer, ValuePointerComponent, ValuePointerRef, ValueRef,
+};
+#[cfg(feature = "std")]
+pub use warnings::deserialize_with_warnings;
+#[doc(hidden)]
+pub use warnings::{rollback_warnings, warn, warnings_checkpoint};
+pub use warnings::{Warning, WarningKind};
 
-use std::ops::ControlFlow;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::ops::ControlFlow;
 
 /// A trait for types that can be deserialized from a [`Value`]. The generic type
 /// parameter `E` is the custom error that is returned when deserialization fails.
@@ -91,6 +167,40 @@ pub trait Deserr<E: DeserializeError>: Sized {
         value: Value<V>,
         location: ValuePointerRef,
     ) -> Result<Self, E>;
+
+    /// Attempts to deserialize `Self` from the given borrowed value. You probably want to use
+    /// the [`deserialize_ref`] function directly instead.
+    ///
+    /// The default implementation converts the value to its owned version and calls
+    /// [`deserialize_from_value`](Deserr::deserialize_from_value). Types that can be built
+    /// without owning the strings and keys of the value, such as the derived structs,
+    /// override it to avoid these allocations.
+    fn deserialize_from_value_ref<'a, V: IntoValueRef<'a>>(
+        value: V,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        Self::deserialize_from_value(value.into_value_ref().into_owned(), location)
+    }
+
+    /// Return the value of a field of this type when its key is missing, or `None` if the
+    /// field is then required.
+    ///
+    /// The default implementation returns `None`. The derived types return the value of
+    /// their `default` container attribute, e.g. `#[deserr(default = LogLevel::Info)]`.
+    /// The `default` field attribute takes precedence over this value.
+    fn default_when_missing() -> Option<Self> {
+        None
+    }
+}
+
+/// A trait for the types that can list the keys they accept, e.g. to document them.
+///
+/// It is implemented by the structs deriving `Deserr` with the `known_fields` attribute.
+pub trait KnownFields {
+    /// Return the keys accepted by the fields of the type, influenced by the `rename` and
+    /// `rename_all` attributes. The key of each field is followed by its aliases, and the
+    /// keys of a flattened field come last.
+    fn known_fields() -> Vec<&'static str>;
 }
 
 /// Deserialize the given value.
@@ -99,13 +209,163 @@ pub trait Deserr<E: DeserializeError>: Sized {
 /// 1. `Ret` is the type we want to deserialize to. For example: `MyStruct`
 /// 2. `Val` is the type of the value given as argument. For example: `serde_json::Value`
 /// 3. `E` is the error type we want to get when deserialization fails. For example: `MyError`
+///
+/// The value is deserialized with [`Deserr::deserialize_from_value`], starting at the
+/// [`ValuePointerRef::origin()`] location.
 pub fn deserialize<Ret, Val, E>(value: Val) -> Result<Ret, E>
 where
     Ret: Deserr<E>,
     Val: IntoValue,
     E: DeserializeError,
 {
-    Ret::deserialize_from_value(value.into_value(), ValuePointerRef::Origin)
+    Ret::deserialize_from_value(value.into_value(), ValuePointerRef::origin())
+}
+
+/// Deserialize the given borrowed value.
+///
+/// This is the same as [`deserialize`], except that the value is borrowed, e.g. a
+/// `&serde_json::Value`. The map keys are never cloned, and the strings are only
+/// cloned when `Ret` needs to own them. Since the value isn't consumed, it can be
+/// deserialized into several types without being parsed again.
+pub fn deserialize_ref<'a, Ret, Val, E>(value: Val) -> Result<Ret, E>
+where
+    Ret: Deserr<E>,
+    Val: IntoValueRef<'a>,
+    E: DeserializeError,
+{
+    Ret::deserialize_from_value_ref(value, ValuePointerRef::origin())
+}
+
+/// Deserialize the given value, reporting every error instead of only the first one.
+///
+/// This is the same as [`deserialize`], except that `Ret` is deserialized with the
+/// [`AccumulatedErrors<E>`](errors::AccumulatedErrors) error type, which never stops the
+/// deserialization. The returned errors are in document order, i.e. the order in which
+/// a depth-first traversal of the value encountered them.
+///
+/// Note that `Ret` must implement `Deserr` for any error type, which is the case of the derived
+/// types that don't use the `error` attribute.
+pub fn deserialize_accumulate<Ret, Val, E>(value: Val) -> Result<Ret, Vec<E>>
+where
+    Ret: Deserr<errors::AccumulatedErrors<E>>,
+    Val: IntoValue,
+    E: DeserializeError,
+{
+    Ret::deserialize_from_value(value.into_value(), ValuePointerRef::origin())
+        .map_err(errors::AccumulatedErrors::into_errors)
+}
+
+/// Whether the deserialization should stop at the first error or report all of them.
+///
+/// See [`deserialize_with_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMode {
+    /// Stop at the first error, like the [`FailFast`](errors::FailFast) error type.
+    FailFast,
+    /// Report every error, like the [`AccumulatedErrors`](errors::AccumulatedErrors) error type.
+    Accumulate,
+}
+
+/// Deserialize the given value, with the merge strategy chosen by `mode`.
+///
+/// This lets the same error type `E` be used both to fail fast and to report every error,
+/// whatever `E` does by itself when two errors are merged. In [`MergeMode::FailFast`] mode,
+/// the returned `Vec` always contains exactly one error. In [`MergeMode::Accumulate`] mode,
+/// it is the same as [`deserialize_accumulate`].
+///
+/// Note that `Ret` must implement `Deserr` for any error type, which is the case of the derived
+/// types that don't use the `error` attribute.
+pub fn deserialize_with_mode<Ret, Val, E>(value: Val, mode: MergeMode) -> Result<Ret, Vec<E>>
+where
+    Ret: Deserr<errors::FailFast<E>> + Deserr<errors::AccumulatedErrors<E>>,
+    Val: IntoValue,
+    E: DeserializeError,
+{
+    match mode {
+        MergeMode::FailFast => <Ret as Deserr<errors::FailFast<E>>>::deserialize_from_value(
+            value.into_value(),
+            ValuePointerRef::origin(),
+        )
+        .map_err(|e| vec![e.into_inner()]),
+        MergeMode::Accumulate => deserialize_accumulate(value),
+    }
+}
+
+/// Remove the value of `key` from the given map and deserialize it, for the `Deserr`
+/// implementations written by hand.
+///
+/// The value is deserialized at the location `location.push_key(key)`, where `location` is
+/// the location of the map. `Ok(None)` is returned if the key is missing, to let the caller
+/// decide whether it is an error.
+///
+/// ## Example
+/// ```
+/// use deserr::{
+///     deserialize, errors::JsonError, get_field, take_cf_content, DeserializeError, Deserr,
+///     ErrorKind, IntoValue, Value, ValueKind, ValuePointerRef,
+/// };
+/// use serde_json::json;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Dog {
+///     name: String,
+///     age: Option<u8>,
+/// }
+///
+/// impl<E: DeserializeError> Deserr<E> for Dog {
+///     fn deserialize_from_value<V: IntoValue>(
+///         value: Value<V>,
+///         location: ValuePointerRef,
+///     ) -> Result<Self, E> {
+///         let mut map = match value {
+///             Value::Map(map) => map,
+///             value => {
+///                 return Err(take_cf_content(E::error(
+///                     None,
+///                     ErrorKind::IncorrectValueKind {
+///                         actual: value,
+///                         accepted: &[ValueKind::Map],
+///                     },
+///                     location,
+///                 )))
+///             }
+///         };
+///         let Some(name) = get_field(&mut map, "name", location)? else {
+///             return Err(take_cf_content(E::error::<V>(
+///                 None,
+///                 ErrorKind::MissingField { field: "name" },
+///                 location,
+///             )));
+///         };
+///         let age = get_field(&mut map, "age", location)?;
+///         Ok(Dog { name, age })
+///     }
+/// }
+///
+/// let dog = deserialize::<Dog, _, JsonError>(json!({ "name": "Kefir" })).unwrap();
+/// assert_eq!(dog, Dog { name: String::from("Kefir"), age: None });
+///
+/// let error = deserialize::<Dog, _, JsonError>(json!({ "name": "Kefir", "age": -1 })).unwrap_err();
+/// assert_eq!(error.to_string(), "Invalid value type at `.age`: expected a positive integer, but found a negative integer: `-1`");
+///
+/// let error = deserialize::<Dog, _, JsonError>(json!({ "age": 2 })).unwrap_err();
+/// assert_eq!(error.to_string(), "Missing field `name`");
+/// ```
+pub fn get_field<T, E>(
+    map: &mut impl Map,
+    key: &str,
+    location: ValuePointerRef,
+) -> Result<Option<T>, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    match map.remove(key) {
+        Some(value) => {
+            T::deserialize_from_value(value.into_value(), location.push_key(key)).map(Some)
+        }
+        None => Ok(None),
+    }
 }
 
 /// A trait which describes how to combine two errors together.
@@ -181,6 +441,32 @@ pub enum ErrorKind<'a, V: IntoValue> {
     },
 }
 
+impl<V: IntoValue> ErrorKind<'_, V> {
+    /// Return the variant of the error kind, without its content.
+    pub fn tag(&self) -> ErrorKindTag {
+        match self {
+            ErrorKind::IncorrectValueKind { .. } => ErrorKindTag::IncorrectValueKind,
+            ErrorKind::MissingField { .. } => ErrorKindTag::MissingField,
+            ErrorKind::UnknownKey { .. } => ErrorKindTag::UnknownKey,
+            ErrorKind::UnknownValue { .. } => ErrorKindTag::UnknownValue,
+            ErrorKind::Unexpected { .. } => ErrorKindTag::Unexpected,
+        }
+    }
+}
+
+/// The variants of [`ErrorKind`], without their content.
+///
+/// It is kept by the built-in error types, such as [`JsonError`](errors::JsonError), so that
+/// the application can tell why the deserialization failed, e.g. to pick an HTTP status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKindTag {
+    IncorrectValueKind,
+    MissingField,
+    UnknownKey,
+    UnknownValue,
+    Unexpected,
+}
+
 /// A trait for errors returned by [`deserialize_from_value`](Deserr::deserialize_from_value).
 pub trait DeserializeError: Sized + MergeWithError<Self> {
     fn error<V: IntoValue>(
@@ -188,6 +474,29 @@ pub trait DeserializeError: Sized + MergeWithError<Self> {
         error: ErrorKind<V>,
         location: ValuePointerRef,
     ) -> ControlFlow<Self, Self>;
+
+    /// Return the error of a value whose kind isn't accepted by the Rust type being
+    /// deserialized. `type_name` is the name of this type, as returned by
+    /// [`core::any::type_name`], e.g. `u32` or `alloc::vec::Vec<u32>`.
+    ///
+    /// It is called by the built-in and derived implementations instead of
+    /// [`error`](DeserializeError::error). The default implementation ignores the type name
+    /// and returns an [`ErrorKind::IncorrectValueKind`]. The type name isn't stable across
+    /// compiler versions, so it is meant for the logs rather than for the clients.
+    fn incorrect_value_kind_error<V: IntoValue>(
+        self_: Option<Self>,
+        actual: Value<V>,
+        accepted: &[ValueKind],
+        type_name: &str,
+        location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let _ = type_name;
+        Self::error(
+            self_,
+            ErrorKind::IncorrectValueKind { actual, accepted },
+            location,
+        )
+    }
 }
 
 /// Used by the derive proc macro. Do not use.
@@ -245,3 +554,110 @@ pub fn take_cf_content<T>(r: ControlFlow<T, T>) -> T {
         ControlFlow::Break(x) => x,
     }
 }
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Describe the errors returned by each variant of an untagged enum, e.g.
+/// ``expected one of `Circle` (Missing field `radius`) or `Square` (Missing field `side`)``.
+#[doc(hidden)]
+pub fn describe_untagged_errors<E: core::fmt::Display>(errors: &[(&str, E)]) -> String {
+    let variants = errors
+        .iter()
+        .map(|(variant, error)| format!("`{variant}` ({error})"))
+        .collect::<Vec<_>>();
+    let variants = match variants.as_slice() {
+        [] => String::new(),
+        [variant] => variant.clone(),
+        [rest @ .., last] => format!("{} or {last}", rest.join(", ")),
+    };
+    format!("expected one of {variants}")
+}
+
+/// Describe a sequence that doesn't have the expected number of elements, e.g.
+/// `expected a sequence of 2 elements but instead found a sequence of 1 element`. With
+/// `at_most`, the sequence was only expected to have `expected` elements or less.
+#[doc(hidden)]
+pub fn describe_sequence_length(expected: usize, at_most: bool, found: usize) -> String {
+    let elements = |len: usize| if len == 1 { "element" } else { "elements" };
+    let at_most = if at_most { "at most " } else { "" };
+    format!(
+        "expected a sequence of {at_most}{expected} {} but instead found a sequence of {found} {}",
+        elements(expected),
+        elements(found)
+    )
+}
+
+/// Used by the derive proc macro for the `deny_null` attribute. Do not use.
+///
+/// Deserialize the value of an `Option<T>` field as a `T`, so that an explicit `null` is
+/// refused by `T` like any other value of the wrong kind.
+#[doc(hidden)]
+pub fn deserialize_non_null<T, V, E>(
+    value: Value<V>,
+    location: ValuePointerRef,
+) -> Result<Option<T>, E>
+where
+    T: Deserr<E>,
+    V: IntoValue,
+    E: DeserializeError,
+{
+    T::deserialize_from_value(value, location).map(Some)
+}
+
+/// Used by the derive proc macro for the `deny_null` attribute. Do not use.
+///
+/// Like [`deserialize_non_null`], from a borrowed value.
+#[doc(hidden)]
+pub fn deserialize_non_null_ref<'a, T, V, E>(
+    value: V,
+    location: ValuePointerRef,
+) -> Result<Option<T>, E>
+where
+    T: Deserr<E>,
+    V: IntoValueRef<'a>,
+    E: DeserializeError,
+{
+    T::deserialize_from_value_ref(value, location).map(Some)
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Return an error if one of the keys of the map is a sequence or a map, which can't be
+/// converted to a string, see [`Map`].
+#[doc(hidden)]
+pub fn check_map_keys<M: Map, E: DeserializeError>(
+    map: &M,
+    location: ValuePointerRef,
+) -> Result<(), E> {
+    match map.unsupported_key() {
+        Some(kind) => Err(take_cf_content(E::error::<M::Value>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "the map has a key that is a {}, but a key must be a string, a number, a boolean or null",
+                    kind.to_string().to_lowercase()
+                ),
+            },
+            location,
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Used by the derive proc macro for the `dedup` attribute. Do not use.
+///
+/// Remove the elements that are equal to a previous element, keeping the first occurrence
+/// of each element in its original position.
+///
+/// The elements only implement `PartialEq`, so each of them is compared with all the elements
+/// kept before it, which takes a quadratic time in the number of elements.
+#[doc(hidden)]
+pub fn dedup<T: PartialEq>(elements: Vec<T>) -> Vec<T> {
+    let mut deduped: Vec<T> = Vec::with_capacity(elements.len());
+    for element in elements {
+        if !deduped.contains(&element) {
+            deduped.push(element);
+        }
+    }
+    deduped
+}
@@ -0,0 +1,237 @@
+use alloc::{format, string::String};
+use core::{cell::Cell, convert::Infallible};
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
+    ValueKind, ValuePointerRef,
+};
+
+/// The limits enforced by [`deserialize_with_limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of nested sequences and maps, e.g. `[[1]]` has a depth of 2.
+    ///
+    /// The default is 128, like the recursion limit of `serde_json`.
+    pub max_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
+
+/// Deserialize the given value like [`deserialize`](crate::deserialize), while enforcing
+/// the given limits.
+///
+/// The values nested deeper than `limits.max_depth` are never given to the `Deserr`
+/// implementations, which protects the stack from the maliciously nested payloads.
+/// Instead, an [`ErrorKind::Unexpected`] error is returned at the origin of the value.
+///
+/// ## Example
+/// ```
+/// use deserr::{deserialize_with_limits, errors::JsonError, Limits};
+/// use serde_json::json;
+///
+/// let limits = Limits { max_depth: 2 };
+/// let value = deserialize_with_limits::<Vec<Vec<u8>>, _, JsonError>(json!([[1]]), limits);
+/// assert_eq!(value.unwrap(), [[1]]);
+///
+/// let error = deserialize_with_limits::<serde_json::Value, _, JsonError>(json!([[[1]]]), limits)
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "Invalid value: the maximum nesting depth of 2 was exceeded");
+/// ```
+pub fn deserialize_with_limits<Ret, Val, E>(value: Val, limits: Limits) -> Result<Ret, E>
+where
+    Ret: Deserr<E>,
+    Val: IntoValue,
+    E: DeserializeError,
+{
+    let guard = DepthGuard {
+        max_depth: limits.max_depth,
+        exceeded: Cell::new(false),
+    };
+    let value = DepthLimited {
+        value,
+        depth: Depth {
+            depth: 0,
+            guard: &guard,
+        },
+    };
+    let result = Ret::deserialize_from_value(value.into_value(), ValuePointerRef::origin());
+    // The result was built from truncated values, so it can't be trusted
+    if guard.exceeded.get() {
+        return Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "the maximum nesting depth of {} was exceeded",
+                    limits.max_depth
+                ),
+            },
+            ValuePointerRef::origin(),
+        )));
+    }
+    result
+}
+
+/// Shared by all the values of a [`deserialize_with_limits`] call.
+struct DepthGuard {
+    max_depth: usize,
+    /// Set when a value nested too deep was replaced by `null`.
+    exceeded: Cell<bool>,
+}
+
+/// The nesting depth of a value, i.e. the number of sequences and maps containing it.
+#[derive(Clone, Copy)]
+struct Depth<'a> {
+    depth: usize,
+    guard: &'a DepthGuard,
+}
+
+impl<'a> Depth<'a> {
+    fn nested(self) -> Self {
+        Depth {
+            depth: self.depth + 1,
+            guard: self.guard,
+        }
+    }
+}
+
+/// A value that turns into `null` instead of a sequence or a map when it is nested too deep.
+struct DepthLimited<'a, V> {
+    value: V,
+    depth: Depth<'a>,
+}
+
+impl<'a, V: IntoValue> IntoValue for DepthLimited<'a, V> {
+    type Sequence = LimitedSequence<'a, V::Sequence>;
+    type Map = LimitedMap<'a, V::Map>;
+
+    fn kind(&self) -> ValueKind {
+        self.value.kind()
+    }
+
+    fn into_value(self) -> Value<Self> {
+        let Depth { depth, guard } = self.depth;
+        match self.value.into_value() {
+            Value::Null => Value::Null,
+            Value::Boolean(b) => Value::Boolean(b),
+            Value::Integer(n) => Value::Integer(n),
+            Value::NegativeInteger(n) => Value::NegativeInteger(n),
+            Value::Float(n) => Value::Float(n),
+            Value::String(s) => Value::String(s),
+            Value::Sequence(_) | Value::Map(_) if depth >= guard.max_depth => {
+                guard.exceeded.set(true);
+                Value::Null
+            }
+            Value::Sequence(seq) => Value::Sequence(LimitedSequence {
+                seq,
+                depth: self.depth.nested(),
+            }),
+            Value::Map(map) => Value::Map(LimitedMap {
+                map,
+                depth: self.depth.nested(),
+            }),
+        }
+    }
+}
+
+/// A sequence whose elements are [`DepthLimited`].
+struct LimitedSequence<'a, S> {
+    seq: S,
+    /// The depth of the elements.
+    depth: Depth<'a>,
+}
+
+impl<'a, S: Sequence> Sequence for LimitedSequence<'a, S> {
+    type Value = DepthLimited<'a, S::Value>;
+    type Iter = LimitedSequenceIter<'a, S::Iter>;
+
+    fn len(&self) -> usize {
+        self.seq.len()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.seq.size_hint()
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        LimitedSequenceIter {
+            iter: self.seq.into_iter(),
+            depth: self.depth,
+        }
+    }
+}
+
+struct LimitedSequenceIter<'a, I> {
+    iter: I,
+    depth: Depth<'a>,
+}
+
+impl<'a, V, I: Iterator<Item = V>> Iterator for LimitedSequenceIter<'a, I> {
+    type Item = DepthLimited<'a, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        Some(DepthLimited {
+            value,
+            depth: self.depth,
+        })
+    }
+}
+
+/// A map whose values are [`DepthLimited`].
+struct LimitedMap<'a, M> {
+    map: M,
+    /// The depth of the values.
+    depth: Depth<'a>,
+}
+
+impl<'a, M: Map> Map for LimitedMap<'a, M> {
+    type Value = DepthLimited<'a, M::Value>;
+    type Iter = LimitedMapIter<'a, M::Iter>;
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let value = self.map.remove(key)?;
+        Some(DepthLimited {
+            value,
+            depth: self.depth,
+        })
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        LimitedMapIter {
+            iter: self.map.into_iter(),
+            depth: self.depth,
+        }
+    }
+
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        self.map.unsupported_key()
+    }
+}
+
+struct LimitedMapIter<'a, I> {
+    iter: I,
+    depth: Depth<'a>,
+}
+
+impl<'a, V, I: Iterator<Item = (String, V)>> Iterator for LimitedMapIter<'a, I> {
+    type Item = (String, DepthLimited<'a, V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+        Some((
+            key,
+            DepthLimited {
+                value,
+                depth: self.depth,
+            },
+        ))
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+
+use crate::ValuePointerRef;
+#[cfg(feature = "std")]
+use crate::{collector, DeserializeError, Deserr, IntoValue, ValuePointer};
+
+/// What happens when a required field is missing, see
+/// [`deserialize_with_missing_field_policy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingFieldPolicy {
+    /// Return an [`ErrorKind::MissingField`](crate::ErrorKind::MissingField) error, like
+    /// [`deserialize`](crate::deserialize) does.
+    #[default]
+    Error,
+    /// Give its `Default` value to the missing field, and report its location. Only the
+    /// structs and enums with the `missing_field_policy` attribute follow this policy.
+    Default,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// The locations of the fields defaulted by the [`deserialize_with_missing_field_policy`]
+    /// call in progress on this thread, if its policy is [`MissingFieldPolicy::Default`].
+    static DEFAULTED_FIELDS: RefCell<Option<Vec<ValuePointer>>> = const { RefCell::new(None) };
+}
+
+/// Deserialize the given value like [`deserialize`](crate::deserialize), while handling the
+/// missing required fields with the given policy.
+///
+/// With [`MissingFieldPolicy::Default`], the required fields of the types deriving `Deserr`
+/// with the `missing_field_policy` attribute are given their `Default` value when they are
+/// missing, instead of being an error. The locations of these fields are returned alongside the
+/// value, in the order in which they were defaulted, e.g. to save an incomplete draft and
+/// list what is left to fill before publishing it.
+///
+/// The fields with the `default` attribute, and the ones whose type provides a default value,
+/// keep taking it and are never reported.
+///
+/// ## Example
+/// ```
+/// use deserr::{
+///     deserialize_with_missing_field_policy, errors::JsonError, Deserr, MissingFieldPolicy,
+/// };
+/// use serde_json::json;
+///
+/// #[derive(Deserr, Debug, PartialEq, Eq)]
+/// #[deserr(missing_field_policy)]
+/// struct Article {
+///     title: String,
+///     body: String,
+///     #[deserr(default)]
+///     tags: Vec<String>,
+/// }
+///
+/// let (article, missing) = deserialize_with_missing_field_policy::<Article, _, JsonError>(
+///     json!({ "title": "Doggos" }),
+///     MissingFieldPolicy::Default,
+/// )
+/// .unwrap();
+/// assert_eq!(article.body, "");
+/// assert_eq!(missing[0].to_string(), ".body");
+///
+/// let error = deserialize_with_missing_field_policy::<Article, _, JsonError>(
+///     json!({ "title": "Doggos" }),
+///     MissingFieldPolicy::Error,
+/// )
+/// .unwrap_err();
+/// assert_eq!(error.to_string(), "Missing field `body`");
+/// ```
+#[cfg(feature = "std")]
+pub fn deserialize_with_missing_field_policy<Ret, Val, E>(
+    value: Val,
+    policy: MissingFieldPolicy,
+) -> Result<(Ret, Vec<ValuePointer>), E>
+where
+    Ret: Deserr<E>,
+    Val: IntoValue,
+    E: DeserializeError,
+{
+    let collect = policy == MissingFieldPolicy::Default;
+    let (result, defaulted) =
+        collector::scope(&DEFAULTED_FIELDS, collect, || crate::deserialize(value));
+    result.map(|value| (value, defaulted))
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Return `true` if the missing field at the given location must take its default value
+/// instead of being an error, and report it to the [`deserialize_with_missing_field_policy`]
+/// call in progress.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn default_missing_field(location: ValuePointerRef) -> bool {
+    collector::push(&DEFAULTED_FIELDS, location.to_owned())
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Return the number of fields defaulted so far, to forget the ones defaulted by an untagged
+/// enum variant that couldn't be deserialized with [`rollback_missing_fields`].
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn missing_fields_checkpoint() -> usize {
+    collector::checkpoint(&DEFAULTED_FIELDS)
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Forget the fields defaulted since the given [`missing_fields_checkpoint`].
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn rollback_missing_fields(checkpoint: usize) {
+    collector::rollback(&DEFAULTED_FIELDS, checkpoint)
+}
+
+// Without the `std` feature, there is no `deserialize_with_missing_field_policy` call, so the
+// missing fields are always an error.
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn default_missing_field(_location: ValuePointerRef) -> bool {
+    false
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn missing_fields_checkpoint() -> usize {
+    0
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn rollback_missing_fields(_checkpoint: usize) {}
@@ -0,0 +1,438 @@
+//! Wrapper types to deserialize the values of query parameters.
+//!
+//! The values of query parameters are always strings, so the types expecting another
+//! kind of value, such as `bool`, can't be deserialized from them directly. The types of
+//! this module parse these strings instead, and accept the values of the right kind as well,
+//! so they can also be used with a JSON payload.
+
+use alloc::{format, string::ToString, vec::Vec};
+use core::{
+    fmt::Display,
+    ops::{ControlFlow, Deref, DerefMut},
+    str::FromStr,
+};
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Value, ValueKind,
+    ValuePointerRef,
+};
+
+/// A `bool` deserialized from a boolean or from a string.
+///
+/// The accepted strings are `true`, `false`, `1` and `0`. Any other string, such as
+/// `yes` or `True`, is an error.
+///
+/// ```
+/// use deserr::{deserialize, errors::QueryParamError, query_params::BoolFromStr, Deserr};
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserr)]
+/// struct Search {
+///     exhaustive: BoolFromStr,
+/// }
+///
+/// let search = deserialize::<Search, _, QueryParamError>(json!({ "exhaustive": "1" })).unwrap();
+/// assert!(*search.exhaustive);
+///
+/// let error = deserialize::<Search, _, QueryParamError>(json!({ "exhaustive": "yes" }))
+///     .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid value in parameter `exhaustive`: expected one of `true`, `false`, `1` or `0`, but found `yes`",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoolFromStr(pub bool);
+
+impl BoolFromStr {
+    /// Return the wrapped `bool`.
+    pub fn into_inner(self) -> bool {
+        self.0
+    }
+}
+
+impl Deref for BoolFromStr {
+    type Target = bool;
+
+    fn deref(&self) -> &bool {
+        &self.0
+    }
+}
+
+impl DerefMut for BoolFromStr {
+    fn deref_mut(&mut self) -> &mut bool {
+        &mut self.0
+    }
+}
+
+impl From<BoolFromStr> for bool {
+    fn from(value: BoolFromStr) -> Self {
+        value.0
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for BoolFromStr {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::Boolean(b) => Ok(BoolFromStr(b)),
+            Value::String(s) => match s.as_str() {
+                "true" | "1" => Ok(BoolFromStr(true)),
+                "false" | "0" => Ok(BoolFromStr(false)),
+                _ => Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::Unexpected {
+                        msg: format!(
+                            "expected one of `true`, `false`, `1` or `0`, but found `{s}`"
+                        ),
+                    },
+                    location,
+                ))),
+            },
+            value => Err(take_cf_content(E::incorrect_value_kind_error(
+                None,
+                value,
+                &[ValueKind::Boolean, ValueKind::String],
+                core::any::type_name::<Self>(),
+                location,
+            ))),
+        }
+    }
+}
+
+/// A number deserialized from a number or from a string, such as a `usize` or an `f64`.
+///
+/// The strings are parsed with the [`FromStr`] implementation of `T`, and the other values
+/// are deserialized as a `T`.
+///
+/// ```
+/// use deserr::{deserialize, errors::QueryParamError, query_params::NumFromStr, Deserr};
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserr)]
+/// struct Search {
+///     limit: NumFromStr<usize>,
+/// }
+///
+/// let search = deserialize::<Search, _, QueryParamError>(json!({ "limit": "42" })).unwrap();
+/// assert_eq!(*search.limit, 42);
+///
+/// let error = deserialize::<Search, _, QueryParamError>(json!({ "limit": "many" }))
+///     .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid value in parameter `limit`: could not parse `many` as a `usize`: invalid digit found in string",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumFromStr<T>(pub T);
+
+impl<T> NumFromStr<T> {
+    /// Return the wrapped number.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for NumFromStr<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NumFromStr<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T, E> Deserr<E> for NumFromStr<T>
+where
+    T: FromStr + Deserr<E>,
+    T::Err: Display,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::String(s) => match T::from_str(&s) {
+                Ok(n) => Ok(NumFromStr(n)),
+                Err(e) => Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::Unexpected {
+                        msg: format!(
+                            "could not parse `{s}` as a `{}`: {e}",
+                            core::any::type_name::<T>()
+                        ),
+                    },
+                    location,
+                ))),
+            },
+            value => T::deserialize_from_value(value, location).map(NumFromStr),
+        }
+    }
+}
+
+/// A list deserialized from a sequence or from a comma-separated string, such as `a,b,c`.
+///
+/// Each piece of the string is deserialized as a `T` from a [`Value::String`], so the
+/// other wrappers of this module can be used to parse them, e.g. `CommaSeparated<NumFromStr<u32>>`.
+/// The errors are located at the index of the piece, as they would be for a sequence.
+///
+/// The trailing empty pieces are ignored, so `a,b,` is `["a", "b"]` and an empty string is an
+/// empty list. The other empty pieces are kept, so `a,,b` is `["a", "", "b"]`.
+///
+/// ```
+/// use deserr::{deserialize, errors::QueryParamError, query_params::{CommaSeparated, NumFromStr}, Deserr};
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserr)]
+/// struct Search {
+///     tags: CommaSeparated<String>,
+///     ids: CommaSeparated<NumFromStr<u32>>,
+/// }
+///
+/// let search = deserialize::<Search, _, QueryParamError>(json!({ "tags": "a,b,", "ids": "1,2" }))
+///     .unwrap();
+/// assert_eq!(*search.tags, ["a", "b"]);
+/// assert_eq!(search.ids.len(), 2);
+///
+/// let error = deserialize::<Search, _, QueryParamError>(json!({ "tags": "", "ids": "1,two" }))
+///     .unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid value in parameter `ids[1]`: could not parse `two` as a `u32`: invalid digit found in string",
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommaSeparated<T>(pub Vec<T>);
+
+impl<T> CommaSeparated<T> {
+    /// Return the wrapped list.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for CommaSeparated<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CommaSeparated<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T> From<CommaSeparated<T>> for Vec<T> {
+    fn from(value: CommaSeparated<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T, E> Deserr<E> for CommaSeparated<T>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::String(s) => {
+                let pieces = s.trim_end_matches(',');
+                let mut res = Vec::new();
+                let mut error: Option<E> = None;
+                if pieces.is_empty() {
+                    return Ok(CommaSeparated(res));
+                }
+                for (index, piece) in pieces.split(',').enumerate() {
+                    let result = T::deserialize_from_value(
+                        Value::<V>::String(piece.to_string()),
+                        location.push_index(index),
+                    );
+                    match result {
+                        Ok(value) => res.push(value),
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_index(index)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    Err(e)
+                } else {
+                    Ok(CommaSeparated(res))
+                }
+            }
+            value @ Value::Sequence(_) => {
+                Vec::<T>::deserialize_from_value(value, location).map(CommaSeparated)
+            }
+            value => Err(take_cf_content(E::incorrect_value_kind_error(
+                None,
+                value,
+                &[ValueKind::String, ValueKind::Sequence],
+                core::any::type_name::<Self>(),
+                location,
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::errors::QueryParamError;
+
+    #[test]
+    fn bool_from_str() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Flags {
+            a: BoolFromStr,
+            b: BoolFromStr,
+            c: BoolFromStr,
+            d: BoolFromStr,
+            e: BoolFromStr,
+        }
+
+        let flags = crate::deserialize::<Flags, _, QueryParamError>(
+            json!({ "a": "true", "b": "false", "c": "1", "d": "0", "e": true }),
+        )
+        .unwrap();
+        insta::assert_debug_snapshot!(flags, @r###"
+        Flags {
+            a: BoolFromStr(
+                true,
+            ),
+            b: BoolFromStr(
+                false,
+            ),
+            c: BoolFromStr(
+                true,
+            ),
+            d: BoolFromStr(
+                false,
+            ),
+            e: BoolFromStr(
+                true,
+            ),
+        }
+        "###);
+
+        let error =
+            crate::deserialize::<BoolFromStr, _, QueryParamError>(json!("True")).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: expected one of `true`, `false`, `1` or `0`, but found `True`");
+
+        let error = crate::deserialize::<BoolFromStr, _, QueryParamError>(json!(1)).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type: expected a string, but found an integer: `1`");
+    }
+
+    #[test]
+    fn num_from_str() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Page {
+            offset: NumFromStr<usize>,
+            limit: NumFromStr<u8>,
+            ratio: NumFromStr<f64>,
+        }
+
+        let page = crate::deserialize::<Page, _, QueryParamError>(
+            json!({ "offset": "12", "limit": 20, "ratio": "0.5" }),
+        )
+        .unwrap();
+        insta::assert_debug_snapshot!(page, @r###"
+        Page {
+            offset: NumFromStr(
+                12,
+            ),
+            limit: NumFromStr(
+                20,
+            ),
+            ratio: NumFromStr(
+                0.5,
+            ),
+        }
+        "###);
+
+        let error = crate::deserialize::<Page, _, QueryParamError>(
+            json!({ "offset": "-12", "limit": "20", "ratio": "0.5" }),
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value in parameter `offset`: could not parse `-12` as a `usize`: invalid digit found in string");
+
+        let error = crate::deserialize::<Page, _, QueryParamError>(
+            json!({ "offset": "12", "limit": "300", "ratio": "0.5" }),
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value in parameter `limit`: could not parse `300` as a `u8`: number too large to fit in target type");
+    }
+
+    #[test]
+    fn comma_separated() {
+        let list =
+            crate::deserialize::<CommaSeparated<String>, _, QueryParamError>(json!("a,,b,,"))
+                .unwrap();
+        insta::assert_debug_snapshot!(list, @r###"
+        CommaSeparated(
+            [
+                "a",
+                "",
+                "b",
+            ],
+        )
+        "###);
+
+        let list =
+            crate::deserialize::<CommaSeparated<String>, _, QueryParamError>(json!(",")).unwrap();
+        insta::assert_debug_snapshot!(list, @r###"
+        CommaSeparated(
+            [],
+        )
+        "###);
+
+        let list =
+            crate::deserialize::<CommaSeparated<NumFromStr<u8>>, _, QueryParamError>(json!([
+                "1", 2
+            ]))
+            .unwrap();
+        insta::assert_debug_snapshot!(list, @r###"
+        CommaSeparated(
+            [
+                NumFromStr(
+                    1,
+                ),
+                NumFromStr(
+                    2,
+                ),
+            ],
+        )
+        "###);
+
+        let error = crate::deserialize::<CommaSeparated<BoolFromStr>, _, QueryParamError>(json!(
+            "true,nope"
+        ))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value in parameter `[1]`: expected one of `true`, `false`, `1` or `0`, but found `nope`");
+
+        let error =
+            crate::deserialize::<CommaSeparated<String>, _, QueryParamError>(json!(1)).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type: expected a string, but found an integer: `1`");
+    }
+}
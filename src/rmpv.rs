@@ -0,0 +1,278 @@
+//! Implements the value traits for [`rmpv::Value`].
+//!
+//! MessagePack has a few types that don't exist in the deserr data model:
+//! - binary data and the data of the extension types are surfaced as a [`Value::Sequence`]
+//!   of integers, one per byte. The type of an extension is ignored.
+//! - strings that are not valid UTF-8 are converted lossily, replacing the invalid sequences with `�`
+//! - 32-bit floats are surfaced as a [`Value::Float`]
+//!
+//! Deserializing into a [`rmpv::Value`] always produces string map keys, 64-bit floats
+//! and no binary or extension values.
+
+use std::ops::ControlFlow;
+
+use crate::value::{key_matches, stringify_key, unsupported_key};
+use crate::{
+    DeserializeError, Deserr, IntoValue, Map, Sequence, Value, ValueKind, ValuePointerRef,
+};
+use rmpv::{Integer, Utf8String, Value as RValue};
+
+/// The map of a [`rmpv::Value`], which is a list of entries with arbitrary keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RMap(Vec<(RValue, RValue)>);
+
+/// Convert a MessagePack string to a Rust string, replacing the invalid UTF-8 sequences with `�`.
+fn lossy_string(s: Utf8String) -> String {
+    String::from_utf8(s.into_bytes())
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// Iterator over the entries of a MessagePack map, whose keys are converted to strings as described
+/// by [`Map`].
+pub struct RMapIter {
+    iter: std::vec::IntoIter<(RValue, RValue)>,
+}
+
+impl Iterator for RMapIter {
+    type Item = (String, RValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the keys that can't be converted are refused beforehand, see `Map::unsupported_key`
+        self.iter
+            .by_ref()
+            .find_map(|(k, v)| Some((stringify_key(k).ok()?, v)))
+    }
+}
+
+impl Map for RMap {
+    type Value = RValue;
+    type Iter = RMapIter;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self.0.iter().position(|(k, _)| key_matches(k, key))?;
+        Some(self.0.remove(index).1)
+    }
+    fn into_iter(self) -> Self::Iter {
+        RMapIter {
+            iter: self.0.into_iter(),
+        }
+    }
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        unsupported_key(self.0.iter().map(|(k, _)| k))
+    }
+}
+
+/// Surface bytes as a sequence of integers, see the [module documentation](self).
+fn bytes_to_sequence(bytes: Vec<u8>) -> Vec<RValue> {
+    bytes
+        .into_iter()
+        .map(|byte| RValue::Integer(byte.into()))
+        .collect()
+}
+
+impl IntoValue for RValue {
+    type Sequence = Vec<RValue>;
+    type Map = RMap;
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            RValue::Nil => Value::Null,
+            RValue::Boolean(b) => Value::Boolean(b),
+            RValue::Integer(n) => {
+                if let Some(n) = n.as_u64() {
+                    Value::Integer(n)
+                } else if let Some(n) = n.as_i64() {
+                    Value::NegativeInteger(n)
+                } else {
+                    // a MessagePack integer is always either a `u64` or an `i64`
+                    unreachable!()
+                }
+            }
+            RValue::F32(n) => Value::Float(n.into()),
+            RValue::F64(n) => Value::Float(n),
+            RValue::String(s) => Value::String(lossy_string(s)),
+            RValue::Binary(x) => Value::Sequence(bytes_to_sequence(x)),
+            RValue::Array(x) => Value::Sequence(x),
+            RValue::Map(x) => Value::Map(RMap(x)),
+            RValue::Ext(_, x) => Value::Sequence(bytes_to_sequence(x)),
+        }
+    }
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            RValue::Nil => ValueKind::Null,
+            RValue::Boolean(_) => ValueKind::Boolean,
+            RValue::Integer(n) => {
+                if n.is_u64() {
+                    ValueKind::Integer
+                } else {
+                    ValueKind::NegativeInteger
+                }
+            }
+            RValue::F32(_) | RValue::F64(_) => ValueKind::Float,
+            RValue::String(_) => ValueKind::String,
+            RValue::Binary(_) | RValue::Array(_) | RValue::Ext(_, _) => ValueKind::Sequence,
+            RValue::Map(_) => ValueKind::Map,
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for RValue {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let mut error: Option<E> = None;
+        Ok(match value {
+            Value::Null => RValue::Nil,
+            Value::Boolean(b) => RValue::Boolean(b),
+            Value::Integer(x) => RValue::Integer(Integer::from(x)),
+            Value::NegativeInteger(x) => RValue::Integer(Integer::from(x)),
+            Value::Float(f) => RValue::F64(f),
+            Value::String(s) => RValue::String(s.into()),
+            Value::Sequence(seq) => {
+                let mut rseq = Vec::with_capacity(seq.len());
+                for (index, value) in seq.into_iter().enumerate() {
+                    let result = Self::deserialize_from_value(
+                        value.into_value(),
+                        location.push_index(index),
+                    );
+                    match result {
+                        Ok(value) => {
+                            rseq.push(value);
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_index(index)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    RValue::Array(rseq)
+                }
+            }
+            Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
+                let mut rmap = Vec::with_capacity(map.len());
+                for (key, value) in map.into_iter() {
+                    let result =
+                        Self::deserialize_from_value(value.into_value(), location.push_key(&key));
+                    match result {
+                        Ok(value) => {
+                            rmap.push((RValue::String(key.into()), value));
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_key(&key)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    RValue::Map(rmap)
+                }
+            }
+        })
+    }
+}
+
+impl<V: IntoValue> From<Value<V>> for RValue {
+    fn from(value: Value<V>) -> Self {
+        match value {
+            Value::Null => RValue::Nil,
+            Value::Boolean(b) => RValue::Boolean(b),
+            Value::Integer(n) => RValue::Integer(Integer::from(n)),
+            Value::NegativeInteger(i) => RValue::Integer(Integer::from(i)),
+            Value::Float(f) => RValue::F64(f),
+            Value::String(s) => RValue::String(s.into()),
+            Value::Sequence(s) => RValue::Array(
+                s.into_iter()
+                    .map(IntoValue::into_value)
+                    .map(RValue::from)
+                    .collect(),
+            ),
+            Value::Map(m) => RValue::Map(
+                m.into_iter()
+                    .map(|(k, v)| (RValue::String(k.into()), RValue::from(v.into_value())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_value_to_deserr_and_back() {
+        let value = RValue::Map(vec![
+            (RValue::from("The"), RValue::from("best")),
+            (
+                RValue::from("doggos"),
+                RValue::Array(vec![RValue::from("are")]),
+            ),
+            (
+                RValue::from("the"),
+                RValue::Map(vec![
+                    (RValue::from("bernese"), RValue::from("mountain")),
+                    (RValue::from("age"), RValue::from(-3)),
+                    (RValue::from("weight"), RValue::from(u64::MAX)),
+                ]),
+            ),
+        ]);
+        let deserr = value.clone().into_value();
+        let deserr: RValue = deserr.into();
+        insta::assert_snapshot!(deserr, @r#"{"The": "best", "doggos": ["are"], "the": {"bernese": "mountain", "age": -3, "weight": 18446744073709551615}}"#);
+
+        assert_eq!(value, deserr);
+    }
+
+    #[test]
+    fn binary_ext_and_non_string_keys() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            #[deserr(rename = "1")]
+            one: Vec<u8>,
+            #[deserr(rename = "true")]
+            truth: Vec<u8>,
+            #[deserr(rename = "null")]
+            name: String,
+        }
+
+        // a MessagePack fixstr of 5 bytes that aren't valid UTF-8
+        let invalid_utf8 = rmpv::decode::read_value(&mut &b"\xa5kef\xffr"[..]).unwrap();
+        let value = RValue::Map(vec![
+            (RValue::from(1), RValue::Binary(vec![0xde, 0xad])),
+            (RValue::from(true), RValue::Ext(2, vec![0xbe, 0xef])),
+            (RValue::Nil, invalid_utf8),
+        ]);
+
+        let doggo: Doggo =
+            crate::deserialize::<_, _, crate::errors::QueryParamError>(value).unwrap();
+        insta::assert_debug_snapshot!(doggo, @r#"
+        Doggo {
+            one: [
+                222,
+                173,
+            ],
+            truth: [
+                190,
+                239,
+            ],
+            name: "kef�r",
+        }
+        "#);
+    }
+}
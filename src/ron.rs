@@ -0,0 +1,299 @@
+//! Implements the value traits for [`ron::Value`].
+//!
+//! RON has a few types that don't exist in the deserr data model:
+//! - `()` and `None` are surfaced as a [`Value::Null`], and `Some(x)` as the value of `x`
+//! - characters are surfaced as a [`Value::String`] of a single character
+//! - bytes are surfaced as a [`Value::Sequence`] of integers, one per byte
+//!
+//! Note that [`ron::Value`] already loses the names of the structs and of the enum variants
+//! when it is parsed: a named struct such as `Doggo(name: "Kefir")` is seen as a map, a tuple
+//! struct or a variant such as `Bernese("big")` as a sequence, and a unit variant such as
+//! `Husky` as `()`, i.e. a [`Value::Null`]. The enums should thus be written as strings in
+//! the RON documents that are deserialized with deserr.
+//!
+//! Deserializing into a [`ron::Value`] always produces string map keys, `()` instead of
+//! `None`, and no characters or bytes.
+
+use std::ops::ControlFlow;
+
+use crate::value::{key_matches, stringify_key, unsupported_key};
+use crate::{
+    DeserializeError, Deserr, IntoValue, Map, Sequence, Value, ValueKind, ValuePointerRef,
+};
+use ron::{
+    value::{Map as RMap, Number},
+    Value as RValue,
+};
+
+/// Iterator over the entries of a RON map, whose keys are converted to strings as described
+/// by [`Map`].
+pub struct RMapIter {
+    iter: <RMap as IntoIterator>::IntoIter,
+}
+
+impl Iterator for RMapIter {
+    type Item = (String, RValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the keys that can't be converted are refused beforehand, see `Map::unsupported_key`
+        self.iter
+            .by_ref()
+            .find_map(|(k, v)| Some((stringify_key(k).ok()?, v)))
+    }
+}
+
+impl Map for RMap {
+    type Value = RValue;
+    type Iter = RMapIter;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let map_key = self.keys().find(|k| key_matches(*k, key))?.clone();
+        RMap::remove(self, &map_key)
+    }
+    fn into_iter(self) -> Self::Iter {
+        RMapIter {
+            iter: <Self as IntoIterator>::into_iter(self),
+        }
+    }
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        unsupported_key(self.keys())
+    }
+}
+
+/// Convert a RON number by trying a `u64`, then an `i64`, and then falling back to an `f64`.
+fn number_to_value<V: IntoValue>(n: Number) -> Value<V> {
+    let unsigned = match n {
+        Number::U8(n) => Some(u64::from(n)),
+        Number::U16(n) => Some(u64::from(n)),
+        Number::U32(n) => Some(u64::from(n)),
+        Number::U64(n) => Some(n),
+        Number::I8(n) => u64::try_from(n).ok(),
+        Number::I16(n) => u64::try_from(n).ok(),
+        Number::I32(n) => u64::try_from(n).ok(),
+        Number::I64(n) => u64::try_from(n).ok(),
+        _ => None,
+    };
+    let signed = match n {
+        Number::I8(n) => Some(i64::from(n)),
+        Number::I16(n) => Some(i64::from(n)),
+        Number::I32(n) => Some(i64::from(n)),
+        Number::I64(n) => Some(n),
+        _ => None,
+    };
+    if let Some(n) = unsigned {
+        Value::Integer(n)
+    } else if let Some(n) = signed {
+        Value::NegativeInteger(n)
+    } else {
+        Value::Float(n.into_f64())
+    }
+}
+
+/// Surface bytes as a sequence of integers, see the [module documentation](self).
+fn bytes_to_sequence(bytes: Vec<u8>) -> Vec<RValue> {
+    bytes
+        .into_iter()
+        .map(|byte| RValue::Number(byte.into()))
+        .collect()
+}
+
+impl IntoValue for RValue {
+    type Sequence = Vec<RValue>;
+    type Map = RMap;
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            RValue::Unit | RValue::Option(None) => Value::Null,
+            RValue::Option(Some(x)) => x.into_value(),
+            RValue::Bool(b) => Value::Boolean(b),
+            RValue::Char(c) => Value::String(c.to_string()),
+            RValue::Number(n) => number_to_value(n),
+            RValue::String(s) => Value::String(s),
+            RValue::Bytes(x) => Value::Sequence(bytes_to_sequence(x)),
+            RValue::Seq(x) => Value::Sequence(x),
+            RValue::Map(x) => Value::Map(x),
+        }
+    }
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            RValue::Unit | RValue::Option(None) => ValueKind::Null,
+            RValue::Option(Some(x)) => x.kind(),
+            RValue::Bool(_) => ValueKind::Boolean,
+            RValue::Char(_) | RValue::String(_) => ValueKind::String,
+            RValue::Number(n) => match number_to_value::<RValue>(*n) {
+                Value::Integer(_) => ValueKind::Integer,
+                Value::NegativeInteger(_) => ValueKind::NegativeInteger,
+                _ => ValueKind::Float,
+            },
+            RValue::Bytes(_) | RValue::Seq(_) => ValueKind::Sequence,
+            RValue::Map(_) => ValueKind::Map,
+        }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for RValue {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let mut error: Option<E> = None;
+        Ok(match value {
+            Value::Null => RValue::Unit,
+            Value::Boolean(b) => RValue::Bool(b),
+            Value::Integer(x) => RValue::Number(Number::from(x)),
+            Value::NegativeInteger(x) => RValue::Number(Number::from(x)),
+            Value::Float(f) => RValue::Number(Number::from(f)),
+            Value::String(s) => RValue::String(s),
+            Value::Sequence(seq) => {
+                let mut rseq = Vec::with_capacity(seq.len());
+                for (index, value) in seq.into_iter().enumerate() {
+                    let result = Self::deserialize_from_value(
+                        value.into_value(),
+                        location.push_index(index),
+                    );
+                    match result {
+                        Ok(value) => {
+                            rseq.push(value);
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_index(index)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    RValue::Seq(rseq)
+                }
+            }
+            Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
+                let mut rmap = RMap::new();
+                for (key, value) in map.into_iter() {
+                    let result =
+                        Self::deserialize_from_value(value.into_value(), location.push_key(&key));
+                    match result {
+                        Ok(value) => {
+                            rmap.insert(key, value);
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_key(&key)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    RValue::Map(rmap)
+                }
+            }
+        })
+    }
+}
+
+impl<V: IntoValue> From<Value<V>> for RValue {
+    fn from(value: Value<V>) -> Self {
+        match value {
+            Value::Null => RValue::Unit,
+            Value::Boolean(b) => RValue::Bool(b),
+            Value::Integer(n) => RValue::Number(Number::from(n)),
+            Value::NegativeInteger(i) => RValue::Number(Number::from(i)),
+            Value::Float(f) => RValue::Number(Number::from(f)),
+            Value::String(s) => RValue::String(s),
+            Value::Sequence(s) => RValue::Seq(
+                s.into_iter()
+                    .map(IntoValue::into_value)
+                    .map(RValue::from)
+                    .collect(),
+            ),
+            Value::Map(m) => RValue::Map(
+                m.into_iter()
+                    .map(|(k, v)| (k, RValue::from(v.into_value())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_value_to_deserr_and_back() {
+        let value: RValue = ron::from_str(
+            r#"(
+                The: "best",
+                doggos: ["are"],
+                the: (bernese: "mountain", age: -3, weight: 18446744073709551615, height: 0.5),
+            )"#,
+        )
+        .unwrap();
+        let deserr = value.clone().into_value();
+        let deserr: RValue = deserr.into();
+        let deserr = ron::to_string(&deserr).unwrap();
+        insta::assert_snapshot!(deserr, @r#"{"The":"best","doggos":["are"],"the":{"age":-3,"bernese":"mountain","height":0.5,"weight":18446744073709551615}}"#);
+
+        // the numbers are widened to 64 bits, but they are written the same way
+        assert_eq!(ron::to_string(&value).unwrap(), deserr);
+    }
+
+    #[test]
+    fn structs_options_and_non_string_keys() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        #[deserr(rename_all = camelCase)]
+        struct Doggo {
+            name: String,
+            initial: String,
+            best_friend: Option<Box<Doggo>>,
+            breed: String,
+            toys: std::collections::BTreeMap<String, String>,
+        }
+
+        let value: RValue = ron::from_str(
+            r#"Doggo(
+                name: "Kefir",
+                initial: 'K',
+                bestFriend: Some(Doggo(name: "Echo", initial: 'E', bestFriend: None, breed: "Husky", toys: {})),
+                breed: "Bernese",
+                toys: { 1: "ball", true: "stick", 'c': "bone" },
+            )"#,
+        )
+        .unwrap();
+
+        let doggo: Doggo =
+            crate::deserialize::<_, _, crate::errors::QueryParamError>(value).unwrap();
+        insta::assert_debug_snapshot!(doggo, @r###"
+        Doggo {
+            name: "Kefir",
+            initial: "K",
+            best_friend: Some(
+                Doggo {
+                    name: "Echo",
+                    initial: "E",
+                    best_friend: None,
+                    breed: "Husky",
+                    toys: {},
+                },
+            ),
+            breed: "Bernese",
+            toys: {
+                "1": "ball",
+                "c": "bone",
+                "true": "stick",
+            },
+        }
+        "###);
+    }
+}
@@ -26,12 +26,11 @@ where
                     location,
                 ))),
             },
-            value => Err(take_cf_content(E::error::<V>(
+            value => Err(take_cf_content(E::incorrect_value_kind_error::<V>(
                 None,
-                ErrorKind::IncorrectValueKind {
-                    actual: value,
-                    accepted: &[ValueKind::String],
-                },
+                value,
+                &[ValueKind::String],
+                std::any::type_name::<Self>(),
                 location,
             ))),
         }
@@ -1,9 +1,11 @@
-use std::ops::ControlFlow;
+use std::{marker::PhantomData, ops::ControlFlow};
 
 use crate::{
-    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
-    ValueKind, ValuePointerRef,
+    errors::JsonError, take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue,
+    IntoValueRef, Map, MapRef, Sequence, SequenceRef, Value, ValueKind, ValuePointerComponent,
+    ValuePointerRef, ValueRef,
 };
+use serde::de::{DeserializeSeed, Deserializer, Error as _, MapAccess, SeqAccess, Visitor};
 use serde_json::{Map as JMap, Number, Value as JValue};
 
 impl Map for JMap<String, JValue> {
@@ -34,10 +36,11 @@ impl IntoValue for JValue {
                     Value::Integer(n)
                 } else if let Some(n) = n.as_i64() {
                     Value::NegativeInteger(n)
-                } else if let Some(n) = n.as_f64() {
-                    Value::Float(n)
                 } else {
-                    panic!();
+                    // a json number is always representable as a float, except with the
+                    // `arbitrary_precision` feature of serde_json. The functions of this module
+                    // refuse such numbers beforehand, see `deserialize_json`.
+                    Value::Float(n.as_f64().unwrap_or(f64::NAN))
                 }
             }
             JValue::String(x) => Value::String(x),
@@ -55,10 +58,8 @@ impl IntoValue for JValue {
                     ValueKind::Integer
                 } else if n.is_i64() {
                     ValueKind::NegativeInteger
-                } else if n.is_f64() {
-                    ValueKind::Float
                 } else {
-                    panic!();
+                    ValueKind::Float
                 }
             }
             JValue::String(_) => ValueKind::String,
@@ -68,6 +69,78 @@ impl IntoValue for JValue {
     }
 }
 
+impl<'a> SequenceRef<'a> for &'a Vec<JValue> {
+    type Value = &'a JValue;
+    type Owned = Vec<JValue>;
+    type Iter = std::slice::Iter<'a, JValue>;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+    fn into_iter(self) -> Self::Iter {
+        self.iter()
+    }
+    fn into_owned(self) -> Self::Owned {
+        self.clone()
+    }
+}
+
+/// Iterator over the entries of a borrowed [`serde_json::Map`] with `&str` keys.
+pub struct JMapRefIter<'a>(serde_json::map::Iter<'a>);
+
+impl<'a> Iterator for JMapRefIter<'a> {
+    type Item = (&'a str, &'a JValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.as_str(), value))
+    }
+}
+
+impl<'a> MapRef<'a> for &'a JMap<String, JValue> {
+    type Value = &'a JValue;
+    type Owned = JMap<String, JValue>;
+    type Iter = JMapRefIter<'a>;
+
+    fn len(&self) -> usize {
+        JMap::len(self)
+    }
+    fn into_iter(self) -> Self::Iter {
+        JMapRefIter(self.iter())
+    }
+    fn into_owned(self) -> Self::Owned {
+        self.clone()
+    }
+}
+
+impl<'a> IntoValueRef<'a> for &'a JValue {
+    type Owned = JValue;
+    type SequenceRef = &'a Vec<JValue>;
+    type MapRef = &'a JMap<String, JValue>;
+
+    fn kind(&self) -> ValueKind {
+        IntoValue::kind(*self)
+    }
+
+    fn into_value_ref(self) -> ValueRef<'a, Self> {
+        match self {
+            JValue::Null => ValueRef::Null,
+            JValue::Bool(b) => ValueRef::Boolean(*b),
+            JValue::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    ValueRef::Integer(n)
+                } else if let Some(n) = n.as_i64() {
+                    ValueRef::NegativeInteger(n)
+                } else {
+                    ValueRef::Float(n.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            JValue::String(x) => ValueRef::String(x),
+            JValue::Array(x) => ValueRef::Sequence(x),
+            JValue::Object(x) => ValueRef::Map(x),
+        }
+    }
+}
+
 impl<E: DeserializeError> Deserr<E> for JValue {
     fn deserialize_from_value<V: IntoValue>(
         value: Value<V>,
@@ -117,11 +190,29 @@ impl<E: DeserializeError> Deserr<E> for JValue {
                     JValue::Array(jseq)
                 }
             }
+            Value::Map(map) => JValue::Object(JMap::deserialize_from_value(
+                Value::<V>::Map(map),
+                location,
+            )?),
+        })
+    }
+}
+
+/// The entries are inserted in the order of the deserialized map, which is kept
+/// if the `preserve_order` feature of deserr, or of `serde_json`, is enabled.
+impl<E: DeserializeError> Deserr<E> for JMap<String, JValue> {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
             Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
+                let mut error: Option<E> = None;
                 let mut jmap = JMap::with_capacity(map.len());
                 for (key, value) in map.into_iter() {
                     let result =
-                        Self::deserialize_from_value(value.into_value(), location.push_key(&key));
+                        JValue::deserialize_from_value(value.into_value(), location.push_key(&key));
                     match result {
                         Ok(value) => {
                             jmap.insert(key, value);
@@ -135,12 +226,19 @@ impl<E: DeserializeError> Deserr<E> for JValue {
                     }
                 }
                 if let Some(e) = error {
-                    return Err(e);
+                    Err(e)
                 } else {
-                    JValue::Object(jmap)
+                    Ok(jmap)
                 }
             }
-        })
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
+                None,
+                v,
+                &[ValueKind::Map],
+                std::any::type_name::<Self>(),
+                location,
+            ))),
+        }
     }
 }
 
@@ -170,6 +268,445 @@ impl<V: IntoValue> From<Value<V>> for JValue {
     }
 }
 
+/// Parse the JSON string `s` and deserialize it into `T`.
+///
+/// The JSON syntax errors are returned through the same error type `E` as the deserialization
+/// errors, as an [`ErrorKind::Unexpected`] at the origin. Their message contains the byte offset
+/// at which the error happened.
+///
+/// When an object contains the same key several times, only its last value is kept, like
+/// in [`serde_json::from_str`]. Use [`from_json_str_strict`] to reject such objects instead.
+pub fn from_json_str<T, E>(s: &str) -> Result<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    match serde_json::from_str::<JValue>(s) {
+        Ok(value) => deserialize_json(value),
+        Err(e) => Err(syntax_error(s, e)),
+    }
+}
+
+/// Parse the JSON string `s` and deserialize it into `T`, like [`from_json_str`], but reject the
+/// objects that contain the same key several times.
+///
+/// The duplicated key is reported as an [`ErrorKind::Unexpected`] located at its second
+/// occurrence, e.g. at `.doggo.name` for `{ "doggo": { "name": "Kefir", "name": "Echo" } }`.
+///
+/// ## Example
+/// ```
+/// use deserr::{errors::JsonError, from_json_str_strict, Deserr};
+///
+/// #[derive(Deserr, Debug)]
+/// struct Doggo {
+///     name: String,
+/// }
+///
+/// let error = from_json_str_strict::<Doggo, JsonError>(r#"{ "name": "Kefir", "name": "Echo" }"#)
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "Invalid value at `.name`: the key `name` is defined more than once");
+/// ```
+pub fn from_json_str_strict<T, E>(s: &str) -> Result<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    let mut state = StrictState::default();
+    let mut deserializer = serde_json::Deserializer::from_str(s);
+    let value = StrictValueSeed { state: &mut state }
+        .deserialize(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|()| value));
+
+    match (value, state.duplicate) {
+        (Ok(value), _) => deserialize_json(value),
+        (Err(_), Some((mut path, key))) => {
+            let msg = format!("the key `{key}` is defined more than once");
+            path.push(ValuePointerComponent::Key(key));
+            Err(with_pointer_ref(
+                &path,
+                ValuePointerRef::Origin,
+                |location| {
+                    take_cf_content(E::error::<JValue>(
+                        None,
+                        ErrorKind::Unexpected { msg },
+                        location,
+                    ))
+                },
+            ))
+        }
+        (Err(e), None) => Err(syntax_error(s, e)),
+    }
+}
+
+/// Parse the JSON values read from `reader` one after the other, and deserialize each of them
+/// into `T`, e.g. to process the lines of an [NDJSON](https://github.com/ndjson/ndjson-spec)
+/// stream.
+///
+/// The values may be separated by any whitespace. They are parsed lazily, so only the value
+/// being deserialized is kept in memory, instead of the whole stream. Each value is
+/// deserialized on its own, so the locations of its errors start at its origin; the index of
+/// the value is given by [`Iterator::enumerate`].
+///
+/// A JSON syntax error or an I/O error is returned through the error type `E`, as an
+/// [`ErrorKind::Unexpected`] at the origin, and ends the stream. A value that can't be
+/// deserialized into `T` doesn't.
+///
+/// ## Example
+/// ```
+/// use deserr::{errors::JsonError, from_json_stream, Deserr};
+///
+/// #[derive(Deserr, Debug, PartialEq, Eq)]
+/// struct Doggo {
+///     name: String,
+/// }
+///
+/// let input = "{ \"name\": \"Kefir\" }\n{ \"name\": 2 }\n{ \"name\": \"Echo\" }\n";
+/// let mut doggos = from_json_stream::<Doggo, JsonError, _>(input.as_bytes());
+///
+/// assert_eq!(doggos.next().unwrap().unwrap(), Doggo { name: String::from("Kefir") });
+/// assert_eq!(
+///     doggos.next().unwrap().unwrap_err().to_string(),
+///     "Invalid value type at `.name`: expected a string, but found a positive integer: `2`",
+/// );
+/// assert_eq!(doggos.next().unwrap().unwrap(), Doggo { name: String::from("Echo") });
+/// assert!(doggos.next().is_none());
+/// ```
+pub fn from_json_stream<T, E, R>(reader: R) -> JsonStream<R, T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+    R: std::io::Read,
+{
+    JsonStream {
+        values: serde_json::Deserializer::from_reader(reader).into_iter(),
+        output: PhantomData,
+    }
+}
+
+/// An iterator over the values of a JSON stream deserialized into `T`, see
+/// [`from_json_stream`].
+pub struct JsonStream<R: std::io::Read, T, E> {
+    values: serde_json::StreamDeserializer<'static, serde_json::de::IoRead<R>, JValue>,
+    output: PhantomData<fn() -> (T, E)>,
+}
+
+impl<R, T, E> JsonStream<R, T, E>
+where
+    R: std::io::Read,
+{
+    /// Return the number of bytes of the stream parsed so far, i.e. the offset of the end of
+    /// the last value that was parsed successfully.
+    pub fn byte_offset(&self) -> usize {
+        self.values.byte_offset()
+    }
+}
+
+impl<R, T, E> Iterator for JsonStream<R, T, E>
+where
+    R: std::io::Read,
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The stream is over after a syntax error, the next calls return `None`
+        Some(match self.values.next()? {
+            Ok(value) => deserialize_json(value),
+            Err(e) => Err(take_cf_content(E::error::<JValue>(
+                None,
+                ErrorKind::Unexpected { msg: e.to_string() },
+                ValuePointerRef::Origin,
+            ))),
+        })
+    }
+}
+
+/// Parse each line read from `reader` as a JSON value, e.g. the lines of an
+/// [NDJSON](https://github.com/ndjson/ndjson-spec) log, and deserialize it into `T`.
+///
+/// The messages of the errors start with the number of their line, counted from 1. Unlike
+/// [`from_json_stream`], a line that isn't valid UTF-8 or valid JSON doesn't stop the
+/// iteration, only an I/O error does. The empty lines are skipped.
+///
+/// ## Example
+/// ```
+/// use deserr::{errors::JsonError, from_ndjson, Deserr};
+///
+/// #[derive(Deserr, Debug, PartialEq, Eq)]
+/// struct Log {
+///     level: String,
+/// }
+///
+/// let input = "{ \"level\": \"info\" }\n{ \"level\": 2 }\n{ \"level\": \n\n{ \"level\": \"warn\" }\n";
+/// let logs = from_ndjson::<Log, _>(input.as_bytes())
+///     .map(|log| log.map_err(|e| e.to_string()))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(logs, [
+///     Ok(Log { level: String::from("info") }),
+///     Err(String::from("Line 2: Invalid value type at `.level`: expected a string, but found a positive integer: `2`")),
+///     Err(String::from("Line 3: Invalid value: EOF while parsing a value at column 11")),
+///     Ok(Log { level: String::from("warn") }),
+/// ]);
+/// ```
+pub fn from_ndjson<T, R>(reader: R) -> NdjsonLines<R, T>
+where
+    T: Deserr<JsonError>,
+    R: std::io::BufRead,
+{
+    NdjsonLines {
+        lines: reader.split(b'\n'),
+        line: 0,
+        failed: false,
+        output: PhantomData,
+    }
+}
+
+/// An iterator over the lines of an NDJSON input deserialized into `T`, see [`from_ndjson`].
+pub struct NdjsonLines<R, T> {
+    lines: std::io::Split<R>,
+    /// The number of the last line read
+    line: usize,
+    /// Whether an I/O error ended the input
+    failed: bool,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<R, T> Iterator for NdjsonLines<R, T>
+where
+    R: std::io::BufRead,
+    T: Deserr<JsonError>,
+{
+    type Item = Result<T, JsonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let line = loop {
+            self.line += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => String::from_utf8(line),
+                // The reader may keep failing, so nothing is read after an I/O error
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(unexpected_error(e.to_string()).on_line(self.line)));
+                }
+            };
+            match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                // The line was read up to its `\n`, so the next one can still be read
+                Err(e) => {
+                    let msg = format!("the line isn't valid UTF-8: {}", e.utf8_error());
+                    return Some(Err(unexpected_error(msg).on_line(self.line)));
+                }
+                Ok(line) => break line,
+            }
+        };
+        let result = match serde_json::from_str::<JValue>(&line) {
+            Ok(value) => deserialize_json(value),
+            // The position of the error is given by its column, the line being known
+            Err(e) => {
+                let msg = e.to_string();
+                let position = format!(" at line {} column {}", e.line(), e.column());
+                let msg = msg.strip_suffix(&position).unwrap_or(&msg);
+                Err(unexpected_error(format!("{msg} at column {}", e.column())))
+            }
+        };
+        Some(result.map_err(|e| e.on_line(self.line)))
+    }
+}
+
+/// Deserialize a parsed JSON value into `T`.
+///
+/// With the `arbitrary_precision` feature of serde_json, a number may be too large to be
+/// represented as a `f64`. Such a number is refused with an [`ErrorKind::Unexpected`] at its
+/// location instead of being deserialized as `NaN`.
+fn deserialize_json<T, E>(value: JValue) -> Result<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    if arbitrary_precision() {
+        check_numbers(&value, ValuePointerRef::Origin)?;
+    }
+    crate::deserialize(value)
+}
+
+/// Whether serde_json keeps the numbers that don't fit in a `f64`, which only happens with its
+/// `arbitrary_precision` feature. Otherwise, such numbers are syntax errors.
+fn arbitrary_precision() -> bool {
+    static ARBITRARY_PRECISION: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *ARBITRARY_PRECISION.get_or_init(|| serde_json::from_str::<Number>("1e400").is_ok())
+}
+
+/// Return an [`ErrorKind::Unexpected`] error for the first number of `value` that is neither
+/// an integer of deserr nor a `f64`, see [`deserialize_json`].
+fn check_numbers<E: DeserializeError>(value: &JValue, location: ValuePointerRef) -> Result<(), E> {
+    match value {
+        JValue::Number(n) if !n.is_u64() && !n.is_i64() && n.as_f64().is_none() => {
+            Err(take_cf_content(E::error::<JValue>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!("the number `{n}` is out of the range of a float"),
+                },
+                location,
+            )))
+        }
+        JValue::Array(elements) => elements
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, element)| check_numbers(element, location.push_index(index))),
+        JValue::Object(entries) => entries
+            .iter()
+            .try_for_each(|(key, value)| check_numbers(value, location.push_key(key))),
+        _ => Ok(()),
+    }
+}
+
+/// Return an [`ErrorKind::Unexpected`] error at the origin with the given message.
+fn unexpected_error(msg: String) -> JsonError {
+    take_cf_content(JsonError::error::<JValue>(
+        None,
+        ErrorKind::Unexpected { msg },
+        ValuePointerRef::Origin,
+    ))
+}
+
+/// Report a JSON syntax error through the deserialization error type, see [`from_json_str`].
+fn syntax_error<E: DeserializeError>(s: &str, e: serde_json::Error) -> E {
+    take_cf_content(E::error::<JValue>(
+        None,
+        ErrorKind::Unexpected {
+            msg: format!("{e} (at byte {})", byte_offset(s, e.line(), e.column())),
+        },
+        ValuePointerRef::Origin,
+    ))
+}
+
+/// Call `f` with the location of `path`, relative to `location`.
+fn with_pointer_ref<R>(
+    path: &[ValuePointerComponent],
+    location: ValuePointerRef,
+    f: impl FnOnce(ValuePointerRef) -> R,
+) -> R {
+    match path.split_first() {
+        None => f(location),
+        Some((ValuePointerComponent::Key(key), rest)) => {
+            with_pointer_ref(rest, location.push_key(key), f)
+        }
+        Some((ValuePointerComponent::Index(index), rest)) => {
+            with_pointer_ref(rest, location.push_index(*index), f)
+        }
+    }
+}
+
+/// The state of the parsing of [`from_json_str_strict`].
+#[derive(Default)]
+struct StrictState {
+    /// The path to the value being parsed.
+    path: Vec<ValuePointerComponent>,
+    /// The path to the object containing a duplicated key, and the key, once it is found.
+    duplicate: Option<(Vec<ValuePointerComponent>, String)>,
+}
+
+/// Parse a [`JValue`] and stop at the first duplicated key, see [`from_json_str_strict`].
+struct StrictValueSeed<'a> {
+    state: &'a mut StrictState,
+}
+
+impl<'de> DeserializeSeed<'de> for StrictValueSeed<'_> {
+    type Value = JValue;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<JValue, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StrictValueSeed<'_> {
+    type Value = JValue;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<Err>(self, v: bool) -> Result<JValue, Err> {
+        Ok(JValue::Bool(v))
+    }
+
+    fn visit_i64<Err>(self, v: i64) -> Result<JValue, Err> {
+        Ok(JValue::from(v))
+    }
+
+    fn visit_u64<Err>(self, v: u64) -> Result<JValue, Err> {
+        Ok(JValue::from(v))
+    }
+
+    fn visit_f64<Err>(self, v: f64) -> Result<JValue, Err> {
+        Ok(JValue::from(v))
+    }
+
+    fn visit_str<Err>(self, v: &str) -> Result<JValue, Err> {
+        Ok(JValue::String(v.to_owned()))
+    }
+
+    fn visit_string<Err>(self, v: String) -> Result<JValue, Err> {
+        Ok(JValue::String(v))
+    }
+
+    fn visit_unit<Err>(self) -> Result<JValue, Err> {
+        Ok(JValue::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JValue, A::Error> {
+        let mut values = Vec::new();
+        loop {
+            self.state
+                .path
+                .push(ValuePointerComponent::Index(values.len()));
+            let value = seq.next_element_seed(StrictValueSeed {
+                state: &mut *self.state,
+            });
+            self.state.path.pop();
+            match value? {
+                Some(value) => values.push(value),
+                None => return Ok(JValue::Array(values)),
+            }
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JValue, A::Error> {
+        let mut values = JMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if values.contains_key(&key) {
+                let error = A::Error::custom(format!("duplicate key `{key}`"));
+                self.state.duplicate = Some((self.state.path.clone(), key));
+                return Err(error);
+            }
+            self.state
+                .path
+                .push(ValuePointerComponent::Key(key.clone()));
+            let value = map.next_value_seed(StrictValueSeed {
+                state: &mut *self.state,
+            });
+            self.state.path.pop();
+            values.insert(key, value?);
+        }
+        Ok(JValue::Object(values))
+    }
+}
+
+/// Convert the one-based `line` and `column` of a serde_json error to a byte offset in `s`.
+fn byte_offset(s: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = s
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column.saturating_sub(1)).min(s.len())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -209,4 +746,269 @@ mod test {
 
         assert_eq!(value, deserr);
     }
+
+    #[test]
+    fn from_json_str_errors() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            name: String,
+        }
+
+        let doggo = from_json_str::<Doggo, crate::errors::JsonError>(r#"{ "name": "Intel" }"#);
+        insta::assert_debug_snapshot!(doggo, @r#"
+        Ok(
+            Doggo {
+                name: "Intel",
+            },
+        )
+        "#);
+
+        let error =
+            from_json_str::<Doggo, crate::errors::JsonError>("{\n  \"name\": \"Intel\",\n}")
+                .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: trailing comma at line 3 column 1 (at byte 21)");
+
+        let error =
+            from_json_str::<Doggo, crate::errors::JsonError>(r#"{ "name": 2 }"#).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type at `.name`: expected a string, but found a positive integer: `2`");
+    }
+
+    #[test]
+    fn from_json_stream_errors() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            name: String,
+        }
+
+        let input = "{ \"name\": \"Kefir\" } {\"name\":\"Echo\"}\n\n{ \"name\": null }\n{ \"name\": \"Intel\", }\n{ \"name\": \"Bob\" }\n";
+        let mut doggos = from_json_stream::<Doggo, crate::errors::JsonError, _>(input.as_bytes());
+        insta::assert_debug_snapshot!(doggos.next(), @r###"
+        Some(
+            Ok(
+                Doggo {
+                    name: "Kefir",
+                },
+            ),
+        )
+        "###);
+        insta::assert_debug_snapshot!(doggos.next(), @r###"
+        Some(
+            Ok(
+                Doggo {
+                    name: "Echo",
+                },
+            ),
+        )
+        "###);
+        insta::assert_debug_snapshot!(doggos.next(), @r###"
+        Some(
+            Err(
+                JsonError(
+                    "Invalid value type at `.name`: expected a string, but found null",
+                ),
+            ),
+        )
+        "###);
+        assert_eq!(doggos.byte_offset(), 53);
+
+        // the values that follow a syntax error can't be found
+        insta::assert_debug_snapshot!(doggos.next(), @r###"
+        Some(
+            Err(
+                JsonError(
+                    "Invalid value: trailing comma at line 4 column 20",
+                ),
+            ),
+        )
+        "###);
+        assert!(doggos.next().is_none());
+    }
+
+    #[test]
+    fn from_ndjson_errors() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            name: String,
+        }
+
+        let input = b"{ \"name\": \"Kefir\" }\r\n\r\n  \n{ \"name\": \"Echo\", }\r\n{ \"nmae\": \"Intel\" }\n\xff\n{ \"name\": \"Bob\" }\n";
+        let doggos = from_ndjson::<Doggo, _>(&input[..])
+            .map(|doggo| doggo.map_err(|e| e.to_string()))
+            .collect::<Vec<_>>();
+        // a line that isn't valid UTF-8 doesn't stop the iteration
+        insta::assert_debug_snapshot!(doggos, @r###"
+        [
+            Ok(
+                Doggo {
+                    name: "Kefir",
+                },
+            ),
+            Err(
+                "Line 4: Invalid value: trailing comma at column 19",
+            ),
+            Err(
+                "Line 5: Missing field `name`",
+            ),
+            Err(
+                "Line 6: Invalid value: the line isn't valid UTF-8: invalid utf-8 sequence of 1 bytes from index 0",
+            ),
+            Ok(
+                Doggo {
+                    name: "Bob",
+                },
+            ),
+        ]
+        "###);
+
+        // but nothing is read after an I/O error
+        struct Failing;
+
+        impl std::io::Read for Failing {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("the disk is on fire"))
+            }
+        }
+
+        let input = std::io::Read::chain(&b"{ \"name\": \"Kefir\" }\n"[..], Failing);
+        let doggos = from_ndjson::<Doggo, _>(std::io::BufReader::new(input))
+            .map(|doggo| doggo.map_err(|e| e.to_string()))
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(doggos, @r###"
+        [
+            Ok(
+                Doggo {
+                    name: "Kefir",
+                },
+            ),
+            Err(
+                "Line 2: Invalid value: the disk is on fire",
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn from_json_str_strict_errors() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            name: String,
+            friends: Vec<Doggo>,
+        }
+
+        let json = r#"{ "name": "Kefir", "friends": [{ "name": "Echo", "friends": [] }] }"#;
+        let doggo = from_json_str_strict::<Doggo, crate::errors::JsonError>(json);
+        insta::assert_debug_snapshot!(doggo, @r###"
+        Ok(
+            Doggo {
+                name: "Kefir",
+                friends: [
+                    Doggo {
+                        name: "Echo",
+                        friends: [],
+                    },
+                ],
+            },
+        )
+        "###);
+
+        // the last value of the duplicated key is silently kept by `from_json_str`
+        let json = r#"{ "name": "Kefir", "friends": [{ "name": "Echo", "name": "Intel", "friends": [] }] }"#;
+        let doggo = from_json_str::<Doggo, crate::errors::JsonError>(json).unwrap();
+        assert_eq!(doggo.friends[0].name, "Intel");
+
+        let error = from_json_str_strict::<Doggo, crate::errors::JsonError>(json).unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.friends[0].name`: the key `name` is defined more than once");
+
+        let error = from_json_str_strict::<Doggo, crate::errors::JsonError>(
+            r#"{ "name": "Kefir", "friends": [], "friends": [] }"#,
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.friends`: the key `friends` is defined more than once");
+
+        let error =
+            from_json_str_strict::<Doggo, crate::errors::JsonError>("{\n  \"name\": \"Intel\",\n}")
+                .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: trailing comma at line 3 column 1 (at byte 21)");
+
+        let error =
+            from_json_str_strict::<Doggo, crate::errors::JsonError>(r#"{ "name": "Kefir" } []"#)
+                .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: trailing characters at line 1 column 21 (at byte 20)");
+    }
+
+    #[test]
+    fn passthrough_fields() {
+        #[allow(dead_code)]
+        #[derive(Debug, deserr::Deserr)]
+        struct Doggo {
+            name: String,
+            metadata: JValue,
+            toys: JMap<String, JValue>,
+        }
+
+        let doggo = crate::deserialize::<Doggo, _, crate::errors::JsonError>(json!({
+            "name": "Kefir",
+            "metadata": { "age": 3, "friends": ["Echo", null], "vaccinated": true, "weight": -1.5 },
+            "toys": { "ball": { "color": "red" }, "stick": 2 },
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(doggo, @r###"
+        Doggo {
+            name: "Kefir",
+            metadata: Object {
+                "age": Number(3),
+                "friends": Array [
+                    String("Echo"),
+                    Null,
+                ],
+                "vaccinated": Bool(true),
+                "weight": Number(-1.5),
+            },
+            toys: {
+                "ball": Object {
+                    "color": String("red"),
+                },
+                "stick": Number(2),
+            },
+        }
+        "###);
+
+        let error = crate::deserialize::<Doggo, _, crate::errors::JsonError>(json!({
+            "name": "Kefir",
+            "metadata": null,
+            "toys": ["ball"],
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @r###"Invalid value type at `.toys`: expected an object, but found an array: `["ball"]`"###);
+    }
+
+    #[test]
+    fn non_finite_floats() {
+        let error: crate::errors::JsonError = JValue::deserialize_from_value(
+            Value::<JValue>::Float(f64::NAN),
+            ValuePointerRef::Origin,
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value: the float NaN is not representable in JSON");
+
+        let error: crate::errors::JsonError = JValue::deserialize_from_value(
+            Value::<JValue>::Float(f64::NEG_INFINITY),
+            ValuePointerRef::Origin.push_key("weight"),
+        )
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.weight`: the float -inf is not representable in JSON");
+
+        // the floats are also checked deep inside of a value coming from another format
+        #[cfg(feature = "toml")]
+        {
+            let value: toml::Value = toml::from_str("doggos = [{ weight = nan }]").unwrap();
+            let error =
+                crate::deserialize::<JValue, _, crate::errors::JsonError>(value).unwrap_err();
+            insta::assert_snapshot!(error, @"Invalid value at `.doggos[0].weight`: the float NaN is not representable in JSON");
+        }
+    }
 }
@@ -1,11 +1,13 @@
 use std::ops::ControlFlow;
 
+use crate::value::{stringify_key, unsupported_key};
 use crate::{
-    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
-    ValueKind, ValuePointerRef,
+    DeserializeError, Deserr, IntoValue, Map, Sequence, Value, ValueKind, ValuePointerRef,
 };
 use serde_yml::{Mapping as YMap, Number, Sequence as YSeq, Value as YValue};
 
+/// Iterator over the entries of a YAML mapping, whose keys are converted to strings as described
+/// by [`Map`].
 pub struct YMapIter {
     iter: <YMap as IntoIterator>::IntoIter,
 }
@@ -14,13 +16,10 @@ impl Iterator for YMapIter {
     type Item = (String, YValue);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|(k, v)| {
-            match k {
-                // kinda questionable but oh well
-                YValue::String(s) => (s, v),
-                _ => panic!(),
-            }
-        })
+        // the keys that can't be converted are refused beforehand, see `Map::unsupported_key`
+        self.iter
+            .by_ref()
+            .find_map(|(k, v)| Some((stringify_key(k).ok()?, v)))
     }
 }
 
@@ -39,6 +38,9 @@ impl Map for YMap {
             iter: <Self as IntoIterator>::into_iter(self),
         }
     }
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        unsupported_key(self.keys())
+    }
 }
 
 impl IntoValue for YValue {
@@ -54,10 +56,9 @@ impl IntoValue for YValue {
                     Value::Integer(n)
                 } else if let Some(n) = n.as_i64() {
                     Value::NegativeInteger(n)
-                } else if let Some(n) = n.as_f64() {
-                    Value::Float(n)
                 } else {
-                    panic!();
+                    // a yaml number is always representable as a float
+                    Value::Float(n.as_f64().unwrap_or(f64::NAN))
                 }
             }
             YValue::String(x) => Value::String(x),
@@ -77,10 +78,8 @@ impl IntoValue for YValue {
                     ValueKind::Integer
                 } else if n.is_i64() {
                     ValueKind::NegativeInteger
-                } else if n.is_f64() {
-                    ValueKind::Float
                 } else {
-                    panic!();
+                    ValueKind::Float
                 }
             }
             YValue::String(_) => ValueKind::String,
@@ -131,6 +130,7 @@ impl<E: DeserializeError> Deserr<E> for YValue {
                 }
             }
             Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
                 let mut jmap = YMap::with_capacity(map.len());
                 for (key, value) in map.into_iter() {
                     let result =
@@ -164,7 +164,7 @@ impl<V: IntoValue> From<Value<V>> for YValue {
             Value::Boolean(b) => YValue::Bool(b),
             Value::Integer(n) => YValue::Number(Number::from(n)),
             Value::NegativeInteger(i) => YValue::Number(Number::from(i)),
-            // if we can't parse the float then its set to `null`
+            // YAML can represent every float, so NaN and the infinities become `.nan` and `.inf`
             Value::Float(f) => YValue::Number(Number::from(f)),
             Value::String(s) => YValue::String(s),
             Value::Sequence(s) => YValue::Sequence(
@@ -184,15 +184,17 @@ impl<V: IntoValue> From<Value<V>> for YValue {
 #[cfg(test)]
 mod test {
     use super::*;
-    use serde_json::json;
 
     #[test]
     fn from_value_to_deserr_and_back() {
-        let value: YValue = serde_yml::from_str("
+        let value: YValue = serde_yml::from_str(
+            "
         The: best
         doggos: [are]
         the:
-          bernese: mountain").unwrap();
+          bernese: mountain",
+        )
+        .unwrap();
         let deserr = value.clone().into_value();
 
         insta::assert_debug_snapshot!(deserr, @r###"
@@ -224,4 +226,85 @@ mod test {
 
         assert_eq!(value, deserr);
     }
+
+    #[test]
+    fn non_string_keys_are_stringified() {
+        let value: YValue = serde_yml::from_str(
+            "
+        1: integer
+        -2: negative
+        1.5: float
+        true: boolean
+        ~: nothing
+        !doggo kefir: tagged",
+        )
+        .unwrap();
+
+        let map: std::collections::BTreeMap<String, String> =
+            crate::deserialize::<_, _, crate::errors::QueryParamError>(value).unwrap();
+        insta::assert_debug_snapshot!(map, @r###"
+        {
+            "-2": "negative",
+            "1": "integer",
+            "1.5": "float",
+            "kefir": "tagged",
+            "null": "nothing",
+            "true": "boolean",
+        }
+        "###);
+    }
+
+    #[test]
+    fn non_scalar_keys_are_errors() {
+        #[allow(dead_code)]
+        #[derive(Debug, crate::Deserr)]
+        struct Struct {
+            doggo: String,
+        }
+
+        let value: YValue = serde_yml::from_str(
+            "
+        doggo: bork
+        [a, b]: sequence",
+        )
+        .unwrap();
+        let err =
+            crate::deserialize::<Struct, _, crate::errors::JsonError>(value.clone()).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value: the map has a key that is a sequence, but a key must be a string, a number, a boolean or null");
+
+        let value: YValue = serde_yml::from_str(
+            "
+        doggos:
+          {x: y}: mapping",
+        )
+        .unwrap();
+        let err = crate::deserialize::<
+            std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+            _,
+            crate::errors::JsonError,
+        >(value.clone())
+        .unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value at `.doggos`: the map has a key that is a map, but a key must be a string, a number, a boolean or null");
+        let err = crate::deserialize::<YValue, _, crate::errors::JsonError>(value).unwrap_err();
+        insta::assert_snapshot!(err, @"Invalid value at `.doggos`: the map has a key that is a map, but a key must be a string, a number, a boolean or null");
+    }
+
+    #[test]
+    fn non_finite_floats() {
+        let value = Value::<YValue>::Sequence(vec![
+            YValue::from(f64::NAN),
+            YValue::from(f64::INFINITY),
+            YValue::from(f64::NEG_INFINITY),
+        ]);
+        let yaml = YValue::from(value);
+        insta::assert_snapshot!(serde_yml::to_string(&yaml).unwrap(), @r###"
+        - .nan
+        - .inf
+        - -.inf
+        "###);
+
+        let yaml: YValue =
+            crate::deserialize::<_, _, crate::errors::JsonError>(YValue::from(f64::NAN)).unwrap();
+        assert!(yaml.as_f64().unwrap().is_nan());
+    }
 }
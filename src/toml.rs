@@ -0,0 +1,384 @@
+//! Implements the value traits for [`toml::Value`].
+//!
+//! TOML has a native datetime type that doesn't exist in the deserr data model.
+//! Datetimes are surfaced as a [`Value::String`] containing their TOML representation,
+//! and any string that is a valid TOML datetime is converted back to a datetime when
+//! deserializing into a [`toml::Value`].
+//!
+//! TOML has no `null` either. Deserializing a [`Value::Null`] into a [`toml::Value`]
+//! returns an error, while the infallible `From` conversion turns it into an empty string.
+
+use std::ops::ControlFlow;
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Sequence, Value,
+    ValueKind, ValuePointerRef,
+};
+use toml::{value::Datetime, Table as TMap, Value as TValue};
+
+impl Map for TMap {
+    type Value = TValue;
+    type Iter = <Self as IntoIterator>::IntoIter;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.remove(key)
+    }
+    fn into_iter(self) -> Self::Iter {
+        <Self as IntoIterator>::into_iter(self)
+    }
+}
+
+impl IntoValue for TValue {
+    type Sequence = Vec<TValue>;
+    type Map = TMap;
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            TValue::Boolean(b) => Value::Boolean(b),
+            TValue::Integer(n) => {
+                if let Ok(n) = u64::try_from(n) {
+                    Value::Integer(n)
+                } else {
+                    Value::NegativeInteger(n)
+                }
+            }
+            TValue::Float(n) => Value::Float(n),
+            TValue::String(x) => Value::String(x),
+            TValue::Datetime(x) => Value::String(x.to_string()),
+            TValue::Array(x) => Value::Sequence(x),
+            TValue::Table(x) => Value::Map(x),
+        }
+    }
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            TValue::Boolean(_) => ValueKind::Boolean,
+            TValue::Integer(n) => {
+                if *n >= 0 {
+                    ValueKind::Integer
+                } else {
+                    ValueKind::NegativeInteger
+                }
+            }
+            TValue::Float(_) => ValueKind::Float,
+            TValue::String(_) => ValueKind::String,
+            TValue::Datetime(_) => ValueKind::String,
+            TValue::Array(_) => ValueKind::Sequence,
+            TValue::Table(_) => ValueKind::Map,
+        }
+    }
+}
+
+/// Convert a string back to a TOML datetime if it was one.
+fn string_or_datetime(s: String) -> TValue {
+    match s.parse::<Datetime>() {
+        Ok(datetime) => TValue::Datetime(datetime),
+        Err(_) => TValue::String(s),
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for TValue {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let mut error: Option<E> = None;
+        Ok(match value {
+            Value::Null => {
+                return Err(take_cf_content(E::error::<V>(
+                    error,
+                    ErrorKind::Unexpected {
+                        msg: String::from("null is not representable in TOML"),
+                    },
+                    location,
+                )));
+            }
+            Value::Boolean(b) => TValue::Boolean(b),
+            Value::Integer(x) => match i64::try_from(x) {
+                Ok(x) => TValue::Integer(x),
+                Err(_) => {
+                    return Err(take_cf_content(E::error::<V>(
+                        error,
+                        ErrorKind::Unexpected {
+                            msg: format!("the integer {x} is not representable in TOML"),
+                        },
+                        location,
+                    )));
+                }
+            },
+            Value::NegativeInteger(x) => TValue::Integer(x),
+            Value::Float(f) => TValue::Float(f),
+            Value::String(s) => string_or_datetime(s),
+            Value::Sequence(seq) => {
+                let mut tseq = Vec::with_capacity(seq.len());
+                for (index, value) in seq.into_iter().enumerate() {
+                    let result = Self::deserialize_from_value(
+                        value.into_value(),
+                        location.push_index(index),
+                    );
+                    match result {
+                        Ok(value) => {
+                            tseq.push(value);
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_index(index)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    TValue::Array(tseq)
+                }
+            }
+            Value::Map(map) => {
+                crate::check_map_keys::<_, E>(&map, location)?;
+                let mut tmap = TMap::with_capacity(map.len());
+                for (key, value) in map.into_iter() {
+                    let result =
+                        Self::deserialize_from_value(value.into_value(), location.push_key(&key));
+                    match result {
+                        Ok(value) => {
+                            tmap.insert(key, value);
+                        }
+                        Err(e) => {
+                            error = match E::merge(error, e, location.push_key(&key)) {
+                                ControlFlow::Continue(e) => Some(e),
+                                ControlFlow::Break(e) => return Err(e),
+                            };
+                        }
+                    }
+                }
+                if let Some(e) = error {
+                    return Err(e);
+                } else {
+                    TValue::Table(tmap)
+                }
+            }
+        })
+    }
+}
+
+impl<V: IntoValue> From<Value<V>> for TValue {
+    fn from(value: Value<V>) -> Self {
+        match value {
+            // TOML has no null, so we use the closest thing to an empty value
+            Value::Null => TValue::String(String::new()),
+            Value::Boolean(b) => TValue::Boolean(b),
+            // integers that don't fit in an `i64` are converted to a float
+            Value::Integer(n) => i64::try_from(n)
+                .map(TValue::Integer)
+                .unwrap_or(TValue::Float(n as f64)),
+            Value::NegativeInteger(i) => TValue::Integer(i),
+            Value::Float(f) => TValue::Float(f),
+            Value::String(s) => string_or_datetime(s),
+            Value::Sequence(s) => TValue::Array(
+                s.into_iter()
+                    .map(IntoValue::into_value)
+                    .map(TValue::from)
+                    .collect(),
+            ),
+            Value::Map(m) => TValue::Table(
+                m.into_iter()
+                    .map(|(k, v)| (k, TValue::from(v.into_value())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_value_to_deserr_and_back() {
+        let value: TValue = toml::from_str(
+            r#"
+        The = "best"
+        doggos = ["are"]
+        born = 1979-05-27T07:32:00Z
+
+        [the]
+        bernese = "mountain"
+
+        [[pack]]
+        name = "rex"
+        age = 3
+
+        [[pack]]
+        name = "fido"
+        age = -1
+        "#,
+        )
+        .unwrap();
+        let deserr = value.clone().into_value();
+
+        insta::assert_debug_snapshot!(deserr, @r#"
+        Map(
+            {
+                "The": String(
+                    "best",
+                ),
+                "born": Datetime(
+                    Datetime {
+                        date: Some(
+                            Date {
+                                year: 1979,
+                                month: 5,
+                                day: 27,
+                            },
+                        ),
+                        time: Some(
+                            Time {
+                                hour: 7,
+                                minute: 32,
+                                second: 0,
+                                nanosecond: 0,
+                            },
+                        ),
+                        offset: Some(
+                            Z,
+                        ),
+                    },
+                ),
+                "doggos": Array(
+                    [
+                        String(
+                            "are",
+                        ),
+                    ],
+                ),
+                "pack": Array(
+                    [
+                        Table(
+                            {
+                                "age": Integer(
+                                    3,
+                                ),
+                                "name": String(
+                                    "rex",
+                                ),
+                            },
+                        ),
+                        Table(
+                            {
+                                "age": Integer(
+                                    -1,
+                                ),
+                                "name": String(
+                                    "fido",
+                                ),
+                            },
+                        ),
+                    ],
+                ),
+                "the": Table(
+                    {
+                        "bernese": String(
+                            "mountain",
+                        ),
+                    },
+                ),
+            },
+        )
+        "#);
+
+        let deserr: TValue = deserr.into();
+        insta::assert_debug_snapshot!(deserr, @r#"
+        Table(
+            {
+                "The": String(
+                    "best",
+                ),
+                "born": Datetime(
+                    Datetime {
+                        date: Some(
+                            Date {
+                                year: 1979,
+                                month: 5,
+                                day: 27,
+                            },
+                        ),
+                        time: Some(
+                            Time {
+                                hour: 7,
+                                minute: 32,
+                                second: 0,
+                                nanosecond: 0,
+                            },
+                        ),
+                        offset: Some(
+                            Z,
+                        ),
+                    },
+                ),
+                "doggos": Array(
+                    [
+                        String(
+                            "are",
+                        ),
+                    ],
+                ),
+                "pack": Array(
+                    [
+                        Table(
+                            {
+                                "age": Integer(
+                                    3,
+                                ),
+                                "name": String(
+                                    "rex",
+                                ),
+                            },
+                        ),
+                        Table(
+                            {
+                                "age": Integer(
+                                    -1,
+                                ),
+                                "name": String(
+                                    "fido",
+                                ),
+                            },
+                        ),
+                    ],
+                ),
+                "the": Table(
+                    {
+                        "bernese": String(
+                            "mountain",
+                        ),
+                    },
+                ),
+            },
+        )
+        "#);
+
+        assert_eq!(value, deserr);
+    }
+
+    #[test]
+    fn datetime_is_a_string() {
+        #[derive(Debug, deserr::Deserr)]
+        struct Config {
+            #[allow(dead_code)]
+            born: String,
+        }
+
+        let value: TValue = toml::from_str("born = 1979-05-27T07:32:00Z").unwrap();
+        let config: Config =
+            deserr::deserialize::<_, _, crate::errors::QueryParamError>(value).unwrap();
+        insta::assert_debug_snapshot!(config, @r#"
+        Config {
+            born: "1979-05-27T07:32:00Z",
+        }
+        "#);
+    }
+}
@@ -0,0 +1,107 @@
+//! Implements [`Deserr`] for [`Uuid`].
+//!
+//! The UUIDs are parsed from strings, in the hyphenated form such as
+//! `67e55044-10b1-426f-9247-bb680e5fe0c8` or in the simple form such as
+//! `67e5504410b1426f9247bb680e5fe0c8`. The braced and URN forms are accepted as well.
+
+use std::convert::Infallible;
+
+use uuid::Uuid;
+
+use crate::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Value, ValueKind,
+    ValuePointerRef,
+};
+
+impl<E: DeserializeError> Deserr<E> for Uuid {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::String(s) => Uuid::parse_str(&s).map_err(|e| {
+                take_cf_content(E::error::<Infallible>(
+                    None,
+                    ErrorKind::Unexpected {
+                        msg: format!("{e}: `{s}`"),
+                    },
+                    location,
+                ))
+            }),
+            v => Err(take_cf_content(E::incorrect_value_kind_error(
+                None,
+                v,
+                &[ValueKind::String],
+                std::any::type_name::<Self>(),
+                location,
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde-json"))]
+mod test {
+    use super::*;
+    use crate::errors::JsonError;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Debug, crate::Deserr)]
+    struct Document {
+        id: Uuid,
+        #[deserr(default)]
+        parent: Option<Uuid>,
+        children: Vec<Uuid>,
+    }
+
+    #[test]
+    fn deserialize_uuids() {
+        let document = crate::deserialize::<Document, _, JsonError>(json!({
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "parent": "67E5504410B1426F9247BB680E5FE0C8",
+            "children": [],
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(document, @r###"
+        Document {
+            id: 67e55044-10b1-426f-9247-bb680e5fe0c8,
+            parent: Some(
+                67e55044-10b1-426f-9247-bb680e5fe0c8,
+            ),
+            children: [],
+        }
+        "###);
+
+        let document = crate::deserialize::<Document, _, JsonError>(json!({
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "parent": null,
+            "children": [],
+        }))
+        .unwrap();
+        insta::assert_debug_snapshot!(document.parent, @"None");
+    }
+
+    #[test]
+    fn invalid_uuids() {
+        let error = crate::deserialize::<Document, _, JsonError>(json!({
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "children": ["67e55044-10b1-426f-9247-bb680e5fe0c8", "67e55044-10b1-426f"],
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.children[1]`: invalid group count: expected 5, found 3: `67e55044-10b1-426f`");
+
+        let error = crate::deserialize::<Document, _, JsonError>(json!({
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0cz",
+            "children": [],
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value at `.id`: invalid character: found `z` at 35: `67e55044-10b1-426f-9247-bb680e5fe0cz`");
+
+        let error = crate::deserialize::<Document, _, JsonError>(json!({
+            "id": 42,
+            "children": [],
+        }))
+        .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid value type at `.id`: expected a string, but found a positive integer: `42`");
+    }
+}
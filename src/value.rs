@@ -1,4 +1,11 @@
-use std::{
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
     convert::Infallible,
     fmt::{Debug, Display},
 };
@@ -12,7 +19,7 @@ use std::{
 /// ```
 /// use deserr::ValuePointerRef;
 ///
-/// let pointer = ValuePointerRef::Origin;
+/// let pointer = ValuePointerRef::origin();
 /// let pointer = pointer.push_key("a");
 /// let pointer = pointer.push_index(2);
 /// // now `pointer` points to "a".2
@@ -21,8 +28,9 @@ use std::{
 /// A `ValuePointerRef` is an immutable data structure, so it is cheap to extend and to copy.
 /// However, if you want to store it inside an owned type, you may want to convert it to a
 /// [`ValuePointer`] instead using [`self.to_owned()`](ValuePointerRef::to_owned).
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub enum ValuePointerRef<'a> {
+    #[default]
     Origin,
     Key {
         key: &'a str,
@@ -34,13 +42,25 @@ pub enum ValuePointerRef<'a> {
     },
 }
 
-impl<'a> Default for ValuePointerRef<'a> {
-    fn default() -> Self {
-        Self::Origin
+impl<'a> ValuePointerRef<'a> {
+    /// Return the pointer to the whole value, where every traversal starts.
+    ///
+    /// It is the location given to [`Deserr::deserialize_from_value`](crate::Deserr::deserialize_from_value)
+    /// by [`deserialize`](crate::deserialize) and the other entry points of the crate, so it
+    /// can be used to call a `Deserr` implementation directly:
+    /// ```
+    /// use deserr::{errors::JsonError, Deserr, IntoValue, ValuePointerRef};
+    /// use serde_json::json;
+    ///
+    /// let value = json!([1, 2]).into_value();
+    /// let numbers: Vec<u8> =
+    ///     Deserr::<JsonError>::deserialize_from_value(value, ValuePointerRef::origin()).unwrap();
+    /// assert_eq!(numbers, [1, 2]);
+    /// ```
+    pub const fn origin() -> Self {
+        ValuePointerRef::Origin
     }
-}
 
-impl<'a> ValuePointerRef<'a> {
     /// Extend `self` such that it points to the next subvalue at the given `key`.
     #[must_use]
     pub fn push_key(&'a self, key: &'a str) -> Self {
@@ -55,10 +75,7 @@ impl<'a> ValuePointerRef<'a> {
 
     /// Return true if the pointer is at the origin.
     pub fn is_origin(&self) -> bool {
-        match self {
-            ValuePointerRef::Origin => true,
-            _ => false,
-        }
+        matches!(self, ValuePointerRef::Origin)
     }
 
     /// Return the last field encountered if there is one.
@@ -114,11 +131,46 @@ pub enum ValuePointerComponent {
 }
 
 /// The owned version of a [`ValuePointerRef`].
+///
+/// It is displayed as a path such as `.foo[3].bar`, or as an empty string if it
+/// points to the origin.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ValuePointer {
     pub path: Vec<ValuePointerComponent>,
 }
 
+impl ValuePointer {
+    /// Format `self` as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901), such as
+    /// `/items/3/name`, or as an empty string if it points to the origin.
+    ///
+    /// In the keys, `~` is escaped as `~0` and `/` as `~1`.
+    pub fn to_json_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for component in &self.path {
+            pointer.push('/');
+            match component {
+                ValuePointerComponent::Key(key) => {
+                    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"))
+                }
+                ValuePointerComponent::Index(index) => pointer.push_str(&index.to_string()),
+            }
+        }
+        pointer
+    }
+}
+
+impl Display for ValuePointer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for component in &self.path {
+            match component {
+                ValuePointerComponent::Key(key) => write!(f, ".{key}")?,
+                ValuePointerComponent::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Equivalent to [`Value`] but without the associated data.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ValueKind {
@@ -133,7 +185,7 @@ pub enum ValueKind {
 }
 
 impl Display for ValueKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ValueKind::Null => write!(f, "Null"),
             ValueKind::Boolean => write!(f, "Boolean"),
@@ -147,8 +199,70 @@ impl Display for ValueKind {
     }
 }
 
+impl ValueKind {
+    /// Return `true` if the kind is one of the numeric kinds: [`Integer`](ValueKind::Integer),
+    /// [`NegativeInteger`](ValueKind::NegativeInteger) or [`Float`](ValueKind::Float).
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            ValueKind::Integer | ValueKind::NegativeInteger | ValueKind::Float
+        )
+    }
+
+    /// Describe a group of value kinds in plain english, e.g. `number, string, or map`.
+    ///
+    /// The kinds are deduplicated and always listed in the same order. When all three numeric
+    /// kinds are present, they are collapsed into `number`, and [`Integer`](ValueKind::Integer)
+    /// with [`NegativeInteger`](ValueKind::NegativeInteger) are collapsed into `integer`.
+    pub fn describe_group(kinds: &[ValueKind]) -> String {
+        let has = |kind| kinds.contains(&kind);
+        let mut words = Vec::new();
+
+        if has(ValueKind::Null) {
+            words.push("null");
+        }
+        if has(ValueKind::Boolean) {
+            words.push("boolean");
+        }
+        match (
+            has(ValueKind::Integer),
+            has(ValueKind::NegativeInteger),
+            has(ValueKind::Float),
+        ) {
+            (true, true, true) => words.push("number"),
+            (integer, negative, float) => {
+                match (integer, negative) {
+                    (true, true) => words.push("integer"),
+                    (true, false) => words.push("positive integer"),
+                    (false, true) => words.push("negative integer"),
+                    (false, false) => (),
+                }
+                if float {
+                    words.push("float");
+                }
+            }
+        }
+        if has(ValueKind::String) {
+            words.push("string");
+        }
+        if has(ValueKind::Sequence) {
+            words.push("sequence");
+        }
+        if has(ValueKind::Map) {
+            words.push("map");
+        }
+
+        match words.as_slice() {
+            [] => String::new(),
+            [word] => word.to_string(),
+            [first, second] => format!("{first} or {second}"),
+            [rest @ .., last] => format!("{}, or {last}", rest.join(", ")),
+        }
+    }
+}
+
 impl Debug for ValueKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         Display::fmt(self, f)
     }
 }
@@ -159,11 +273,18 @@ impl Debug for ValueKind {
 /// It is an enum with a variant for each possible value kind. The content of the variants
 /// is either a simple value, such as `bool` or `String`, or an abstract [`Sequence`] or
 /// [`Map`], which are views into the rest of the serialized data.
+///
+/// The integers are never truncated: a number is an [`Integer`](Value::Integer) if it fits in
+/// a `u64`, or else a [`NegativeInteger`](Value::NegativeInteger) if it fits in an `i64`, or
+/// else a [`Float`](Value::Float). So the integers outside of `i64::MIN..=u64::MAX`, such
+/// as the big integers of CBOR, are only accepted by the types deserialized from floats.
 #[derive(Debug)]
 pub enum Value<V: IntoValue> {
     Null,
     Boolean(bool),
+    /// A positive integer, including `0`.
     Integer(u64),
+    /// A negative integer. A positive integer is never given as a `NegativeInteger`.
     NegativeInteger(i64),
     Float(f64),
     String(String),
@@ -187,6 +308,58 @@ impl<V: IntoValue> Value<V> {
 }
 
 /// A trait for a value that can be deserialized via [`Deserr`].
+///
+/// To deserialize your own tree type, you need to:
+/// - pick its [`Sequence`] and [`Map`] types. They are usually the types your tree already
+///   uses for its arrays and objects, but you can also convert them to a `Vec<Self>` and an
+///   [`EntriesMap<Self>`], which implement these traits for any `IntoValue`. An `EntriesMap`
+///   can be collected from the entries of any map, e.g. a `HashMap<String, Self>`.
+/// - return the [`ValueKind`] of a value without consuming it in [`kind`](IntoValue::kind)
+/// - convert a value to a [`Value`] in [`into_value`](IntoValue::into_value)
+///
+/// If you only need to deserialize some native Rust collections, such as a
+/// `HashMap<String, serde_json::Value>`, you can convert them to a
+/// [`BufferedValue`](crate::BufferedValue) instead. The collections don't implement
+/// `IntoValue` themselves, because the elements of a `Vec<V>` or the values of a
+/// `HashMap<String, V>` are `V`s, while the elements of an `IntoValue` must be of its own type.
+///
+/// ## Example
+/// ```
+/// use deserr::{deserialize, errors::JsonError, Deserr, EntriesMap, IntoValue, Value, ValueKind};
+///
+/// enum Tree {
+///     Leaf(String),
+///     Branches(Vec<(String, Tree)>),
+/// }
+///
+/// impl IntoValue for Tree {
+///     type Sequence = Vec<Tree>;
+///     type Map = EntriesMap<Tree>;
+///
+///     fn kind(&self) -> ValueKind {
+///         match self {
+///             Tree::Leaf(_) => ValueKind::String,
+///             Tree::Branches(_) => ValueKind::Map,
+///         }
+///     }
+///
+///     fn into_value(self) -> Value<Self> {
+///         match self {
+///             Tree::Leaf(s) => Value::String(s),
+///             Tree::Branches(branches) => Value::Map(EntriesMap(branches)),
+///         }
+///     }
+/// }
+///
+/// #[derive(Deserr, Debug, PartialEq)]
+/// struct Doggo {
+///     name: String,
+/// }
+///
+/// let tree = Tree::Branches(vec![(String::from("name"), Tree::Leaf(String::from("Kefir")))]);
+/// let doggo = deserialize::<Doggo, _, JsonError>(tree).unwrap();
+/// assert_eq!(doggo, Doggo { name: String::from("Kefir") });
+/// ```
 pub trait IntoValue: Sized {
     type Sequence: Sequence<Value = Self>;
     type Map: Map<Value = Self>;
@@ -206,9 +379,27 @@ pub trait Sequence {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Return the bounds on the number of elements of the sequence, with the same meaning
+    /// as [`Iterator::size_hint`]. It can be used to preallocate a collection before
+    /// deserializing the elements.
+    ///
+    /// The default implementation returns `(len, Some(len))`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
 }
 
 /// A keyed map of values conforming to [`IntoValue`].
+///
+/// The keys of a map are strings. The formats whose maps can have other keys, such as YAML,
+/// CBOR, MessagePack or RON, convert them the same way:
+/// - `null`, the booleans and the numbers are stringified, e.g. `1` becomes `"1"` and `true`
+///   becomes `"true"`
+/// - the keys that are sequences or maps can't be converted. They are returned by
+///   [`Map::unsupported_key`], and skipped by the iterator of the map. The derived
+///   implementations of `Deserr`, and the ones of the maps and the values of the formats,
+///   refuse such a map with an [`ErrorKind::Unexpected`](crate::ErrorKind::Unexpected) error.
 pub trait Map {
     type Value: IntoValue;
     type Iter: Iterator<Item = (String, Self::Value)>;
@@ -220,6 +411,92 @@ pub trait Map {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Return the kind of the first key of the map that can't be converted to a string, see
+    /// the [documentation of the trait](Map).
+    ///
+    /// The default implementation returns `None`, for the maps whose keys are strings.
+    fn unsupported_key(&self) -> Option<ValueKind> {
+        None
+    }
+}
+
+/// A [`Map`] made of a list of entries, which are kept in their original order.
+///
+/// It can be used as the map of an [`IntoValue`] type that doesn't have its own map type.
+/// It is a distinct type rather than an implementation of `Map` for `Vec<(String, V)>`, so
+/// that the methods of `Map` don't shadow the ones of the vectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntriesMap<V>(pub Vec<(String, V)>);
+
+impl<V: IntoValue> Map for EntriesMap<V> {
+    type Value = V;
+    type Iter = vec::IntoIter<(String, V)>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        let index = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(index).1)
+    }
+
+    fn into_iter(self) -> Self::Iter {
+        IntoIterator::into_iter(self.0)
+    }
+}
+
+impl<V> From<Vec<(String, V)>> for EntriesMap<V> {
+    fn from(entries: Vec<(String, V)>) -> Self {
+        EntriesMap(entries)
+    }
+}
+
+impl<V> FromIterator<(String, V)> for EntriesMap<V> {
+    fn from_iter<I: IntoIterator<Item = (String, V)>>(entries: I) -> Self {
+        EntriesMap(entries.into_iter().collect())
+    }
+}
+
+/// Convert the key of a map to a string, or return its kind if it is a sequence or a map,
+/// see [`Map`].
+#[cfg(any(
+    feature = "serde-yml",
+    feature = "cbor",
+    feature = "rmpv",
+    feature = "ron"
+))]
+pub(crate) fn stringify_key<V: IntoValue>(key: V) -> Result<String, ValueKind> {
+    match key.into_value() {
+        Value::Null => Ok(String::from("null")),
+        Value::Boolean(b) => Ok(b.to_string()),
+        Value::Integer(n) => Ok(n.to_string()),
+        Value::NegativeInteger(n) => Ok(n.to_string()),
+        Value::Float(n) => Ok(n.to_string()),
+        Value::String(s) => Ok(s),
+        key => Err(key.kind()),
+    }
+}
+
+/// Return `true` if the key of a map is converted to `key`, see [`Map`].
+#[cfg(any(feature = "cbor", feature = "rmpv", feature = "ron"))]
+pub(crate) fn key_matches<V: IntoValue + Clone>(map_key: &V, key: &str) -> bool {
+    stringify_key(map_key.clone()).is_ok_and(|map_key| map_key == key)
+}
+
+/// Return the kind of the first key that can't be converted to a string, see [`Map`].
+#[cfg(any(
+    feature = "serde-yml",
+    feature = "cbor",
+    feature = "rmpv",
+    feature = "ron"
+))]
+pub(crate) fn unsupported_key<'a, V: IntoValue + 'a>(
+    keys: impl Iterator<Item = &'a V>,
+) -> Option<ValueKind> {
+    keys.map(IntoValue::kind)
+        .find(|kind| matches!(kind, ValueKind::Sequence | ValueKind::Map))
 }
 
 impl IntoValue for Infallible {
@@ -237,7 +514,7 @@ impl IntoValue for Infallible {
 
 impl Sequence for Infallible {
     type Value = Self;
-    type Iter = std::iter::Empty<Infallible>;
+    type Iter = core::iter::Empty<Infallible>;
 
     fn len(&self) -> usize {
         unreachable!()
@@ -250,7 +527,7 @@ impl Sequence for Infallible {
 
 impl Map for Infallible {
     type Value = Self;
-    type Iter = std::iter::Empty<(String, Infallible)>;
+    type Iter = core::iter::Empty<(String, Infallible)>;
 
     fn len(&self) -> usize {
         unreachable!()
@@ -264,3 +541,122 @@ impl Map for Infallible {
         unreachable!()
     }
 }
+
+/// `ValueRef<'a, V>` is the borrowed equivalent of [`Value`].
+///
+/// The strings and the map keys are borrowed from the parsed serialization data
+/// instead of being moved out of it. It is created by [`IntoValueRef::into_value_ref`].
+#[derive(Debug)]
+pub enum ValueRef<'a, V: IntoValueRef<'a>> {
+    Null,
+    Boolean(bool),
+    Integer(u64),
+    NegativeInteger(i64),
+    Float(f64),
+    String(&'a str),
+    Sequence(V::SequenceRef),
+    Map(V::MapRef),
+}
+
+impl<'a, V: IntoValueRef<'a>> ValueRef<'a, V> {
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            ValueRef::Null => ValueKind::Null,
+            ValueRef::Boolean(_) => ValueKind::Boolean,
+            ValueRef::Integer(_) => ValueKind::Integer,
+            ValueRef::NegativeInteger(_) => ValueKind::NegativeInteger,
+            ValueRef::Float(_) => ValueKind::Float,
+            ValueRef::String(_) => ValueKind::String,
+            ValueRef::Sequence(_) => ValueKind::Sequence,
+            ValueRef::Map(_) => ValueKind::Map,
+        }
+    }
+
+    /// Convert `self` to the equivalent owned [`Value`], cloning the borrowed data.
+    pub fn into_owned(self) -> Value<V::Owned> {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Boolean(b) => Value::Boolean(b),
+            ValueRef::Integer(n) => Value::Integer(n),
+            ValueRef::NegativeInteger(n) => Value::NegativeInteger(n),
+            ValueRef::Float(n) => Value::Float(n),
+            ValueRef::String(s) => Value::String(s.to_owned()),
+            ValueRef::Sequence(seq) => Value::Sequence(seq.into_owned()),
+            ValueRef::Map(map) => Value::Map(map.into_owned()),
+        }
+    }
+}
+
+/// A trait for a borrowed value that can be deserialized via [`Deserr`](crate::Deserr)
+/// without being consumed.
+///
+/// The types that don't override [`Deserr::deserialize_from_value_ref`](crate::Deserr::deserialize_from_value_ref)
+/// are deserialized from the [`Owned`](IntoValueRef::Owned) version of the value instead.
+pub trait IntoValueRef<'a>: Sized {
+    /// The owned version of the value, used when a type can't be deserialized from a borrowed value.
+    type Owned: IntoValue;
+    type SequenceRef: SequenceRef<'a, Value = Self, Owned = <Self::Owned as IntoValue>::Sequence>;
+    type MapRef: MapRef<'a, Value = Self, Owned = <Self::Owned as IntoValue>::Map>;
+
+    fn kind(&self) -> ValueKind;
+    fn into_value_ref(self) -> ValueRef<'a, Self>;
+}
+
+/// A borrowed sequence of values conforming to [`IntoValueRef`].
+pub trait SequenceRef<'a> {
+    type Value: IntoValueRef<'a>;
+    type Owned;
+    type Iter: Iterator<Item = Self::Value>;
+
+    fn len(&self) -> usize;
+    fn into_iter(self) -> Self::Iter;
+    fn into_owned(self) -> Self::Owned;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the bounds on the number of elements of the sequence, see [`Sequence::size_hint`].
+    ///
+    /// The default implementation returns `(len, Some(len))`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+/// A borrowed keyed map of values conforming to [`IntoValueRef`].
+pub trait MapRef<'a> {
+    type Value: IntoValueRef<'a>;
+    type Owned;
+    type Iter: Iterator<Item = (&'a str, Self::Value)>;
+
+    fn len(&self) -> usize;
+    fn into_iter(self) -> Self::Iter;
+    fn into_owned(self) -> Self::Owned;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_pointer() {
+        let origin = ValuePointerRef::origin();
+        insta::assert_snapshot!(origin.to_owned().to_json_pointer(), @"");
+
+        let items = origin.push_key("items");
+        let item = items.push_index(3);
+        let name = item.push_key("name");
+        insta::assert_snapshot!(name.to_owned().to_json_pointer(), @"/items/3/name");
+
+        // `~` is escaped before `/`, so that `~1` in a key doesn't become a `/`
+        let weird = name.push_key("a/b~1c~");
+        insta::assert_snapshot!(weird.to_owned().to_json_pointer(), @"/items/3/name/a~1b~01c~0");
+        let empty = origin.push_key("");
+        insta::assert_snapshot!(empty.to_owned().to_json_pointer(), @"/");
+    }
+}
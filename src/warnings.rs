@@ -0,0 +1,124 @@
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+#[cfg(feature = "std")]
+use crate::{collector, DeserializeError, Deserr, IntoValue};
+use crate::{ValuePointer, ValuePointerRef};
+
+/// Something that was accepted during the deserialization, but that the caller may want
+/// to report, such as a deprecated field or an ignored key.
+///
+/// The warnings are collected by [`deserialize_with_warnings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The location of the value that caused the warning.
+    pub location: ValuePointer,
+    pub kind: WarningKind,
+}
+
+/// The kind of a [`Warning`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A field with the `deprecated` attribute was given, `msg` is the message of the
+    /// attribute if there is one, e.g. ``use `limit` instead``.
+    Deprecated { msg: Option<String> },
+    /// A key that doesn't match any field was ignored, because the struct doesn't use
+    /// the `deny_unknown_fields` attribute.
+    UnknownKeyIgnored,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.kind {
+            WarningKind::Deprecated { msg: None } => {
+                write!(f, "The field `{}` is deprecated", self.location)
+            }
+            WarningKind::Deprecated { msg: Some(msg) } => {
+                write!(f, "The field `{}` is deprecated: {msg}", self.location)
+            }
+            WarningKind::UnknownKeyIgnored => {
+                write!(f, "The unknown field `{}` was ignored", self.location)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// The warnings of the [`deserialize_with_warnings`] call in progress on this thread, if any.
+    static WARNINGS: RefCell<Option<Vec<Warning>>> = const { RefCell::new(None) };
+}
+
+/// Deserialize the given value like [`deserialize`](crate::deserialize), and collect the
+/// warnings reported along the way.
+///
+/// The warnings are returned even if the deserialization fails, in the order in which they
+/// were encountered. When the value is deserialized with the other functions, the warnings
+/// are simply dropped.
+///
+/// Two kinds of warnings are reported by the derived implementations:
+/// - the fields with the `deprecated` attribute that were given
+/// - the unknown keys that were ignored by the structs that don't use the
+///   `deny_unknown_fields` attribute, which helps to catch the typos in a configuration
+#[cfg(feature = "std")]
+pub fn deserialize_with_warnings<Ret, Val, E>(value: Val) -> (Result<Ret, E>, Vec<Warning>)
+where
+    Ret: Deserr<E>,
+    Val: IntoValue,
+    E: DeserializeError,
+{
+    collector::scope(&WARNINGS, true, || crate::deserialize(value))
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Report a warning to the [`deserialize_with_warnings`] call in progress, if any.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn warn(location: ValuePointerRef, kind: WarningKind) {
+    let warning = Warning {
+        location: location.to_owned(),
+        kind,
+    };
+    collector::push(&WARNINGS, warning);
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Return the number of warnings reported so far, to drop the ones reported by an
+/// untagged enum variant that couldn't be deserialized with [`rollback_warnings`].
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn warnings_checkpoint() -> usize {
+    collector::checkpoint(&WARNINGS)
+}
+
+/// Used by the derive proc macro. Do not use.
+///
+/// Drop the warnings reported since the given [`warnings_checkpoint`].
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn rollback_warnings(checkpoint: usize) {
+    collector::rollback(&WARNINGS, checkpoint)
+}
+
+// Without the `std` feature, there is no `deserialize_with_warnings` call to report the
+// warnings to, so they are always dropped.
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn warn(_location: ValuePointerRef, _kind: WarningKind) {}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn warnings_checkpoint() -> usize {
+    0
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+pub fn rollback_warnings(_checkpoint: usize) {}
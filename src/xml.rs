@@ -0,0 +1,347 @@
+//! Parse and deserialize XML documents with [`from_xml_str`].
+//!
+//! XML doesn't have the same data model as deserr, so the documents are converted with the
+//! following rules:
+//! - the root element is the deserialized value, its name is ignored
+//! - an element without attributes or child elements is a [`Value::String`](crate::Value::String) of its text, or
+//!   a [`Value::Null`](crate::Value::Null) if it has no text, e.g. `<name>Kefir</name>` or `<nickname/>`
+//! - the other elements are [`Value::Map`](crate::Value::Map)s, in which:
+//!   - each attribute is an entry whose key is the name of the attribute prefixed by `@`,
+//!     e.g. `@id` for `id="2"`
+//!   - each child element is an entry whose key is the name of the element, and the child
+//!     elements that share a name are gathered in a [`Value::Sequence`](crate::Value::Sequence), in their order
+//!   - the text, if there is any, is the `#text` entry
+//! - the text is trimmed, so the whitespace used to indent the document is ignored, and the
+//!   texts separated by child elements are joined with a space. The entities, such as `&amp;`,
+//!   are resolved and the `CDATA` sections are part of the text.
+//! - the comments, the processing instructions and the declarations are ignored
+//!
+//! Note that XML doesn't distinguish the numbers and the booleans from the strings, so
+//! they are always seen as strings. The types of the [`query_params`](crate::query_params)
+//! module parse them instead. Similarly, a child element that isn't repeated is never seen
+//! as a sequence, so a `Vec` field must be given at least two elements.
+//!
+//! ## Example
+//! ```
+//! use deserr::{errors::JsonError, from_xml_str, query_params::NumFromStr, Deserr};
+//!
+//! #[derive(Deserr, Debug)]
+//! struct Kennel {
+//!     #[deserr(rename = "@name")]
+//!     name: String,
+//!     #[deserr(rename = "doggo")]
+//!     doggos: Vec<Doggo>,
+//! }
+//!
+//! #[derive(Deserr, Debug)]
+//! struct Doggo {
+//!     name: String,
+//!     age: NumFromStr<u8>,
+//!     nickname: Option<String>,
+//! }
+//!
+//! let kennel = from_xml_str::<Kennel, JsonError>(r#"
+//!     <kennel name="The good boys">
+//!         <doggo><name>Kefir</name><age>2</age><nickname/></doggo>
+//!         <doggo><name>Echo</name><age>5</age><nickname>Ecco</nickname></doggo>
+//!     </kennel>
+//! "#)
+//! .unwrap();
+//! assert_eq!(kennel.name, "The good boys");
+//! assert_eq!(kennel.doggos[0].nickname, None);
+//! assert_eq!(*kennel.doggos[1].age, 5);
+//! ```
+
+use std::convert::Infallible;
+
+use quick_xml::{
+    escape::resolve_predefined_entity,
+    events::{BytesStart, Event},
+    Decoder, Reader,
+};
+
+use crate::{
+    take_cf_content, BufferedMap, BufferedSequence, BufferedValue, DeserializeError, Deserr,
+    ErrorKind, ValuePointerRef,
+};
+
+/// Parse the XML string `s` and deserialize it into `T`, see the
+/// [module documentation](self) for the conversion rules.
+///
+/// The XML syntax errors are returned through the same error type `E` as the deserialization
+/// errors, as an [`ErrorKind::Unexpected`] at the origin. Their message contains the byte offset
+/// at which the error happened.
+pub fn from_xml_str<T, E>(s: &str) -> Result<T, E>
+where
+    T: Deserr<E>,
+    E: DeserializeError,
+{
+    let mut reader = Reader::from_str(s);
+    match parse_document(&mut reader) {
+        Ok(value) => crate::deserialize(value),
+        Err((msg, position)) => Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!("{msg} (at byte {position})"),
+            },
+            ValuePointerRef::Origin,
+        ))),
+    }
+}
+
+/// An element whose end tag wasn't read yet.
+struct Element {
+    name: String,
+    /// The attributes, with their `@` prefix.
+    attributes: Vec<(String, BufferedValue)>,
+    /// The child elements, gathered by name in the order of their first occurrence.
+    children: Vec<(String, Vec<BufferedValue>)>,
+    /// The trimmed texts that are separated by child elements.
+    texts: Vec<String>,
+    /// The text read since the last child element.
+    text: String,
+}
+
+impl Element {
+    fn new(start: &BytesStart, decoder: Decoder) -> Result<Self, quick_xml::Error> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            let value = attribute.decode_and_unescape_value(decoder)?;
+            attributes.push((
+                format!("@{}", decoder.decode(attribute.key.0)?),
+                BufferedValue::String(value.into_owned()),
+            ));
+        }
+        Ok(Element {
+            name: decoder.decode(start.name().0)?.into_owned(),
+            attributes,
+            children: Vec::new(),
+            texts: Vec::new(),
+            text: String::new(),
+        })
+    }
+
+    /// Move the text read since the last child element to `texts`.
+    fn flush_text(&mut self) {
+        let text = self.text.trim();
+        if !text.is_empty() {
+            self.texts.push(text.to_owned());
+        }
+        self.text.clear();
+    }
+
+    fn push_child(&mut self, name: String, value: BufferedValue) {
+        match self.children.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, values)) => values.push(value),
+            None => self.children.push((name, vec![value])),
+        }
+    }
+
+    /// Convert the element to a value, see the [module documentation](self).
+    fn into_value(mut self) -> (String, BufferedValue) {
+        self.flush_text();
+        let text = self.texts.join(" ");
+        if self.attributes.is_empty() && self.children.is_empty() {
+            let value = match text.is_empty() {
+                true => BufferedValue::Null,
+                false => BufferedValue::String(text),
+            };
+            return (self.name, value);
+        }
+
+        let mut entries = self.attributes;
+        entries.extend(self.children.into_iter().map(|(name, mut values)| {
+            let value = match values.len() {
+                1 => values.remove(0),
+                _ => BufferedValue::Sequence(BufferedSequence(values)),
+            };
+            (name, value)
+        }));
+        if !text.is_empty() {
+            entries.push((String::from("#text"), BufferedValue::String(text)));
+        }
+        (self.name, BufferedValue::Map(BufferedMap(entries)))
+    }
+}
+
+/// Parse the root element of the document, or return an error message with its byte offset.
+fn parse_document(reader: &mut Reader<&[u8]>) -> Result<BufferedValue, (String, u64)> {
+    let mut open_elements: Vec<Element> = Vec::new();
+    let mut root = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| (e.to_string(), reader.error_position()))?;
+        if let (Event::Start(_) | Event::Empty(_), Some(parent)) =
+            (&event, open_elements.last_mut())
+        {
+            parent.flush_text();
+        }
+        let closed = match event {
+            Event::Start(start) => {
+                let element = Element::new(&start, reader.decoder())
+                    .map_err(|e| (e.to_string(), reader.buffer_position()))?;
+                open_elements.push(element);
+                None
+            }
+            Event::Empty(start) => {
+                let element = Element::new(&start, reader.decoder())
+                    .map_err(|e| (e.to_string(), reader.buffer_position()))?;
+                Some(element.into_value())
+            }
+            // The end tags are checked by the reader, so there is always an open element
+            Event::End(_) => open_elements.pop().map(Element::into_value),
+            Event::Text(text) => {
+                if let Some(element) = open_elements.last_mut() {
+                    let content = text
+                        .xml10_content()
+                        .map_err(|e| (e.to_string(), reader.buffer_position()))?;
+                    element.text.push_str(&content);
+                }
+                None
+            }
+            Event::CData(data) => {
+                if let Some(element) = open_elements.last_mut() {
+                    let content = data
+                        .xml10_content()
+                        .map_err(|e| (e.to_string(), reader.buffer_position()))?;
+                    element.text.push_str(&content);
+                }
+                None
+            }
+            Event::GeneralRef(reference) => {
+                let resolved = match reference
+                    .resolve_char_ref()
+                    .map_err(|e| (e.to_string(), reader.buffer_position()))?
+                {
+                    Some(c) => c.to_string(),
+                    None => {
+                        let name = reference
+                            .decode()
+                            .map_err(|e| (e.to_string(), reader.buffer_position()))?;
+                        match resolve_predefined_entity(&name) {
+                            Some(s) => s.to_owned(),
+                            None => {
+                                return Err((
+                                    format!("unknown entity `&{name};`"),
+                                    reader.buffer_position(),
+                                ))
+                            }
+                        }
+                    }
+                };
+                if let Some(element) = open_elements.last_mut() {
+                    element.text.push_str(&resolved);
+                }
+                None
+            }
+            Event::Eof => break,
+            Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => None,
+        };
+
+        if let Some((name, value)) = closed {
+            match open_elements.last_mut() {
+                Some(parent) => parent.push_child(name, value),
+                None if root.is_none() => root = Some(value),
+                None => {
+                    return Err((
+                        format!("the document has more than one root element, found `<{name}>`"),
+                        reader.buffer_position(),
+                    ))
+                }
+            }
+        }
+    }
+
+    if let Some(element) = open_elements.last() {
+        return Err((
+            format!("the element `<{}>` is never closed", element.name),
+            reader.buffer_position(),
+        ));
+    }
+    root.ok_or_else(|| {
+        (
+            String::from("the document has no root element"),
+            reader.buffer_position(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{errors::JsonError, IntoValue, Value};
+
+    fn parse(s: &str) -> BufferedValue {
+        parse_document(&mut Reader::from_str(s)).unwrap()
+    }
+
+    #[test]
+    fn mapping_rules() {
+        let value = parse(
+            r#"<?xml version="1.0"?>
+            <!-- a kennel -->
+            <kennel name="Good &amp; boys">
+                Some text
+                <doggo id="1"><name>Kefir</name></doggo>
+                <owner/>
+                <doggo id="2"><name><![CDATA[<Echo>]]></name></doggo>
+                &#65;
+            </kennel>"#,
+        );
+        assert_eq!(
+            value.prettyprint().to_string(),
+            r##"Map {
+  "@name": String "Good & boys",
+  "doggo": Sequence [
+    Map {
+      "@id": String "1",
+      "name": String "Kefir",
+    },
+    Map {
+      "@id": String "2",
+      "name": String "<Echo>",
+    },
+  ],
+  "owner": Null,
+  "#text": String "Some text A",
+}"##
+        );
+
+        assert!(
+            matches!(parse("<name>  Kefir </name>").into_value(), Value::String(s) if s == "Kefir")
+        );
+        assert!(matches!(parse("<name>  </name>").into_value(), Value::Null));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let error = from_xml_str::<String, JsonError>("<a><b></a>").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value: ill-formed document: expected `</b>`, but `</a>` was found (at byte 6)"
+        );
+        let error = from_xml_str::<String, JsonError>("<a/><b/>").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value: the document has more than one root element, found `<b>` (at byte 8)"
+        );
+        let error = from_xml_str::<String, JsonError>("<a>").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value: the element `<a>` is never closed (at byte 3)"
+        );
+        let error = from_xml_str::<String, JsonError>("<!-- -->").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value: the document has no root element (at byte 8)"
+        );
+        let error = from_xml_str::<String, JsonError>("<a>&doggo;</a>").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value: unknown entity `&doggo;` (at byte 10)"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use deserr::{deserialize_accumulate, deserialize_with_mode, errors::JsonError, Deserr, MergeMode};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Form {
+    name: String,
+    age: u8,
+    address: Address,
+    tags: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+struct Address {
+    street: String,
+    number: u32,
+}
+
+#[test]
+fn accumulate_every_error_in_document_order() {
+    let value = json!({
+        "address": { "number": -2, "street": true },
+        "name": 42,
+        "nmae": "doggo",
+        "tags": ["a", 1, "b", null],
+    });
+    let errors = deserialize_accumulate::<Form, _, JsonError>(value).unwrap_err();
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+    assert_debug_snapshot!(errors, @r###"
+    [
+        "Invalid value type at `.address.number`: expected a positive integer, but found a negative integer: `-2`",
+        "Invalid value type at `.address.street`: expected a string, but found a boolean: `true`",
+        "Invalid value type at `.name`: expected a string, but found a positive integer: `42`",
+        "Unknown field `nmae`: did you mean `name`? expected one of `name`, `age`, `address`, `tags`",
+        "Invalid value type at `.tags[1]`: expected a string, but found a positive integer: `1`",
+        "Invalid value type at `.tags[3]`: expected a string, but found null",
+        "Missing field `age`",
+    ]
+    "###);
+}
+
+#[test]
+fn accumulate_without_errors() {
+    let value = json!({
+        "name": "doggo",
+        "age": 4,
+        "address": { "street": "kefir", "number": 2 },
+        "tags": [],
+    });
+    let form = deserialize_accumulate::<Form, _, JsonError>(value).unwrap();
+
+    assert_snapshot!(form.name, @"doggo");
+}
+
+#[test]
+fn choose_the_merge_mode_at_the_call_site() {
+    let value = json!({
+        "address": { "number": -2, "street": true },
+        "name": 42,
+        "nmae": "doggo",
+        "tags": [],
+    });
+
+    let errors = deserialize_with_mode::<Form, _, JsonError>(value.clone(), MergeMode::FailFast)
+        .unwrap_err();
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_debug_snapshot!(errors, @r###"
+    [
+        "Invalid value type at `.address.number`: expected a positive integer, but found a negative integer: `-2`",
+    ]
+    "###);
+
+    let errors =
+        deserialize_with_mode::<Form, _, JsonError>(value, MergeMode::Accumulate).unwrap_err();
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_debug_snapshot!(errors, @r###"
+    [
+        "Invalid value type at `.address.number`: expected a positive integer, but found a negative integer: `-2`",
+        "Invalid value type at `.address.street`: expected a string, but found a boolean: `true`",
+        "Invalid value type at `.name`: expected a string, but found a positive integer: `42`",
+        "Unknown field `nmae`: did you mean `name`? expected one of `name`, `age`, `address`, `tags`",
+        "Missing field `age`",
+    ]
+    "###);
+}
+
+#[test]
+fn accumulate_every_unknown_field() {
+    let value = json!({
+        "address": { "number": 2, "street": "kefir" },
+        "age": 4,
+        "agge": 4,
+        "name": "doggo",
+        "nmae": "doggo",
+        "tags": [],
+    });
+
+    let errors = deserialize_with_mode::<Form, _, JsonError>(value.clone(), MergeMode::FailFast)
+        .unwrap_err();
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_debug_snapshot!(errors, @r###"
+    [
+        "Unknown field `agge`: did you mean `age`? expected one of `name`, `age`, `address`, `tags`",
+    ]
+    "###);
+
+    let errors =
+        deserialize_with_mode::<Form, _, JsonError>(value, MergeMode::Accumulate).unwrap_err();
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_debug_snapshot!(errors, @r###"
+    [
+        "Unknown field `agge`: did you mean `age`? expected one of `name`, `age`, `address`, `tags`",
+        "Unknown field `nmae`: did you mean `name`? expected one of `name`, `age`, `address`, `tags`",
+    ]
+    "###);
+}
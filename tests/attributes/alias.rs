@@ -0,0 +1,150 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn alias() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields, rename_all = camelCase)]
+    struct Struct {
+        query: String,
+        #[deserr(alias = "max_results", alias = "maxResults")]
+        limit: usize,
+    }
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "query": "doggo", "limit": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        query: "doggo",
+        limit: 2,
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "query": "doggo", "max_results": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        query: "doggo",
+        limit: 2,
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "query": "doggo", "maxResults": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        query: "doggo",
+        limit: 2,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "query": "doggo", "limit": 2, "max_results": 3 }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value: the field `limit` is given twice, as `limit` and as `max_results`");
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "query": "doggo" })).unwrap_err();
+    assert_snapshot!(data, @"Missing field `limit`");
+}
+
+#[test]
+fn alias_all() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields, alias_all = "kebab-case")]
+    struct Struct {
+        page_size: usize,
+        #[deserr(alias = "sortBy")]
+        sort_by: String,
+        #[deserr(rename = "q")]
+        search_query: String,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "page_size": 2, "sort_by": "age", "q": "doggo" }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        page_size: 2,
+        sort_by: "age",
+        search_query: "doggo",
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "page-size": 2, "sortBy": "age", "q": "doggo" }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        page_size: 2,
+        sort_by: "age",
+        search_query: "doggo",
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "page-size": 2, "sort-by": "age", "q": "doggo" }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        page_size: 2,
+        sort_by: "age",
+        search_query: "doggo",
+    }
+    "###);
+
+    // the renamed fields don't get an alias
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "page_size": 2, "sort_by": "age", "search-query": "doggo" }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Unknown field `search-query`: expected one of `page_size`, `sort_by`, `q`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "page-size": 3, "page_size": 2, "q": "doggo", "sort_by": "age" }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value: the field `page_size` is given twice, as `page-size` and as `page_size`");
+
+    // the fields of the variants get an alias as well
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type", alias_all = camelCase)]
+    enum Enum {
+        #[deserr(rename_all = UPPERCASE)]
+        Doggo {
+            page_size: usize,
+        },
+        Catto {
+            sort_by: String,
+        },
+    }
+
+    let data =
+        deserialize::<Enum, _, JsonError>(json!({ "type": "Doggo", "PAGE_SIZE": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Doggo {
+        page_size: 2,
+    }
+    "###);
+    let data =
+        deserialize::<Enum, _, JsonError>(json!({ "type": "Doggo", "pageSize": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Doggo {
+        page_size: 2,
+    }
+    "###);
+    let data =
+        deserialize::<Enum, _, JsonError>(json!({ "type": "Catto", "sortBy": "age" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Catto {
+        sort_by: "age",
+    }
+    "###);
+}
@@ -0,0 +1,72 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn case_insensitive_unit_enum() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = lowercase, case_insensitive)]
+    enum Order {
+        Asc,
+        Desc,
+    }
+
+    let data =
+        deserialize::<Vec<Order>, _, JsonError>(json!(["ASC", "asc", "Asc", "dEsC"])).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    [
+        Asc,
+        Asc,
+        Asc,
+        Desc,
+    ]
+    "###);
+
+    // the error lists the canonical spellings
+    let error = deserialize::<Order, _, JsonError>(json!("Ascending")).unwrap_err();
+    assert_snapshot!(error, @"Unknown value `Ascending`: expected one of `asc`, `desc`");
+}
+
+#[test]
+fn case_insensitive_tagged_enum() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type", case_insensitive)]
+    enum Shape {
+        Circle { radius: u32 },
+        Square { side: u32 },
+    }
+
+    let data =
+        deserialize::<Shape, _, JsonError>(json!({ "type": "CIRCLE", "radius": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Circle {
+        radius: 2,
+    }
+    "###);
+
+    let error =
+        deserialize::<Shape, _, JsonError>(json!({ "type": "triangle", "side": 2 })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: Incorrect tag value");
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "t", content = "c", case_insensitive)]
+    enum Action {
+        Jump(u32),
+        Stop,
+    }
+
+    let data =
+        deserialize::<Vec<Action>, _, JsonError>(json!([{ "t": "jump", "c": 3 }, { "t": "STOP" }]))
+            .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    [
+        Jump(
+            3,
+        ),
+        Stop,
+    ]
+    "###);
+}
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+fn sort(mut tags: Vec<String>) -> Vec<String> {
+    tags.sort();
+    tags
+}
+
+#[test]
+fn dedup() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(dedup)]
+        tags: Vec<String>,
+        #[deserr(default = vec![2, 2], dedup)]
+        ids: Vec<u8>,
+        #[deserr(dedup, map = sort)]
+        sorted_tags: Vec<String>,
+    }
+
+    // the first occurrence of each element is kept
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "tags": ["b", "a", "b", "c", "a"], "sorted_tags": ["b", "a", "b"] }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        tags: [
+            "b",
+            "a",
+            "c",
+        ],
+        ids: [
+            2,
+        ],
+        sorted_tags: [
+            "a",
+            "b",
+        ],
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "tags": [], "ids": [1, 2, 1], "sorted_tags": [] }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        tags: [],
+        ids: [
+            1,
+            2,
+        ],
+        sorted_tags: [],
+    }
+    "###);
+
+    // the errors are still located at the index of the element
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "tags": ["a", "a", 2], "sorted_tags": [] }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.tags[2]`: expected a string, but found a positive integer: `2`");
+}
+
+#[test]
+fn set_refuses_duplicates() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        tags: HashSet<String>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "tags": ["a"] })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        tags: {
+            "a",
+        },
+    }
+    "###);
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "tags": ["a", "b", "a"] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.tags[2]`: the sequence contains this element more than once");
+
+    let error = deserialize::<Struct, _, JsonError>(json!({ "tags": ["a", 2] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.tags[1]`: expected a string, but found a positive integer: `2`");
+}
@@ -1,4 +1,4 @@
-use deserr::{deserialize, Deserr, JsonError};
+use deserr::{deserialize, errors::JsonError, Deserr};
 use insta::{assert_debug_snapshot, assert_snapshot};
 use serde_json::json;
 
@@ -30,7 +30,7 @@ fn option_dont_use_default_by_default() {
 
     let data = deserialize::<Struct, _, JsonError>(json!({})).unwrap_err();
 
-    assert_snapshot!(data, @"Json deserialize error: missing field `doggo` at ``");
+    assert_snapshot!(data, @"Missing field `doggo`");
 }
 
 #[test]
@@ -106,3 +106,250 @@ fn default_with_a_parameter() {
     }
     "###);
 }
+
+#[test]
+fn default_is_only_evaluated_when_missing() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static EVALUATED: AtomicUsize = AtomicUsize::new(0);
+
+    fn level() -> String {
+        EVALUATED.fetch_add(1, Ordering::Relaxed);
+        String::from("info")
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(default = level())]
+        level: String,
+        #[deserr(default = 30)]
+        timeout: u32,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "level": "debug" })).unwrap();
+
+    assert_debug_snapshot!(data, @r#"
+    Struct {
+        level: "debug",
+        timeout: 30,
+    }
+    "#);
+    assert_eq!(EVALUATED.load(Ordering::Relaxed), 0);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({})).unwrap();
+
+    assert_debug_snapshot!(data, @r#"
+    Struct {
+        level: "info",
+        timeout: 30,
+    }
+    "#);
+    assert_eq!(EVALUATED.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn default_enum_field() {
+    #[allow(unused)]
+    #[derive(Debug, Default, Deserr)]
+    enum LogLevel {
+        Debug,
+        #[default]
+        Info,
+        Error,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(default)]
+        level: LogLevel,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        level: Info,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "level": "Error" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        level: Error,
+    }
+    "###);
+}
+
+#[test]
+fn default_container() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(default = LogLevel::Info)]
+    enum LogLevel {
+        Debug,
+        Info,
+        Error,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(default = Limits { max: 10 })]
+    struct Limits {
+        #[deserr(default = 10)]
+        max: usize,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        level: LogLevel,
+        #[deserr(default = LogLevel::Error)]
+        fallback: LogLevel,
+        limits: Limits,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        level: Info,
+        fallback: Error,
+        limits: Limits {
+            max: 10,
+        },
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "level": "Debug", "fallback": "Info", "limits": { "max": 2 } }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        level: Debug,
+        fallback: Info,
+        limits: Limits {
+            max: 2,
+        },
+    }
+    "###);
+
+    // the default value is only used when the field is missing
+    let error = deserialize::<Struct, _, JsonError>(json!({ "level": null })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.level`: expected a string, but found null");
+
+    // and it doesn't give a value to the missing fields of the struct
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(default = Range { start: 0, end: 10 })]
+    struct Range {
+        start: usize,
+        end: usize,
+    }
+    let error = deserialize::<Range, _, JsonError>(json!({ "start": 2 })).unwrap_err();
+    assert_snapshot!(error, @"Missing field `end`");
+}
+
+#[test]
+fn fill_from_default() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(fill_from_default, deny_unknown_fields)]
+    #[non_exhaustive]
+    pub struct Settings {
+        pub name: String,
+        pub retries: Option<u8>,
+        #[deserr(default = 5)]
+        pub timeout: u32,
+        #[deserr(skip)]
+        generation: u64,
+        cache_size: usize,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Settings {
+                name: String::from("doggo"),
+                retries: Some(3),
+                timeout: 30,
+                generation: 1,
+                cache_size: 512,
+            }
+        }
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        settings: Settings,
+    }
+
+    // the missing fields take their value in the default struct, unless they have their own default
+    let data = deserialize::<Settings, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Settings {
+        name: "doggo",
+        retries: Some(
+            3,
+        ),
+        timeout: 5,
+        generation: 1,
+        cache_size: 512,
+    }
+    "###);
+
+    let data =
+        deserialize::<Settings, _, JsonError>(json!({ "name": "kefir", "cache_size": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Settings {
+        name: "kefir",
+        retries: Some(
+            3,
+        ),
+        timeout: 5,
+        generation: 1,
+        cache_size: 2,
+    }
+    "###);
+
+    // the struct isn't the default value of the fields of its type
+    let error = deserialize::<Struct, _, JsonError>(json!({})).unwrap_err();
+    assert_snapshot!(error, @"Missing field `settings`");
+
+    // the skipped fields are never deserialized
+    let error = deserialize::<Settings, _, JsonError>(json!({ "generation": 2 })).unwrap_err();
+    assert_snapshot!(error, @"Unknown field `generation`: expected one of `name`, `retries`, `timeout`, `cache_size`");
+}
+
+#[test]
+fn fill_from_default_expr() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(fill_from_default = Range::new(10))]
+    struct Range {
+        start: usize,
+        end: usize,
+    }
+
+    impl Range {
+        fn new(len: usize) -> Self {
+            Range { start: 0, end: len }
+        }
+    }
+
+    let data = deserialize::<Range, _, JsonError>(json!({ "start": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Range {
+        start: 2,
+        end: 10,
+    }
+    "###);
+
+    let data = deserialize::<Range, _, JsonError>(json!({ "start": 2, "end": 4 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Range {
+        start: 2,
+        end: 4,
+    }
+    "###);
+}
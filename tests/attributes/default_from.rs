@@ -0,0 +1,111 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn default_from() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct User {
+        #[deserr(map = |s: String| s.to_lowercase())]
+        username: String,
+        #[deserr(default_from = username)]
+        display_name: String,
+        #[deserr(default_from = display_name)]
+        handle: String,
+    }
+
+    let data = deserialize::<User, _, JsonError>(json!({ "username": "Kefir" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    User {
+        username: "kefir",
+        display_name: "kefir",
+        handle: "kefir",
+    }
+    "###);
+
+    let data = deserialize::<User, _, JsonError>(json!({
+        "display_name": "Kefir the doggo",
+        "username": "Kefir",
+    }))
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    User {
+        username: "kefir",
+        display_name: "Kefir the doggo",
+        handle: "Kefir the doggo",
+    }
+    "###);
+
+    let data = deserialize::<User, _, JsonError>(json!({
+        "display_name": "Kefir the doggo",
+        "handle": "Kefir",
+        "username": "Kefir",
+    }))
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    User {
+        username: "kefir",
+        display_name: "Kefir the doggo",
+        handle: "Kefir",
+    }
+    "###);
+
+    // the display name doesn't take a value from a username in error
+    let error = deserialize::<User, _, JsonError>(json!({})).unwrap_err();
+    assert_snapshot!(error, @"Missing field `username`");
+    let error = deserialize::<User, _, JsonError>(json!({ "username": 2 })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.username`: expected a string, but found a positive integer: `2`");
+}
+
+#[test]
+fn default_from_default_field() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Search {
+        #[deserr(default = 20)]
+        limit: usize,
+        #[deserr(default_from = limit)]
+        page_size: usize,
+        #[deserr(skip, default_from = limit)]
+        cached: usize,
+    }
+
+    let data = deserialize::<Search, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        limit: 20,
+        page_size: 20,
+        cached: 20,
+    }
+    "###);
+    let data = deserialize::<Search, _, JsonError>(json!({ "limit": 5 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        limit: 5,
+        page_size: 5,
+        cached: 5,
+    }
+    "###);
+}
+
+#[test]
+fn default_from_converted_field() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Search {
+        #[deserr(from(u8) = usize::from)]
+        limit: usize,
+        #[deserr(default_from = limit)]
+        page_size: usize,
+    }
+
+    // the page size takes the converted limit
+    let data = deserialize::<Search, _, JsonError>(json!({ "limit": 5 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        limit: 5,
+        page_size: 5,
+    }
+    "###);
+}
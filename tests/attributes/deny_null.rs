@@ -0,0 +1,61 @@
+use deserr::{deserialize, deserialize_ref, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn deny_null() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Search {
+        #[deserr(deny_null)]
+        filter: Option<String>,
+        #[deserr(default = Some(20), deny_null)]
+        limit: Option<u8>,
+    }
+
+    let data = deserialize::<Search, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        filter: None,
+        limit: Some(
+            20,
+        ),
+    }
+    "###);
+
+    let data =
+        deserialize::<Search, _, JsonError>(json!({ "filter": "doggo", "limit": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        filter: Some(
+            "doggo",
+        ),
+        limit: Some(
+            2,
+        ),
+    }
+    "###);
+
+    // a key that is given must have a value
+    let error = deserialize::<Search, _, JsonError>(json!({ "filter": null })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.filter`: expected a string, but found null");
+    let error = deserialize::<Search, _, JsonError>(json!({ "limit": null })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.limit`: expected a positive integer, but found null");
+
+    // the borrowed values are handled the same way
+    let value = json!({ "filter": "doggo" });
+    let data = deserialize_ref::<Search, _, JsonError>(&value).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Search {
+        filter: Some(
+            "doggo",
+        ),
+        limit: Some(
+            20,
+        ),
+    }
+    "###);
+    let value = json!({ "filter": null });
+    let error = deserialize_ref::<Search, _, JsonError>(&value).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.filter`: expected a string, but found null");
+}
@@ -81,3 +81,38 @@ fn custom_deny_unknown_fields() {
 
     assert_snapshot!(data, @"Invalid value: The word is doggo, not the opposite");
 }
+
+#[test]
+fn custom_deny_unknown_fields_receives_the_accepted_fields() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields = list_allowed_fields)]
+    struct Struct {
+        word: String,
+        #[deserr(rename = "turboWord")]
+        turbo_word: String,
+    }
+
+    fn list_allowed_fields<E: DeserializeError>(
+        field: &str,
+        accepted: &[&str],
+        location: ValuePointerRef,
+    ) -> E {
+        take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!("`{field}` is not allowed, use {}", accepted.join(" or ")),
+            },
+            location,
+        ))
+    }
+
+    let value = json!({ "word": "doggo", "turboWord": "doggo", "turbo": "doggo" });
+
+    let data = deserialize::<Struct, _, JsonError>(value.clone()).unwrap_err();
+    assert_snapshot!(data, @"Invalid value: `turbo` is not allowed, use word or turboWord");
+
+    // the borrowed path gives the same arguments to the function
+    let data = deserr::deserialize_ref::<Struct, _, JsonError>(&value).unwrap_err();
+    assert_snapshot!(data, @"Invalid value: `turbo` is not allowed, use word or turboWord");
+}
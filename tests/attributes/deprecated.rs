@@ -0,0 +1,74 @@
+use deserr::{deserialize_with_warnings, errors::JsonError, Deserr, Warning};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn deprecated() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Search {
+        query: String,
+        #[deserr(default)]
+        limit: Option<usize>,
+        #[deserr(deprecated = "use `limit` instead", alias = "hitsPerPage")]
+        hits_per_page: Option<usize>,
+        #[deserr(default, deprecated)]
+        page: Vec<Page>,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Page {
+        #[deserr(default, deprecated)]
+        number: usize,
+    }
+
+    let (search, warnings) = deserialize_with_warnings::<Search, _, JsonError>(
+        json!({ "query": "doggo", "hits_per_page": 2 }),
+    );
+    assert!(search.is_ok());
+    assert_debug_snapshot!(warnings, @r###"
+    [
+        Warning {
+            location: ValuePointer {
+                path: [
+                    Key(
+                        "hits_per_page",
+                    ),
+                ],
+            },
+            kind: Deprecated {
+                msg: Some(
+                    "use `limit` instead",
+                ),
+            },
+        },
+    ]
+    "###);
+
+    let (_, warnings) = deserialize_with_warnings::<Search, _, JsonError>(json!({
+        "query": "doggo",
+        "hitsPerPage": 2,
+        "page": [{ "number": 1 }, {}, { "number": 3 }],
+    }));
+    let warnings = warnings.iter().map(Warning::to_string).collect::<Vec<_>>();
+    assert_debug_snapshot!(warnings, @r###"
+    [
+        "The field `.hitsPerPage` is deprecated: use `limit` instead",
+        "The field `.page` is deprecated",
+        "The field `.page[0].number` is deprecated",
+        "The field `.page[2].number` is deprecated",
+    ]
+    "###);
+
+    // the warnings are returned even if the deserialization fails
+    let (search, warnings) =
+        deserialize_with_warnings::<Search, _, JsonError>(json!({ "hits_per_page": 2 }));
+    assert_snapshot!(search.unwrap_err(), @"Missing field `query`");
+    assert_eq!(warnings.len(), 1);
+
+    // no warnings without the deprecated fields
+    let (_, warnings) =
+        deserialize_with_warnings::<Search, _, JsonError>(json!({ "query": "doggo", "limit": 2 }));
+    assert!(warnings.is_empty());
+}
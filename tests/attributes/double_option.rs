@@ -0,0 +1,86 @@
+use deserr::{deserialize, deserialize_ref, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn double_option() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Patch {
+        #[deserr(double_option)]
+        doggo: Option<Option<String>>,
+        #[deserr(double_option)]
+        age: Option<Option<u8>>,
+    }
+
+    let data = deserialize::<Patch, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Patch {
+        doggo: None,
+        age: None,
+    }
+    "###);
+
+    let data = deserialize::<Patch, _, JsonError>(json!({ "doggo": null, "age": 2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Patch {
+        doggo: Some(
+            None,
+        ),
+        age: Some(
+            Some(
+                2,
+            ),
+        ),
+    }
+    "###);
+
+    let error = deserialize::<Patch, _, JsonError>(json!({ "age": "two" })).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `.age`: expected a positive integer, but found a string: `"two"`"###);
+
+    // the borrowed values are handled the same way
+    let value = json!({ "doggo": "bork", "age": null });
+    let data = deserialize_ref::<Patch, _, JsonError>(&value).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Patch {
+        doggo: Some(
+            Some(
+                "bork",
+            ),
+        ),
+        age: Some(
+            None,
+        ),
+    }
+    "###);
+}
+
+#[test]
+fn double_option_with_default() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Patch {
+        #[deserr(default = Some(Some(2)), double_option)]
+        age: Option<Option<u8>>,
+    }
+
+    let data = deserialize::<Patch, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Patch {
+        age: Some(
+            Some(
+                2,
+            ),
+        ),
+    }
+    "###);
+
+    let data = deserialize::<Patch, _, JsonError>(json!({ "age": null })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Patch {
+        age: Some(
+            None,
+        ),
+    }
+    "###);
+}
@@ -0,0 +1,65 @@
+use deserr::{deserialize, deserialize_ref, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn empty_string_as_none() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(empty_string_as_none)]
+        doggo: Option<String>,
+        #[deserr(empty_string_as_none)]
+        age: Option<u8>,
+        catto: Option<String>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "", "age": "", "catto": "" }))
+        .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: None,
+        age: None,
+        catto: Some(
+            "",
+        ),
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "doggo": "bork", "age": 2, "catto": null }))
+            .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: Some(
+            "bork",
+        ),
+        age: Some(
+            2,
+        ),
+        catto: None,
+    }
+    "###);
+
+    // the borrowed values are handled the same way
+    let value = json!({ "doggo": "", "age": "", "catto": "miaou" });
+    let data = deserialize_ref::<Struct, _, JsonError>(&value).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: None,
+        age: None,
+        catto: Some(
+            "miaou",
+        ),
+    }
+    "###);
+
+    let value = json!({ "doggo": "bork", "age": "two", "catto": null });
+    let error = deserialize_ref::<Struct, _, JsonError>(&value).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `.age`: expected a positive integer, but found a string: `"two"`"###);
+
+    // the field is still mandatory
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "age": "", "catto": null })).unwrap_err();
+    assert_snapshot!(error, @"Missing field `doggo`");
+}
@@ -1,4 +1,9 @@
-use deserr::{deserialize, errors::JsonError, Deserr};
+use deserr::{
+    deserialize,
+    errors::{JsonError, QueryParamError},
+    query_params::NumFromStr,
+    Deserr,
+};
 use insta::{assert_debug_snapshot, assert_snapshot};
 use serde_json::json;
 
@@ -28,3 +33,35 @@ fn error_attribute() {
     let data = deserialize::<Struct, _, _>(json!({ "doggo": "bork" })).unwrap_err();
     assert_snapshot!(data, @"Missing field `catto`");
 }
+
+#[test]
+fn generic_over_the_error_type() {
+    // without the `error` attribute, the struct can be deserialized with any error type
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields)]
+    struct Search {
+        query: String,
+        limit: NumFromStr<usize>,
+    }
+
+    // from a JSON payload
+    let data =
+        deserialize::<Search, _, JsonError>(json!({ "query": "doggo", "limit": 5 })).unwrap();
+    assert_eq!(*data.limit, 5);
+    let error = deserialize::<Search, _, JsonError>(json!({ "query": "doggo", "limit": "-5" }))
+        .unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.limit`: could not parse `-5` as a `usize`: invalid digit found in string");
+
+    // and from query parameters, where every value is a string
+    let data = deserialize::<Search, _, QueryParamError>(json!({ "query": "doggo", "limit": "5" }))
+        .unwrap();
+    assert_eq!(*data.limit, 5);
+    let error =
+        deserialize::<Search, _, QueryParamError>(json!({ "query": "doggo", "limit": "-5" }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Invalid value in parameter `limit`: could not parse `-5` as a `usize`: invalid digit found in string");
+    let error = deserialize::<Search, _, QueryParamError>(json!({ "q": "doggo", "limit": "5" }))
+        .unwrap_err();
+    assert_snapshot!(error, @"Unknown parameter `q`: expected one of `query`, `limit`");
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use deserr::{
+    deserialize, deserialize_ref, errors::JsonError, DeserializeError, Deserr, IntoValue, Map,
+    Value, ValuePointerRef,
+};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+struct CommonOptions {
+    verbose: bool,
+    #[deserr(default = 30)]
+    timeout: u32,
+}
+
+#[test]
+fn flatten() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Config {
+        name: String,
+        #[deserr(flatten)]
+        common: CommonOptions,
+    }
+
+    let value = json!({ "name": "doggo", "verbose": true, "bork": 2 });
+    let data = deserialize::<Config, _, JsonError>(value.clone()).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        name: "doggo",
+        common: CommonOptions {
+            verbose: true,
+            timeout: 30,
+        },
+    }
+    "###);
+
+    let data = deserialize_ref::<Config, _, JsonError>(&value).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        name: "doggo",
+        common: CommonOptions {
+            verbose: true,
+            timeout: 30,
+        },
+    }
+    "###);
+
+    let data = deserialize::<Config, _, JsonError>(json!({ "name": "doggo" })).unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `verbose`");
+
+    let data = deserialize::<Config, _, JsonError>(json!({ "name": "doggo", "verbose": [true] }))
+        .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value type at `.verbose`: expected a boolean, but found an array: `[true]`");
+}
+
+#[test]
+fn flatten_and_deny_unknown_fields() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields)]
+    struct StrictOptions {
+        verbose: bool,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Config {
+        name: String,
+        #[deserr(flatten)]
+        options: StrictOptions,
+    }
+
+    // the keys of the outer struct are never given to the flattened struct
+    let data =
+        deserialize::<Config, _, JsonError>(json!({ "name": "doggo", "verbose": true })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        name: "doggo",
+        options: StrictOptions {
+            verbose: true,
+        },
+    }
+    "###);
+
+    // the keys that nobody claimed are denied by the flattened struct
+    let data = deserialize::<Config, _, JsonError>(
+        json!({ "name": "doggo", "verbose": true, "verbos": false }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Unknown field `verbos`: did you mean `verbose`? expected one of `verbose`");
+}
+
+#[test]
+fn nested_flatten() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Middle {
+        level: u8,
+        #[deserr(flatten)]
+        common: CommonOptions,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Config {
+        name: String,
+        #[deserr(flatten)]
+        middle: Middle,
+    }
+
+    let data = deserialize::<Config, _, JsonError>(
+        json!({ "name": "doggo", "level": 2, "verbose": false, "timeout": 10 }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        name: "doggo",
+        middle: Middle {
+            level: 2,
+            common: CommonOptions {
+                verbose: false,
+                timeout: 10,
+            },
+        },
+    }
+    "###);
+}
+
+#[test]
+fn flatten_additional_properties() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = camelCase)]
+    struct Schema {
+        #[deserr(rename = "type")]
+        kind: String,
+        #[deserr(default)]
+        max_length: Option<usize>,
+        #[deserr(flatten)]
+        additional_properties: HashMap<String, serde_json::Value>,
+    }
+
+    let data = deserialize::<Schema, _, JsonError>(json!({
+        "type": "string",
+        "maxLength": 12,
+        "kind": "doggo",
+        "max_length": [1, 2],
+    }))
+    .unwrap();
+
+    let mut additional_properties = data.additional_properties.into_iter().collect::<Vec<_>>();
+    additional_properties.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(data.kind, "string");
+    assert_eq!(data.max_length, Some(12));
+    assert_debug_snapshot!(additional_properties, @r###"
+    [
+        (
+            "kind",
+            String("doggo"),
+        ),
+        (
+            "max_length",
+            Array [
+                Number(1),
+                Number(2),
+            ],
+        ),
+    ]
+    "###);
+
+    let data = deserialize::<Schema, _, JsonError>(json!({ "type": "string" })).unwrap();
+    assert!(data.additional_properties.is_empty());
+}
+
+#[test]
+fn flatten_keeps_the_order_of_the_map() {
+    /// Records the keys in the order in which they are received
+    #[allow(unused)]
+    #[derive(Debug)]
+    struct Keys(Vec<String>);
+
+    impl<E: DeserializeError> Deserr<E> for Keys {
+        fn deserialize_from_value<V: IntoValue>(
+            value: Value<V>,
+            _location: ValuePointerRef,
+        ) -> Result<Self, E> {
+            match value {
+                Value::Map(map) => Ok(Keys(map.into_iter().map(|(key, _)| key).collect())),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        b: u8,
+        d: u8,
+        #[deserr(flatten)]
+        others: Keys,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "a": 0, "b": 1, "c": 2, "d": 3, "e": 4, "f": 5 }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data.others, @r###"
+    Keys(
+        [
+            "a",
+            "c",
+            "e",
+            "f",
+        ],
+    )
+    "###);
+}
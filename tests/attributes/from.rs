@@ -14,7 +14,7 @@ fn from_container_attribute() {
 
     impl From<String> for AsciiString {
         fn from(s: String) -> Self {
-            if s.chars().any(|c| !c.is_ascii()) {
+            if !s.is_ascii() {
                 Self::Invalid(s)
             } else {
                 Self::Valid(s)
@@ -77,7 +77,7 @@ fn from_field_attribute() {
 
     impl From<String> for AsciiString {
         fn from(s: String) -> Self {
-            if s.chars().any(|c| !c.is_ascii()) {
+            if !s.is_ascii() {
                 Self::Invalid(s)
             } else {
                 Self::Valid(s)
@@ -112,3 +112,33 @@ fn from_field_attribute() {
     }
     "###);
 }
+
+#[test]
+fn closures() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(from(&String) = |s| Name(s.trim().to_string()))]
+    struct Name(String);
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        name: Name,
+        #[deserr(from(Vec<String>) = |toys| toys.len())]
+        toys: usize,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "name": " kefir ", "toys": ["ball", "stick"] }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        name: Name(
+            "kefir",
+        ),
+        toys: 2,
+    }
+    "###);
+}
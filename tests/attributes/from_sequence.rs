@@ -0,0 +1,91 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn from_sequence() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(from_sequence)]
+    struct Point {
+        x: i32,
+        y: i32,
+        #[deserr(default)]
+        z: i32,
+        #[deserr(skip)]
+        label: Option<String>,
+    }
+
+    let data = deserialize::<Point, _, JsonError>(json!([1, -2, 3])).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Point {
+        x: 1,
+        y: -2,
+        z: 3,
+        label: None,
+    }
+    "###);
+
+    // the missing trailing elements use the default value of their field
+    let data = deserialize::<Point, _, JsonError>(json!([1, -2])).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Point {
+        x: 1,
+        y: -2,
+        z: 0,
+        label: None,
+    }
+    "###);
+
+    let error = deserialize::<Point, _, JsonError>(json!([1])).unwrap_err();
+    assert_snapshot!(error, @"Missing field `y`");
+
+    let error = deserialize::<Point, _, JsonError>(json!([1, 2, 3, 4])).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: expected a sequence of at most 3 elements but instead found a sequence of 4 elements");
+
+    let error = deserialize::<Point, _, JsonError>(json!([1, "2"])).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `[1]`: expected an integer, but found a string: `"2"`"###);
+
+    let error = deserialize::<Point, _, JsonError>(json!({ "x": 1, "y": 2 })).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type: expected an array, but found an object: `{"x":1,"y":2}`"###);
+}
+
+#[test]
+fn from_sequence_nested() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(from_sequence)]
+    struct Range {
+        #[deserr(range = ..=100)]
+        start: u32,
+        #[deserr(range = ..=100)]
+        end: u32,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Query {
+        ranges: Vec<Range>,
+    }
+
+    let data =
+        deserialize::<Query, _, JsonError>(json!({ "ranges": [[0, 10], [20, 30]] })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Query {
+        ranges: [
+            Range {
+                start: 0,
+                end: 10,
+            },
+            Range {
+                start: 20,
+                end: 30,
+            },
+        ],
+    }
+    "###);
+
+    let error =
+        deserialize::<Query, _, JsonError>(json!({ "ranges": [[0, 10], [20, 300]] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.ranges[1][1]`: expected a value in the range `..=100`, but found `300`");
+}
@@ -1,5 +1,5 @@
 use deserr::{deserialize, errors::JsonError, Deserr};
-use insta::assert_debug_snapshot;
+use insta::{assert_debug_snapshot, assert_snapshot};
 use serde_json::json;
 
 #[test]
@@ -61,3 +61,42 @@ fn map_and_default() {
     }
     "###);
 }
+
+#[test]
+fn map_after_try_from() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(try_from(String) = trim -> EmptyString, map = |s: String| s.to_uppercase())]
+        doggo: String,
+    }
+
+    #[derive(Debug)]
+    struct EmptyString;
+
+    impl std::fmt::Display for EmptyString {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "the string is empty")
+        }
+    }
+
+    impl std::error::Error for EmptyString {}
+
+    fn trim(s: String) -> Result<String, EmptyString> {
+        match s.trim() {
+            "" => Err(EmptyString),
+            s => Ok(s.to_string()),
+        }
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "  kefir " })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: "KEFIR",
+    }
+    "###);
+
+    // the error of the fallible transformation is located at the field
+    let error = deserialize::<Struct, _, JsonError>(json!({ "doggo": "  " })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.doggo`: the string is empty");
+}
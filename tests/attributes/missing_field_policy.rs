@@ -0,0 +1,199 @@
+use std::convert::Infallible;
+
+use deserr::{
+    deserialize, deserialize_with_missing_field_policy, errors::JsonError, take_cf_content,
+    DeserializeError, Deserr, ErrorKind, MissingFieldPolicy, ValuePointer, ValuePointerRef,
+};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+fn describe(missing: Vec<ValuePointer>) -> Vec<String> {
+    missing.iter().map(ValuePointer::to_string).collect()
+}
+
+#[test]
+fn missing_field_policy() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(missing_field_policy)]
+    struct Article {
+        title: String,
+        #[deserr(default = 3)]
+        version: u32,
+        #[deserr(default)]
+        summary: Option<String>,
+        authors: Vec<Author>,
+        #[deserr(missing_field_error = missing_body)]
+        body: String,
+    }
+
+    fn missing_body<E: DeserializeError>(_field_name: &str, location: ValuePointerRef) -> E {
+        take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: String::from("an article needs a body"),
+            },
+            location,
+        ))
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(missing_field_policy)]
+    struct Author {
+        name: String,
+        age: u8,
+    }
+
+    let (article, missing) = deserialize_with_missing_field_policy::<Article, _, JsonError>(
+        json!({ "authors": [{ "name": "Kefir" }, { "age": 3 }] }),
+        MissingFieldPolicy::Default,
+    )
+    .unwrap();
+    assert_debug_snapshot!(article, @r###"
+    Article {
+        title: "",
+        version: 3,
+        summary: None,
+        authors: [
+            Author {
+                name: "Kefir",
+                age: 0,
+            },
+            Author {
+                name: "",
+                age: 3,
+            },
+        ],
+        body: "",
+    }
+    "###);
+    assert_debug_snapshot!(describe(missing), @r###"
+    [
+        ".authors[0].age",
+        ".authors[1].name",
+        ".title",
+        ".body",
+    ]
+    "###);
+
+    // the fields given with a wrong type are still errors
+    let error = deserialize_with_missing_field_policy::<Article, _, JsonError>(
+        json!({ "title": 2 }),
+        MissingFieldPolicy::Default,
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.title`: expected a string, but found a positive integer: `2`");
+
+    let error = deserialize_with_missing_field_policy::<Article, _, JsonError>(
+        json!({ "title": "Doggos", "authors": [] }),
+        MissingFieldPolicy::Error,
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value: an article needs a body");
+
+    // the policy only applies to the call that was given it
+    let error = deserialize::<Article, _, JsonError>(json!({ "title": "Doggos", "authors": [] }))
+        .unwrap_err();
+    assert_snapshot!(error, @"Invalid value: an article needs a body");
+
+    // and to the types with the attribute
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Blog {
+        name: String,
+        article: Article,
+    }
+    let error = deserialize_with_missing_field_policy::<Blog, _, JsonError>(
+        json!({ "article": {} }),
+        MissingFieldPolicy::Default,
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Missing field `name`");
+}
+
+#[test]
+fn missing_field_policy_untagged() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(untagged, missing_field_policy)]
+    enum Draft {
+        Complete { title: String, body: String },
+        Id { id: u32 },
+    }
+
+    // the fields defaulted by the variants that don't match aren't reported
+    let (draft, missing) = deserialize_with_missing_field_policy::<Draft, _, JsonError>(
+        json!({ "id": 2, "title": 3 }),
+        MissingFieldPolicy::Default,
+    )
+    .unwrap();
+    assert_debug_snapshot!(draft, @r###"
+    Id {
+        id: 2,
+    }
+    "###);
+    assert!(missing.is_empty());
+
+    let (draft, missing) = deserialize_with_missing_field_policy::<Draft, _, JsonError>(
+        json!({ "title": "Doggos" }),
+        MissingFieldPolicy::Default,
+    )
+    .unwrap();
+    assert_debug_snapshot!(draft, @r###"
+    Complete {
+        title: "Doggos",
+        body: "",
+    }
+    "###);
+    assert_debug_snapshot!(describe(missing), @r###"
+    [
+        ".body",
+    ]
+    "###);
+}
+
+#[test]
+fn missing_field_policy_is_restored_after_a_panic() {
+    use std::panic::catch_unwind;
+
+    use deserr::{IntoValue, Value};
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(missing_field_policy)]
+    struct Article {
+        title: String,
+        body: String,
+        #[deserr(default)]
+        panic: Panic,
+    }
+
+    /// Panics when it is deserialized from `true`.
+    #[derive(Debug, Default)]
+    struct Panic;
+
+    impl<E: DeserializeError> Deserr<E> for Panic {
+        fn deserialize_from_value<V: IntoValue>(
+            value: Value<V>,
+            location: ValuePointerRef,
+        ) -> Result<Self, E> {
+            match bool::deserialize_from_value(value, location)? {
+                true => panic!("the value was `true`"),
+                false => Ok(Panic),
+            }
+        }
+    }
+
+    let result = catch_unwind(|| {
+        deserialize_with_missing_field_policy::<Article, _, JsonError>(
+            json!({ "panic": true }),
+            MissingFieldPolicy::Default,
+        )
+    });
+    assert!(result.is_err());
+
+    // the policy doesn't leak to the next calls
+    let error = deserialize::<Article, _, JsonError>(json!({ "title": "Doggos" })).unwrap_err();
+    assert_snapshot!(error, @"Missing field `body`");
+}
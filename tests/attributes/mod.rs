@@ -1,11 +1,31 @@
+mod alias;
+mod case_insensitive;
+mod dedup;
+mod default;
+mod default_from;
+mod deny_null;
 mod deny_unknown_fields;
+mod deprecated;
+mod double_option;
+mod empty_string_as_none;
 mod error;
+mod flatten;
 mod from;
+mod from_sequence;
 mod map;
 mod missing_field_error;
+mod missing_field_policy;
+mod null_is_none;
+mod partial;
+mod range;
 mod rename_all;
+mod saturating;
 mod skip;
+mod string_constraints;
 mod tag;
+mod transparent;
 mod try_from;
+mod try_from_json;
+mod untagged;
 mod validate;
 mod where_predicate;
@@ -0,0 +1,92 @@
+use deserr::{deserialize, deserialize_ref, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn null_is_none() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(default)]
+        doggo: Option<String>,
+        #[deserr(default = 4, null_is_none)]
+        age: u8,
+        #[deserr(default)]
+        catto: u8,
+    }
+
+    // a missing field always uses its default value
+    let data = deserialize::<Struct, _, JsonError>(json!({})).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: None,
+        age: 4,
+        catto: 0,
+    }
+    "###);
+
+    // an explicit null is `None` for an `Option`, and the default value with `null_is_none`
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": null, "age": null })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: None,
+        age: 4,
+        catto: 0,
+    }
+    "###);
+
+    // but it's still refused by the other fields
+    let error = deserialize::<Struct, _, JsonError>(json!({ "catto": null })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.catto`: expected a positive integer, but found null");
+
+    // the borrowed values are handled the same way
+    let value = json!({ "doggo": "bork", "age": null });
+    let data = deserialize_ref::<Struct, _, JsonError>(&value).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: Some(
+            "bork",
+        ),
+        age: 4,
+        catto: 0,
+    }
+    "###);
+}
+
+#[test]
+fn null_is_none_without_default() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(null_is_none)]
+        doggo: String,
+    }
+
+    let error = deserialize::<Struct, _, JsonError>(json!({ "doggo": null })).unwrap_err();
+    assert_snapshot!(error, @"Missing field `doggo`");
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "bork" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: "bork",
+    }
+    "###);
+}
+
+#[test]
+fn null_is_none_from_sequence() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Point(#[deserr(default, null_is_none)] i32, i32);
+
+    let data = deserialize::<Point, _, JsonError>(json!([null, 2])).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Point(
+        0,
+        2,
+    )
+    "###);
+
+    let error = deserialize::<Point, _, JsonError>(json!([1, null])).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `[1]`: expected an integer, but found null");
+}
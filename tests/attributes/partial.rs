@@ -0,0 +1,95 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn partial() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(partial(derive(Debug)), rename_all = camelCase, deny_unknown_fields)]
+    struct Settings {
+        name: String,
+        #[deserr(default = 20)]
+        page_size: usize,
+        #[deserr(alias = "tags")]
+        labels: Vec<String>,
+        #[deserr(skip)]
+        revision: usize,
+    }
+
+    let mut settings = Settings {
+        name: String::from("doggos"),
+        page_size: 20,
+        labels: vec![String::from("bernese")],
+        revision: 3,
+    };
+
+    let patch = deserialize::<PartialSettings, _, JsonError>(json!({ "pageSize": 50, "tags": [] }))
+        .unwrap();
+    assert!(patch.name.is_none());
+    patch.merge_into(&mut settings);
+    assert_debug_snapshot!(settings, @r###"
+    Settings {
+        name: "doggos",
+        page_size: 50,
+        labels: [],
+        revision: 3,
+    }
+    "###);
+
+    // an empty update changes nothing, it doesn't reset the fields to their default value
+    let patch = deserialize::<PartialSettings, _, JsonError>(json!({})).unwrap();
+    patch.merge_into(&mut settings);
+    assert_eq!(settings.page_size, 50);
+
+    PartialSettings::default().merge_into(&mut settings);
+    assert_eq!(settings.name, "doggos");
+
+    // the errors are the same as the ones of the full struct
+    let error =
+        deserialize::<PartialSettings, _, JsonError>(json!({ "pageSize": "50", "page_size": 2 }))
+            .unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `.pageSize`: expected a positive integer, but found a string: `"50"`"###);
+    let error = deserialize::<PartialSettings, _, JsonError>(json!({ "labels": [], "tags": [] }))
+        .unwrap_err();
+    assert_snapshot!(error, @"Invalid value: the field `labels` is given twice, as `labels` and as `tags`");
+}
+
+#[test]
+fn partial_with_from_map_and_generics() {
+    #[derive(Debug, Deserr)]
+    #[deserr(partial(derive(Debug)))]
+    struct Search<T> {
+        query: T,
+        #[deserr(try_from(&String) = str::parse::<usize> -> std::num::ParseIntError)]
+        limit: usize,
+        #[deserr(map = add_one)]
+        offset: usize,
+    }
+
+    fn add_one(n: usize) -> usize {
+        n + 1
+    }
+
+    let mut search = Search {
+        query: String::from("doggo"),
+        limit: 10,
+        offset: 0,
+    };
+
+    let patch =
+        deserialize::<PartialSearch<String>, _, JsonError>(json!({ "limit": "3", "offset": 1 }))
+            .unwrap();
+    patch.merge_into(&mut search);
+    assert_debug_snapshot!(search, @r###"
+    Search {
+        query: "doggo",
+        limit: 3,
+        offset: 2,
+    }
+    "###);
+
+    let error =
+        deserialize::<PartialSearch<String>, _, JsonError>(json!({ "limit": "many" })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.limit`: invalid digit found in string");
+}
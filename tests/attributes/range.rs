@@ -0,0 +1,55 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn range() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(range = 1..=65535)]
+        port: u32,
+        #[deserr(range = 0.0..100.0)]
+        percentage: f64,
+        #[deserr(range = -10..)]
+        offset: i64,
+        #[deserr(default, range = ..=10)]
+        retries: u64,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": 65535, "percentage": 0.0, "offset": -10, "retries": 10 }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        port: 65535,
+        percentage: 0.0,
+        offset: -10,
+        retries: 10,
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "port": 0, "percentage": 0.0, "offset": 0 }))
+            .unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.port`: expected a value in the range `1..=65535`, but found `0`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": 80, "percentage": 100.0, "offset": 0 }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.percentage`: expected a value in the range `0.0..100.0`, but found `100`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": 80, "percentage": 10.0, "offset": -11 }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.offset`: expected a value in the range `-10..`, but found `-11`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": 80, "percentage": 10.0, "offset": 0, "retries": 11 }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.retries`: expected a value in the range `..=10`, but found `11`");
+}
@@ -96,3 +96,52 @@ fn rename_all_lowercase() {
 
     assert_snapshot!(data, @"Missing field `BORK`");
 }
+
+#[test]
+fn rename_all_other_casings() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = "kebab-case")]
+    struct Kebab {
+        multiple_words: String,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = "SCREAMING-KEBAB-CASE")]
+    struct ScreamingKebab {
+        multiple_words: String,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = SCREAMING_SNAKE_CASE)]
+    struct ScreamingSnake {
+        multiple_words: String,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = "PascalCase")]
+    struct Pascal {
+        multiple_words: String,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = "kebab-case")]
+    enum Enum {
+        MultipleWords,
+    }
+
+    let error = deserialize::<Kebab, _, JsonError>(json!({})).unwrap_err();
+    assert_snapshot!(error, @"Missing field `multiple-words`");
+    let error = deserialize::<ScreamingKebab, _, JsonError>(json!({})).unwrap_err();
+    assert_snapshot!(error, @"Missing field `MULTIPLE-WORDS`");
+    let error = deserialize::<ScreamingSnake, _, JsonError>(json!({})).unwrap_err();
+    assert_snapshot!(error, @"Missing field `MULTIPLE_WORDS`");
+    let error = deserialize::<Pascal, _, JsonError>(json!({})).unwrap_err();
+    assert_snapshot!(error, @"Missing field `MultipleWords`");
+    let error = deserialize::<Enum, _, JsonError>(json!("bork")).unwrap_err();
+    assert_snapshot!(error, @"Unknown value `bork`: expected one of `multiple-words`");
+}
@@ -0,0 +1,99 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn saturating() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(saturating)]
+        unsigned: u8,
+        #[deserr(saturating)]
+        signed: i16,
+        #[deserr(default, saturating)]
+        limit: u64,
+        // the value is clamped to the bounds of the type it is converted from
+        #[deserr(saturating, from(u16) = u32::from)]
+        mapped: u32,
+    }
+
+    // the values in range are kept
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "unsigned": 255, "signed": -32768, "mapped": 65535 }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        unsigned: 255,
+        signed: -32768,
+        limit: 0,
+        mapped: 65535,
+    }
+    "###);
+
+    // the values right past the bounds are clamped to them
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "unsigned": 256, "signed": -32769, "limit": 1, "mapped": 65536 }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        unsigned: 255,
+        signed: -32768,
+        limit: 1,
+        mapped: 65535,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "unsigned": -1, "signed": 32768, "limit": -1, "mapped": u64::MAX }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        unsigned: 0,
+        signed: 32767,
+        limit: 0,
+        mapped: 65535,
+    }
+    "###);
+
+    // the integers too large for a `u64` or an `i64` are floats, and are clamped too
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "unsigned": 1e20, "signed": -1e20, "limit": 1e20, "mapped": 1e20 }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        unsigned: 255,
+        signed: -32768,
+        limit: 18446744073709551615,
+        mapped: 65535,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "unsigned": 2.0, "signed": -2.0, "limit": 18446744073709551616.0, "mapped": 0.0 }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        unsigned: 2,
+        signed: -2,
+        limit: 18446744073709551615,
+        mapped: 0,
+    }
+    "###);
+
+    // a value that isn't an integer is still an error
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "unsigned": 2.5, "signed": 0, "mapped": 0 }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.unsigned`: expected an integer, but found a number: `2.5`");
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "unsigned": "256", "signed": 0, "mapped": 0 }))
+            .unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `.unsigned`: expected an integer, but found a string: `"256"`"###);
+}
@@ -1,4 +1,4 @@
-use deserr::{deserialize, errors::JsonError, Deserr};
+use deserr::{deserialize, errors::JsonError, Deserr, KnownFields};
 use insta::assert_debug_snapshot;
 use serde_json::json;
 
@@ -147,3 +147,41 @@ fn skip_and_default_and_deny_unknown_fields() {
     )
     "###);
 }
+
+#[test]
+fn skip_is_not_a_known_field() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields, known_fields)]
+    struct Doggo {
+        // assigned by the server, never read from the input
+        #[deserr(skip)]
+        id: u64,
+        #[deserr(skip, default = vec![String::from("ball")])]
+        toys: Vec<String>,
+        name: String,
+    }
+
+    assert_eq!(<Doggo as KnownFields>::known_fields(), ["name"]);
+
+    let data = deserialize::<Doggo, _, JsonError>(json!({ "name": "kefir" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Doggo {
+        id: 0,
+        toys: [
+            "ball",
+        ],
+        name: "kefir",
+    }
+    "###);
+
+    let data =
+        deserialize::<Doggo, _, JsonError>(json!({ "name": "kefir", "id": 12 })).unwrap_err();
+
+    assert_debug_snapshot!(data, @r###"
+    JsonError(
+        "Unknown field `id`: expected one of `name`",
+    )
+    "###);
+}
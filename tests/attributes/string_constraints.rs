@@ -0,0 +1,51 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn length() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(min_len = 1, max_len = 4)]
+        name: String,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "name": "kéfi" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        name: "kéfi",
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "name": "" })).unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.name`: expected at least 1 characters, but found 0");
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "name": "doggo" })).unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.name`: expected at most 4 characters, but found 5");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn pattern() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(pattern = "^[a-z0-9_]+$", max_len = 8)]
+        id: String,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "id": "doggo_42" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        id: "doggo_42",
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "id": "Doggo" })).unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.id`: expected a string matching the pattern `^[a-z0-9_]+$`, but found `Doggo`");
+
+    // The constraints are checked in the order `min_len`, `max_len`, `pattern`
+    let data = deserialize::<Struct, _, JsonError>(json!({ "id": "Doggo Doggo" })).unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.id`: expected at most 8 characters, but found 11");
+}
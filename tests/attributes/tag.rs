@@ -128,3 +128,262 @@ fn tagged_enum_plus_rename() {
     )
     "###);
 }
+
+#[test]
+fn adjacently_tagged_enum() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        either: Either,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type", content = "data")]
+    enum Either {
+        Left { doggo: String },
+        Right { doggo: bool, catto: String },
+        Neither,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "either": { "data": { "doggo": "bork" }, "type": "Left" } }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        either: Left {
+            doggo: "bork",
+        },
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "either": { "type": "Neither" } })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        either: Neither,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "either": { "type": "Neither", "data": null } }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        either: Neither,
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "either": { "type": "Neither", "data": { "doggo": "bork" } } }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @r###"Invalid value type at `.either.data`: expected null, but found an object: `{"doggo":"bork"}`"###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "either": { "data": { "doggo": "bork" } } }))
+            .unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `type` inside `.either`");
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "either": { "type": "Up" } })).unwrap_err();
+
+    assert_snapshot!(data, @"Unknown value `Up` at `.either.type`: expected one of `Left`, `Right`, `Neither`");
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "either": { "type": "Left" } })).unwrap_err();
+
+    assert_snapshot!(data, @"Missing field `data` inside `.either`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "either": { "type": "Right", "data": { "doggo": "bork" } } }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value type at `.either.data.doggo`: expected a boolean, but found a string: `\"bork\"`");
+}
+
+#[test]
+fn tagged_enum_with_newtype_variants() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields)]
+    struct Position {
+        x: i32,
+        y: i32,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type", deny_unknown_fields)]
+    enum Action {
+        Move { x: i32, y: i32 },
+        Teleport(Position),
+        Wait,
+    }
+
+    // the fields of a struct variant and the content of a newtype variant are read from the rest of the map
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Move", "x": 1, "y": -2 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Move {
+        x: 1,
+        y: -2,
+    }
+    "###);
+
+    let data = deserialize::<Action, _, JsonError>(json!({ "type": "Teleport", "x": 1, "y": -2 }))
+        .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Teleport(
+        Position {
+            x: 1,
+            y: -2,
+        },
+    )
+    "###);
+
+    let data = deserialize::<Action, _, JsonError>(json!({ "type": "Wait" })).unwrap();
+    assert_debug_snapshot!(data, @"Wait");
+
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Move", "x": 1, "y": -2, "z": 3 }))
+            .unwrap_err();
+    assert_snapshot!(data, @"Unknown field `z`: expected one of `x`, `y`");
+
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Teleport", "x": 1, "y": -2, "z": 3 }))
+            .unwrap_err();
+    assert_snapshot!(data, @"Unknown field `z`: expected one of `x`, `y`");
+
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Teleport", "x": "1", "y": -2 }))
+            .unwrap_err();
+    assert_snapshot!(data, @r###"Invalid value type at `.x`: expected an integer, but found a string: `"1"`"###);
+}
+
+#[test]
+fn adjacently_tagged_enum_with_newtype_variants() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type", content = "data", deny_unknown_fields)]
+    enum Action {
+        Move { x: i32, y: i32 },
+        Say(String),
+    }
+
+    // the fields of a struct variant and the content of a newtype variant are read from the content key
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Move", "data": { "x": 1, "y": -2 } }))
+            .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Move {
+        x: 1,
+        y: -2,
+    }
+    "###);
+
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Say", "data": "bork" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Say(
+        "bork",
+    )
+    "###);
+
+    let data = deserialize::<Action, _, JsonError>(
+        json!({ "type": "Move", "data": { "x": 1, "y": -2, "z": 3 } }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Unknown field `z` inside `.data`: expected one of `x`, `y`");
+
+    let data =
+        deserialize::<Action, _, JsonError>(json!({ "type": "Say", "data": 42 })).unwrap_err();
+    assert_snapshot!(data, @"Invalid value type at `.data`: expected a string, but found a positive integer: `42`");
+
+    let data = deserialize::<Action, _, JsonError>(json!({ "type": "Say" })).unwrap_err();
+    assert_snapshot!(data, @"Missing field `data`");
+
+    // the keys next to the tag and the content are unknown keys
+    let data = deserialize::<Action, _, JsonError>(
+        json!({ "bogus": 2, "data": { "x": 1, "y": -2 }, "type": "Move" }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Unknown field `bogus`: expected one of `type`, `data`");
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(tag = "type", content = "data")]
+    enum LaxAction {
+        Move { x: i32, y: i32 },
+        Wait,
+    }
+
+    let (data, warnings) = deserr::deserialize_with_warnings::<LaxAction, _, JsonError>(
+        json!({ "bogus": 2, "type": "Wait" }),
+    );
+    assert_debug_snapshot!((data.unwrap(), warnings), @r###"
+    (
+        Wait,
+        [
+            Warning {
+                location: ValuePointer {
+                    path: [
+                        Key(
+                            "bogus",
+                        ),
+                    ],
+                },
+                kind: UnknownKeyIgnored,
+            },
+        ],
+    )
+    "###);
+}
+
+#[test]
+fn unit_enum_from_string() {
+    // An enum with only unit variants doesn't need a tag
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Sort {
+        order: Order,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = lowercase)]
+    enum Order {
+        Asc,
+        #[deserr(rename = "descending")]
+        Desc,
+    }
+
+    let data = deserialize::<Sort, _, JsonError>(json!({ "order": "asc" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Sort {
+        order: Asc,
+    }
+    "###);
+
+    let data = deserialize::<Sort, _, JsonError>(json!({ "order": "descending" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Sort {
+        order: Desc,
+    }
+    "###);
+
+    let data = deserialize::<Sort, _, JsonError>(json!({ "order": "Asc" })).unwrap_err();
+    assert_snapshot!(data, @"Unknown value `Asc` at `.order`: expected one of `asc`, `descending`");
+
+    let data =
+        deserialize::<Sort, _, JsonError>(json!({ "order": { "type": "asc" } })).unwrap_err();
+    assert_snapshot!(data, @r###"Invalid value type at `.order`: expected a string, but found an object: `{"type":"asc"}`"###);
+}
@@ -0,0 +1,115 @@
+use deserr::{deserialize, errors::JsonError, Deserr, ValuePointerRef};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn transparent_newtypes() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(transparent)]
+    struct UserId(String);
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(transparent)]
+    struct Port {
+        value: u16,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(transparent)]
+    struct Ids<T>(Vec<T>);
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        owner: UserId,
+        port: Port,
+        friends: Ids<UserId>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "owner": "kefir", "port": 7700, "friends": ["echo", "intel"] }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        owner: UserId(
+            "kefir",
+        ),
+        port: Port {
+            value: 7700,
+        },
+        friends: Ids(
+            [
+                UserId(
+                    "echo",
+                ),
+                UserId(
+                    "intel",
+                ),
+            ],
+        ),
+    }
+    "###);
+
+    // the errors are located as if the inner types were deserialized in place
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "owner": "kefir", "port": 70000, "friends": ["echo"] }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.port`: value: `70000` is too large to be deserialized, maximum value authorized is `65535`");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "owner": "kefir", "port": 7700, "friends": ["echo", 2] }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value type at `.friends[1]`: expected a string, but found a positive integer: `2`");
+
+    let data = deserialize::<UserId, _, JsonError>(json!({ "0": "kefir" })).unwrap_err();
+    assert_snapshot!(data, @r###"Invalid value type: expected a string, but found an object: `{"0":"kefir"}`"###);
+
+    let data = deserr::deserialize_ref::<Struct, _, JsonError>(
+        &json!({ "owner": "kefir", "port": 7700, "friends": [null] }),
+    )
+    .unwrap_err();
+    assert_snapshot!(data, @"Invalid value type at `.friends[0]`: expected a string, but found null");
+}
+
+#[test]
+fn transparent_and_validate() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(transparent, validate = not_empty -> __Deserr_E)]
+    struct Name(String);
+
+    fn not_empty<E: deserr::DeserializeError>(
+        name: Name,
+        location: ValuePointerRef,
+    ) -> Result<Name, E> {
+        if name.0.is_empty() {
+            Err(deserr::take_cf_content(
+                E::error::<std::convert::Infallible>(
+                    None,
+                    deserr::ErrorKind::Unexpected {
+                        msg: String::from("the name can't be empty"),
+                    },
+                    location,
+                ),
+            ))
+        } else {
+            Ok(name)
+        }
+    }
+
+    let data = deserialize::<Name, _, JsonError>(json!("kefir")).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Name(
+        "kefir",
+    )
+    "###);
+
+    let data = deserialize::<Vec<Name>, _, JsonError>(json!(["kefir", ""])).unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `[1]`: the name can't be empty");
+}
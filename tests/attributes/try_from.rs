@@ -135,3 +135,144 @@ fn from_field_attribute() {
 
     assert_snapshot!(data, @"Invalid value at `.doggo`: Encountered invalid character: `👉`, only ascii characters are accepted");
 }
+
+#[test]
+fn closures() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(try_from(&String) = |s| s.trim().parse() -> std::num::ParseIntError)]
+    struct Port(u16);
+
+    impl FromStr for Port {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(Port)
+        }
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        port: Port,
+        #[deserr(try_from(String) = |s| s.trim().parse::<usize>() -> std::num::ParseIntError)]
+        workers: usize,
+        #[deserr(try_from(&String) = |s| match s.chars().find(|c| !c.is_ascii()) {
+            Some(c) => Err(AsciiStringError(c)),
+            None => Ok(s.to_uppercase()),
+        } -> AsciiStringError)]
+        name: String,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": " 7700 ", "workers": "4 ", "name": "kefir" }),
+    )
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        port: Port(
+            7700,
+        ),
+        workers: 4,
+        name: "KEFIR",
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": "7700", "workers": "four", "name": "kefir" }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.workers`: invalid digit found in string");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": "77000", "workers": "4", "name": "kefir" }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.port`: number too large to fit in target type");
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "port": "7700", "workers": "4", "name": "👉 👈" }),
+    )
+    .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.name`: Encountered invalid character: `👉`, only ascii characters are accepted");
+}
+
+#[test]
+fn try_from_error() {
+    // `JsonError` can't be merged with this error
+    struct NotAscii(char);
+
+    #[derive(Debug)]
+    struct AsciiString(String);
+
+    impl FromStr for AsciiString {
+        type Err = NotAscii;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if let Some(c) = s.chars().find(|c| !c.is_ascii()) {
+                Err(NotAscii(c))
+            } else {
+                Ok(Self(s.to_string()))
+            }
+        }
+    }
+
+    fn ascii_error<E: DeserializeError>(error: NotAscii, location: ValuePointerRef) -> E {
+        take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!("`{}` is not an ascii character", error.0),
+            },
+            location,
+        ))
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(try_from(&String) = FromStr::from_str -> NotAscii, try_from_error = ascii_error)]
+    struct Name(String);
+
+    impl FromStr for Name {
+        type Err = NotAscii;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            AsciiString::from_str(s).map(|s| Name(s.0))
+        }
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        #[deserr(try_from(&String) = FromStr::from_str -> NotAscii, try_from_error = ascii_error)]
+        doggo: AsciiString,
+        name: Name,
+    }
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "doggo": "BORK", "name": "kefir" })).unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: AsciiString(
+            "BORK",
+        ),
+        name: Name(
+            "kefir",
+        ),
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "👉 👈", "name": "kefir" }))
+        .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.doggo`: `👉` is not an ascii character");
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "doggo": "BORK", "name": "kéfir" }))
+        .unwrap_err();
+
+    assert_snapshot!(data, @"Invalid value at `.name`: `é` is not an ascii character");
+}
@@ -0,0 +1,82 @@
+use std::ops::ControlFlow;
+
+use deserr::{
+    errors::JsonError, DeserializeError, Deserr, ErrorKind, IntoValue, MergeWithError,
+    ValuePointerRef,
+};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn try_from_json() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(try_from_json)]
+    struct Struct<T> {
+        doggo: T,
+    }
+
+    let data: Struct<String> = json!({ "doggo": "bork" }).try_into().unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        doggo: "bork",
+    }
+    "###);
+
+    let error: JsonError = Struct::<String>::try_from(json!({ "doggo": 2 })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.doggo`: expected a string, but found a positive integer: `2`");
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(try_from_json, tag = "type")]
+    enum Enum {
+        Doggo { name: String },
+    }
+
+    let data = Enum::try_from(json!({ "type": "Doggo", "name": "Kefir" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Doggo {
+        name: "Kefir",
+    }
+    "###);
+}
+
+#[derive(Debug)]
+struct MyError(String);
+
+impl DeserializeError for MyError {
+    fn error<V: IntoValue>(
+        _self_: Option<Self>,
+        error: ErrorKind<V>,
+        _location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        let msg = match error {
+            ErrorKind::MissingField { field } => format!("missing `{field}`"),
+            _ => String::from("invalid value"),
+        };
+        ControlFlow::Break(MyError(msg))
+    }
+}
+
+impl MergeWithError<MyError> for MyError {
+    fn merge(
+        _self_: Option<Self>,
+        other: MyError,
+        _merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        ControlFlow::Break(other)
+    }
+}
+
+#[test]
+fn try_from_json_with_custom_error() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(try_from_json, error = MyError)]
+    struct Struct {
+        doggo: String,
+    }
+
+    let error: MyError = Struct::try_from(json!({})).unwrap_err();
+    assert_eq!(error.0, "missing `doggo`");
+}
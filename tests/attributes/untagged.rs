@@ -0,0 +1,98 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn untagged_enum() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        // The error of an untagged enum must implement `Display`
+        #[deserr(needs_predicate)]
+        shape: Shape,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(untagged)]
+    enum Shape {
+        Circle { radius: f64 },
+        Rectangle { width: f64, height: f64 },
+        Named(String),
+        Nothing,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "shape": { "radius": 2.0 } })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        shape: Circle {
+            radius: 2.0,
+        },
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "shape": { "width": 2.0, "height": 3.0 } }))
+            .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        shape: Rectangle {
+            width: 2.0,
+            height: 3.0,
+        },
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "shape": "triangle" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        shape: Named(
+            "triangle",
+        ),
+    }
+    "###);
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "shape": null })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        shape: Nothing,
+    }
+    "###);
+
+    let data =
+        deserialize::<Struct, _, JsonError>(json!({ "shape": { "width": 2.0 } })).unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.shape`: expected one of `Circle` (Missing field `radius` inside `.shape`), `Rectangle` (Missing field `height` inside `.shape`), `Named` (Invalid value type at `.shape`: expected a string, but found an object: `{\"width\":2.0}`) or `Nothing` (Invalid value type at `.shape`: expected null, but found an object: `{\"width\":2.0}`)");
+}
+
+#[test]
+fn untagged_enum_first_match_wins() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(untagged)]
+    enum Number {
+        Small(u8),
+        Big(u64),
+        Float(f64),
+    }
+
+    let data = deserialize::<Number, _, JsonError>(json!(12)).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Small(
+        12,
+    )
+    "###);
+
+    let data = deserialize::<Number, _, JsonError>(json!(1200)).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Big(
+        1200,
+    )
+    "###);
+
+    let data = deserialize::<Number, _, JsonError>(json!(-1)).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Float(
+        -1.0,
+    )
+    "###);
+}
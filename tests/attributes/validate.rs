@@ -49,3 +49,89 @@ fn validate() {
 
     assert_snapshot!(data, @"Invalid value: `end` (`2`) should be greater than `start` (`6`)");
 }
+
+#[test]
+fn validate_normalizes_the_struct() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(validate = normalize_user -> __Deserr_E)]
+    struct User {
+        email: String,
+        #[deserr(default)]
+        display_name: Option<String>,
+    }
+
+    fn normalize_user<E: DeserializeError>(
+        mut user: User,
+        _location: ValuePointerRef,
+    ) -> Result<User, E> {
+        user.email = user.email.to_lowercase();
+        if user.display_name.is_none() {
+            let name = user.email.split('@').next().unwrap_or_default();
+            user.display_name = Some(name.to_string());
+        }
+        Ok(user)
+    }
+
+    let data = deserialize::<User, _, JsonError>(json!({ "email": "Kefir@Doggo.com" })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    User {
+        email: "kefir@doggo.com",
+        display_name: Some(
+            "kefir",
+        ),
+    }
+    "###);
+
+    let data = deserialize::<User, _, JsonError>(
+        json!({ "email": "Kefir@Doggo.com", "display_name": "Kefirounet" }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    User {
+        email: "kefir@doggo.com",
+        display_name: Some(
+            "Kefirounet",
+        ),
+    }
+    "###);
+}
+
+#[test]
+fn validate_locates_the_error_at_a_field() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Schedule {
+        opening: Range,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(validate = validate_range -> __Deserr_E)]
+    struct Range {
+        start: usize,
+        end: usize,
+    }
+
+    fn validate_range<E: DeserializeError>(
+        range: Range,
+        location: ValuePointerRef,
+    ) -> Result<Range, E> {
+        if range.end < range.start {
+            Err(deserr::take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!("should be greater than `start` (`{}`)", range.start),
+                },
+                location.push_key("end"),
+            )))
+        } else {
+            Ok(range)
+        }
+    }
+
+    let data =
+        deserialize::<Schedule, _, JsonError>(json!({ "opening": { "start": 6, "end": 2 } }))
+            .unwrap_err();
+    assert_snapshot!(data, @"Invalid value at `.opening.end`: should be greater than `start` (`6`)");
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, HashMap};
+
+use deserr::{deserialize, deserialize_ref, errors::JsonError, Deserr};
+use insta::assert_debug_snapshot;
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Dog {
+    name: String,
+    age: u8,
+    good_boy: Option<bool>,
+    friends: Vec<Friend>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+struct Friend {
+    name: String,
+}
+
+#[test]
+fn deserialize_from_a_borrowed_value() {
+    let value =
+        json!({ "name": "Intel", "age": 4, "good_boy": null, "friends": [{ "name": "Max" }] });
+    let dog = deserialize_ref::<Dog, _, JsonError>(&value).unwrap();
+
+    assert_debug_snapshot!(dog, @r###"
+    Dog {
+        name: "Intel",
+        age: 4,
+        good_boy: None,
+        friends: [
+            Friend {
+                name: "Max",
+            },
+        ],
+    }
+    "###);
+}
+
+#[test]
+fn borrowed_and_owned_errors_are_the_same() {
+    let values = [
+        json!({ "name": "Intel", "age": 4, "good_boy": true, "friends": [], "fur": "long" }),
+        json!({ "name": "Intel", "age": 400, "friends": [] }),
+        json!({ "name": "Intel", "age": 4, "friends": [{ "name": 12 }] }),
+        json!({ "name": "Intel", "age": 4 }),
+        json!(["Intel"]),
+    ];
+
+    for value in values {
+        let borrowed = deserialize_ref::<Dog, _, JsonError>(&value).unwrap_err();
+        let owned = deserialize::<Dog, _, JsonError>(value).unwrap_err();
+        assert_eq!(borrowed.to_string(), owned.to_string());
+    }
+}
+
+#[test]
+fn deserialize_the_same_value_into_several_types() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Names {
+        name: String,
+        friends: Vec<Friend>,
+    }
+
+    let value =
+        json!({ "name": "Intel", "age": 4, "good_boy": true, "friends": [{ "name": "Max" }] });
+
+    let dog = deserialize_ref::<Dog, _, JsonError>(&value).unwrap();
+    let names = deserialize_ref::<Names, _, JsonError>(&value).unwrap();
+    let error = deserialize_ref::<Friend, _, JsonError>(&value["age"]).unwrap_err();
+
+    assert_debug_snapshot!((dog, names, error.to_string()), @r###"
+    (
+        Dog {
+            name: "Intel",
+            age: 4,
+            good_boy: Some(
+                true,
+            ),
+            friends: [
+                Friend {
+                    name: "Max",
+                },
+            ],
+        },
+        Names {
+            name: "Intel",
+            friends: [
+                Friend {
+                    name: "Max",
+                },
+            ],
+        },
+        "Invalid value type: expected an object, but found a positive integer: `4`",
+    )
+    "###);
+}
+
+#[test]
+fn deserialize_borrowed_maps() {
+    let value = json!({ "doggos": { "Intel": { "name": "Max" }, "Max": { "name": "Intel" } } });
+    let hash =
+        deserialize_ref::<HashMap<String, HashMap<String, HashMap<String, String>>>, _, JsonError>(
+            &value,
+        )
+        .unwrap();
+    let btree = deserialize_ref::<BTreeMap<String, BTreeMap<String, Friend>>, _, JsonError>(&value)
+        .unwrap();
+    assert_eq!(hash["doggos"]["Intel"]["name"], "Max");
+    assert_debug_snapshot!(btree, @r###"
+    {
+        "doggos": {
+            "Intel": Friend {
+                name: "Max",
+            },
+            "Max": Friend {
+                name: "Intel",
+            },
+        },
+    }
+    "###);
+
+    let value = json!({ "1": 1, "three": 3 });
+    let borrowed = deserialize_ref::<BTreeMap<u8, u8>, _, JsonError>(&value).unwrap_err();
+    let owned = deserialize::<BTreeMap<u8, u8>, _, JsonError>(value).unwrap_err();
+    assert_eq!(borrowed.to_string(), owned.to_string());
+    insta::assert_snapshot!(borrowed, @r###"Invalid value at `.three`: the key "three" could not be deserialized into the key type `u8`"###);
+}
@@ -18,6 +18,24 @@ fn deserialize_char() {
     }
     "###);
 
+    // a char is a single unicode scalar value, whatever its length in bytes
+    let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "c": "é" })).unwrap();
+    insta::assert_debug_snapshot!(ret, @r###"
+    Test {
+        c: 'é',
+    }
+    "###);
+
+    let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "c": "🐕" })).unwrap();
+    insta::assert_debug_snapshot!(ret, @r###"
+    Test {
+        c: '🐕',
+    }
+    "###);
+
+    let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "c": "🐕🐈" })).unwrap_err();
+    insta::assert_snapshot!(ret, @"Invalid value at `.c`: expected a string of one character, but found the following string of 2 characters: `🐕🐈`");
+
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "c": "jorts" })).unwrap_err();
     insta::assert_snapshot!(ret, @"Invalid value at `.c`: expected a string of one character, but found the following string of 5 characters: `jorts`");
 
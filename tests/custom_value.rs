@@ -0,0 +1,249 @@
+use deserr::{deserialize, errors::JsonError, Deserr, EntriesMap, IntoValue, Value, ValueKind};
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+/// A tree that only uses the `Sequence` and `Map` implementations provided by deserr.
+enum Tree {
+    Null,
+    Number(u64),
+    Text(String),
+    List(Vec<Tree>),
+    Entries(Vec<(String, Tree)>),
+}
+
+impl IntoValue for Tree {
+    type Sequence = Vec<Tree>;
+    type Map = EntriesMap<Tree>;
+
+    fn kind(&self) -> ValueKind {
+        match self {
+            Tree::Null => ValueKind::Null,
+            Tree::Number(_) => ValueKind::Integer,
+            Tree::Text(_) => ValueKind::String,
+            Tree::List(_) => ValueKind::Sequence,
+            Tree::Entries(_) => ValueKind::Map,
+        }
+    }
+
+    fn into_value(self) -> Value<Self> {
+        match self {
+            Tree::Null => Value::Null,
+            Tree::Number(n) => Value::Integer(n),
+            Tree::Text(s) => Value::String(s),
+            Tree::List(x) => Value::Sequence(x),
+            Tree::Entries(x) => Value::Map(EntriesMap(x)),
+        }
+    }
+}
+
+fn entries<const N: usize>(entries: [(&str, Tree); N]) -> Tree {
+    Tree::Entries(
+        entries
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+#[allow(unused)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Doggo {
+    name: String,
+    age: Option<u8>,
+    toys: Vec<String>,
+}
+
+#[test]
+fn deserialize_a_custom_value() {
+    let tree = entries([
+        ("name", Tree::Text(String::from("Kefir"))),
+        ("age", Tree::Null),
+        ("toys", Tree::List(vec![Tree::Text(String::from("ball"))])),
+        ("breed", Tree::Text(String::from("Bernese"))),
+    ]);
+    let error = deserialize::<Doggo, _, JsonError>(tree).unwrap_err();
+    assert_snapshot!(error, @"Unknown field `breed`: expected one of `name`, `age`, `toys`");
+
+    let tree = entries([
+        ("name", Tree::Text(String::from("Kefir"))),
+        ("age", Tree::Number(2)),
+        ("toys", Tree::List(vec![Tree::Text(String::from("ball"))])),
+    ]);
+    let doggo = deserialize::<Doggo, _, JsonError>(tree).unwrap();
+    assert_debug_snapshot!(doggo, @r###"
+    Doggo {
+        name: "Kefir",
+        age: Some(
+            2,
+        ),
+        toys: [
+            "ball",
+        ],
+    }
+    "###);
+
+    let tree = entries([
+        ("name", Tree::Text(String::from("Kefir"))),
+        ("toys", Tree::List(vec![Tree::Number(2)])),
+    ]);
+    let error = deserialize::<Doggo, _, JsonError>(tree).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.toys[0]`: expected a string, but found a positive integer: `2`");
+}
+
+#[test]
+fn deserialize_std_collections() {
+    use deserr::BufferedValue;
+    use serde_json::json;
+    use std::collections::{BTreeMap, HashMap};
+
+    let body = HashMap::from([
+        (String::from("name"), json!("Kefir")),
+        (String::from("age"), json!(2)),
+        (String::from("toys"), json!(["ball", "stick"])),
+    ]);
+    let doggo = deserialize::<Doggo, _, JsonError>(BufferedValue::from(body)).unwrap();
+    assert_debug_snapshot!(doggo, @r###"
+    Doggo {
+        name: "Kefir",
+        age: Some(
+            2,
+        ),
+        toys: [
+            "ball",
+            "stick",
+        ],
+    }
+    "###);
+
+    let body = BTreeMap::from([
+        (String::from("name"), json!("Kefir")),
+        (String::from("toys"), json!(["ball", 2])),
+    ]);
+    let error = deserialize::<Doggo, _, JsonError>(BufferedValue::from(body)).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.toys[1]`: expected a string, but found a positive integer: `2`");
+
+    let toys = vec![json!("ball"), json!("stick")];
+    let toys = deserialize::<Vec<String>, _, JsonError>(BufferedValue::from(toys)).unwrap();
+    assert_eq!(toys, ["ball", "stick"]);
+
+    // the values can be nested by buffering them first
+    let body = HashMap::from([
+        (
+            String::from("name"),
+            BufferedValue::String(String::from("Kefir")),
+        ),
+        (String::from("age"), BufferedValue::Null),
+        (
+            String::from("toys"),
+            BufferedValue::from(Vec::<Tree>::new()),
+        ),
+    ]);
+    let doggo = deserialize::<Doggo, _, JsonError>(BufferedValue::from(body)).unwrap();
+    assert_debug_snapshot!(doggo, @r###"
+    Doggo {
+        name: "Kefir",
+        age: None,
+        toys: [],
+    }
+    "###);
+}
+
+#[test]
+fn prettyprint_buffered_value() {
+    use deserr::BufferedValue;
+    use serde_json::json;
+
+    let body = json!({
+        "age": -2,
+        "good": true,
+        "name": "Ke\"fir",
+        "toys": [{ "kind": "ball", "tags": [] }, {}],
+    });
+    let body = BufferedValue::from_value(body.into_value());
+    assert_snapshot!(body.prettyprint(), @r###"
+    Map {
+      "age": NegativeInteger -2,
+      "good": Boolean true,
+      "name": String "Ke\"fir",
+      "toys": Sequence [
+        Map {
+          "kind": String "ball",
+          "tags": Sequence [],
+        },
+        Map {},
+      ],
+    }
+    "###);
+
+    let body = BufferedValue::from_value(json!(2.0).into_value());
+    assert_snapshot!(body.prettyprint(), @"Float 2.0");
+}
+
+#[test]
+fn merge_buffered_values() {
+    use deserr::{BufferedValue, SequenceMerge};
+    use serde_json::json;
+
+    #[derive(Debug, Deserr)]
+    #[allow(unused)]
+    struct Config {
+        name: String,
+        age: Option<u8>,
+        toys: Vec<String>,
+    }
+
+    let base = BufferedValue::from_value(
+        json!({ "age": 2, "extra": { "a": 1, "b": 2 }, "name": "Kefir", "toys": ["ball"] })
+            .into_value(),
+    );
+    let overlay = BufferedValue::from_value(
+        json!({ "age": null, "extra": { "b": [3] }, "name": "Echo", "toys": ["stick"] })
+            .into_value(),
+    );
+
+    let merged = base
+        .clone()
+        .merge(overlay.clone(), SequenceMerge::default());
+    assert_snapshot!(merged.prettyprint(), @r###"
+    Map {
+      "age": Null,
+      "extra": Map {
+        "a": Integer 1,
+        "b": Sequence [
+          Integer 3,
+        ],
+      },
+      "name": String "Echo",
+      "toys": Sequence [
+        String "stick",
+      ],
+    }
+    "###);
+    let config = deserialize::<Config, _, JsonError>(merged).unwrap();
+    assert_debug_snapshot!(config, @r###"
+    Config {
+        name: "Echo",
+        age: None,
+        toys: [
+            "stick",
+        ],
+    }
+    "###);
+
+    let merged = base.clone().merge(overlay, SequenceMerge::Concatenate);
+    let config = deserialize::<Config, _, JsonError>(merged).unwrap();
+    assert_debug_snapshot!(config, @r###"
+    Config {
+        name: "Echo",
+        age: None,
+        toys: [
+            "ball",
+            "stick",
+        ],
+    }
+    "###);
+
+    // a value that isn't a map replaces the whole map
+    let merged = base.merge(BufferedValue::Integer(2), SequenceMerge::Concatenate);
+    assert_snapshot!(merged.prettyprint(), @"Integer 2");
+}
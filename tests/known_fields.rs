@@ -0,0 +1,83 @@
+use deserr::{deserialize, errors::JsonError, Deserr, KnownFields};
+use serde_json::json;
+
+#[test]
+fn known_fields_are_the_keys_of_the_fields() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(rename_all = camelCase, known_fields)]
+    struct Doggo {
+        name: String,
+        #[deserr(rename = "years")]
+        age: u8,
+        good_boy: bool,
+        #[deserr(alias = "friends")]
+        best_friends: Vec<String>,
+        #[deserr(skip)]
+        cache: Option<u64>,
+    }
+
+    assert_eq!(
+        Doggo::known_fields(),
+        ["name", "years", "goodBoy", "bestFriends", "friends"]
+    );
+}
+
+#[test]
+fn known_fields_are_the_keys_denied_by_deny_unknown_fields() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields, known_fields)]
+    struct Wrapper<T> {
+        #[deserr(rename = "inner")]
+        value: T,
+        tag: Option<String>,
+    }
+
+    assert_eq!(Wrapper::<u8>::known_fields(), ["inner", "tag"]);
+
+    let error =
+        deserialize::<Wrapper<u8>, _, JsonError>(json!({ "inner": 1, "doggo": true })).unwrap_err();
+    insta::assert_snapshot!(error, @"Unknown field `doggo`: expected one of `inner`, `tag`");
+}
+
+#[test]
+fn known_fields_include_the_keys_of_the_flattened_field() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(known_fields)]
+    struct Pagination {
+        offset: usize,
+        limit: usize,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(known_fields)]
+    struct Search {
+        query: String,
+        #[deserr(flatten)]
+        pagination: Pagination,
+    }
+
+    assert_eq!(Search::known_fields(), ["query", "offset", "limit"]);
+}
+
+#[test]
+fn known_fields_dont_collide_with_an_inherent_function() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(known_fields)]
+    struct Search {
+        query: String,
+    }
+
+    impl Search {
+        fn known_fields() -> &'static str {
+            "the fields of a search"
+        }
+    }
+
+    assert_eq!(Search::known_fields(), "the fields of a search");
+    assert_eq!(<Search as KnownFields>::known_fields(), ["query"]);
+}
@@ -0,0 +1,67 @@
+use deserr::{deserialize_with_limits, errors::JsonError, Deserr, Limits};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::{json, Value};
+
+/// Return a value made of `depth` nested sequences
+fn nested(depth: usize) -> Value {
+    (0..depth).fold(json!(null), |value, _| json!([value]))
+}
+
+#[test]
+fn max_depth() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Config {
+        name: String,
+        #[deserr(default)]
+        tree: Option<Value>,
+    }
+
+    let limits = Limits { max_depth: 4 };
+    let config = deserialize_with_limits::<Config, _, JsonError>(
+        json!({ "name": "doggo", "tree": nested(3) }),
+        limits,
+    )
+    .unwrap();
+    assert_debug_snapshot!(config, @r###"
+    Config {
+        name: "doggo",
+        tree: Some(
+            Array [
+                Array [
+                    Array [
+                        Null,
+                    ],
+                ],
+            ],
+        ),
+    }
+    "###);
+
+    let error = deserialize_with_limits::<Config, _, JsonError>(
+        json!({ "name": "doggo", "tree": nested(4) }),
+        limits,
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value: the maximum nesting depth of 4 was exceeded");
+
+    // the maps count as well
+    let error = deserialize_with_limits::<Config, _, JsonError>(
+        json!({ "name": "doggo", "tree": { "a": { "b": { "c": {} } } } }),
+        limits,
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value: the maximum nesting depth of 4 was exceeded");
+}
+
+#[test]
+fn default_max_depth() {
+    assert_eq!(Limits::default().max_depth, 128);
+
+    let value = deserialize_with_limits::<Value, _, JsonError>(nested(128), Limits::default());
+    assert!(value.is_ok());
+
+    let error = deserialize_with_limits::<Value, _, JsonError>(nested(1_000), Limits::default())
+        .unwrap_err();
+    assert_snapshot!(error, @"Invalid value: the maximum nesting depth of 128 was exceeded");
+}
@@ -0,0 +1,112 @@
+use deserr::{
+    deserialize,
+    errors::{localized::ErrorMessages, JsonError, LocalizedError, QueryParamError},
+    Deserr, ErrorKindTag, IntoValue, Value, ValueKind, ValuePointerRef,
+};
+use insta::assert_snapshot;
+use serde_json::json;
+
+/// The messages in Esperanto, that keep the English messages of the query parameters
+/// for the unknown values.
+struct Esperanto;
+
+impl ErrorMessages for Esperanto {
+    fn incorrect_value_kind<V: IntoValue>(
+        _actual: Value<V>,
+        _accepted: &[ValueKind],
+        location: ValuePointerRef,
+    ) -> String {
+        format!("Nevalida tipo de valoro ĉe `{}`", location.to_owned())
+    }
+
+    fn missing_field(field: &str, _location: ValuePointerRef) -> String {
+        format!("Mankas la kampo `{field}`")
+    }
+
+    fn unknown_key(key: &str, accepted: &[&str], _location: ValuePointerRef) -> String {
+        format!(
+            "Nekonata kampo `{key}`, atendis unu el {}",
+            accepted.join(", ")
+        )
+    }
+
+    fn unknown_value(value: &str, accepted: &[&str], location: ValuePointerRef) -> String {
+        QueryParamError::unknown_value(value, accepted, location)
+    }
+
+    fn unexpected(msg: &str, _location: ValuePointerRef) -> String {
+        format!("Nevalida valoro: {msg}")
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Search {
+    q: String,
+    mode: Mode,
+    #[deserr(default, try_from(&String) = core::str::FromStr::from_str -> core::num::ParseIntError)]
+    limit: usize,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+enum Mode {
+    Fast,
+    Exact,
+}
+
+#[test]
+fn localized_messages() {
+    let error =
+        deserialize::<Search, _, LocalizedError<Esperanto>>(json!({ "mode": "Fast" })).unwrap_err();
+    assert_snapshot!(error, @"Mankas la kampo `q`");
+    assert_eq!(error.kind(), ErrorKindTag::MissingField);
+
+    let error =
+        deserialize::<Search, _, LocalizedError<Esperanto>>(json!({ "q": 2, "mode": "Fast" }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Nevalida tipo de valoro ĉe `.q`");
+    assert_snapshot!(error.location(), @".q");
+
+    let error = deserialize::<Search, _, LocalizedError<Esperanto>>(
+        json!({ "q": "", "mode": "Fast", "doggo": true }),
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Nekonata kampo `doggo`, atendis unu el q, mode, limit");
+
+    let error =
+        deserialize::<Search, _, LocalizedError<Esperanto>>(json!({ "q": "", "mode": "Slow" }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Unknown value `Slow` for parameter `mode`: expected one of `Fast`, `Exact`");
+
+    let error = deserialize::<Search, _, LocalizedError<Esperanto>>(
+        json!({ "q": "", "mode": "Fast", "limit": "ten" }),
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Nevalida valoro: invalid digit found in string");
+    assert_eq!(error.kind(), ErrorKindTag::Unexpected);
+}
+
+#[test]
+fn builtin_messages() {
+    // The built-in errors are sets of messages too, and give the same messages when localized
+    let values = [
+        json!({ "mode": "Fast" }),
+        json!({ "q": 2, "mode": "Fast" }),
+        json!({ "q": "", "mode": "Fast", "doggo": true }),
+        json!({ "q": "", "mode": "Slow" }),
+        json!({ "q": "", "mode": "Fast", "limit": "ten" }),
+    ];
+    for value in values {
+        let error = deserialize::<Search, _, JsonError>(value.clone()).unwrap_err();
+        let localized =
+            deserialize::<Search, _, LocalizedError<JsonError>>(value.clone()).unwrap_err();
+        assert_eq!(error.to_string(), localized.to_string());
+
+        let error = deserialize::<Search, _, QueryParamError>(value.clone()).unwrap_err();
+        let localized =
+            deserialize::<Search, _, LocalizedError<QueryParamError>>(value).unwrap_err();
+        assert_eq!(error.to_string(), localized.to_string());
+    }
+}
@@ -148,7 +148,7 @@ fn non_zero_positive_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u8": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_u8`: a non-zero integer value lower than `255` was expected, but found a zero",
+        "Invalid value at `.non_zero_u8`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u8": -1 })).unwrap_err();
@@ -169,7 +169,7 @@ fn non_zero_positive_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u16": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_u16`: a non-zero integer value lower than `65535` was expected, but found a zero",
+        "Invalid value at `.non_zero_u16`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u16": -1 })).unwrap_err();
@@ -190,7 +190,7 @@ fn non_zero_positive_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u32": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_u32`: a non-zero integer value lower than `4294967295` was expected, but found a zero",
+        "Invalid value at `.non_zero_u32`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u32": -1 })).unwrap_err();
@@ -203,7 +203,7 @@ fn non_zero_positive_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u64": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_u64`: a non-zero integer value lower than `18446744073709551615` was expected, but found a zero",
+        "Invalid value at `.non_zero_u64`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
 
@@ -218,7 +218,7 @@ fn non_zero_positive_integer() {
         deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_usize": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_usize`: a non-zero integer value lower than `18446744073709551615` was expected, but found a zero",
+        "Invalid value at `.non_zero_usize`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
 
@@ -230,6 +230,13 @@ fn non_zero_positive_integer() {
     )
     "###);
 
+    let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_u128": 0 })).unwrap_err();
+    insta::assert_debug_snapshot!(ret, @r###"
+    JsonError(
+        "Invalid value at `.non_zero_u128`: value: `0` is invalid, the value must be non-zero",
+    )
+    "###);
+
     // we can't test the u64 and usize because we have no way to create a value that overflow since it's `serde_json` that doesn't support u128 yet.
 
     // let ret = deserr::deserialize::<Test, _, DefaultError>(json!({ "non_zero_u64": u64::MAX as u128 + 1 }))
@@ -327,7 +334,7 @@ fn non_zero_negative_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_i8": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_i8`: a non-zero integer value higher than `-128` was expected, but found a zero",
+        "Invalid value at `.non_zero_i8`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
 
@@ -348,7 +355,7 @@ fn non_zero_negative_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_i16": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_i16`: a non-zero integer value higher than `-32768` was expected, but found a zero",
+        "Invalid value at `.non_zero_i16`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
 
@@ -362,7 +369,7 @@ fn non_zero_negative_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_i32": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_i32`: a non-zero integer value higher than `-2147483648` was expected, but found a zero",
+        "Invalid value at `.non_zero_i32`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "i32": i32::MIN as i64 - 1 }))
@@ -375,14 +382,108 @@ fn non_zero_negative_integer() {
     let ret = deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_i64": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_i64`: a non-zero integer value higher than `-9223372036854775808` was expected, but found a zero",
+        "Invalid value at `.non_zero_i64`: value: `0` is invalid, the value must be non-zero",
     )
     "###);
     let ret =
         deserr::deserialize::<Test, _, JsonError>(json!({ "non_zero_isize": 0 })).unwrap_err();
     insta::assert_debug_snapshot!(ret, @r###"
     JsonError(
-        "Invalid value at `.non_zero_isize`: a non-zero integer value higher than `-9223372036854775808` was expected, but found a zero",
+        "Invalid value at `.non_zero_isize`: value: `0` is invalid, the value must be non-zero",
+    )
+    "###);
+}
+
+#[test]
+fn integer_boundaries() {
+    fn parse<T: deserr::Deserr<JsonError>>(number: &str) -> Result<T, JsonError> {
+        let value: serde_json::Value = serde_json::from_str(number).unwrap();
+        deserr::deserialize::<T, _, JsonError>(value)
+    }
+
+    // the whole range of `u64` and `i64` is lossless
+    insta::assert_debug_snapshot!(parse::<u64>("18446744073709551615"), @r###"
+    Ok(
+        18446744073709551615,
+    )
+    "###);
+    insta::assert_debug_snapshot!(parse::<u128>("18446744073709551615"), @r###"
+    Ok(
+        18446744073709551615,
+    )
+    "###);
+    insta::assert_debug_snapshot!(parse::<i64>("-9223372036854775808"), @r###"
+    Ok(
+        -9223372036854775808,
+    )
+    "###);
+    insta::assert_debug_snapshot!(parse::<i128>("-9223372036854775808"), @r###"
+    Ok(
+        -9223372036854775808,
+    )
+    "###);
+    insta::assert_debug_snapshot!(parse::<i64>("18446744073709551615"), @r###"
+    Err(
+        JsonError(
+            "Invalid value: value: `18446744073709551615` is too large to be deserialized, maximum value authorized is `9223372036854775807`",
+        ),
+    )
+    "###);
+
+    // `serde_json` parses the numbers outside of this range as floats, they are never truncated
+    insta::assert_debug_snapshot!(parse::<u128>("18446744073709551616"), @r###"
+    Err(
+        JsonError(
+            "Invalid value type: expected a positive integer, but found a number: `1.8446744073709552e+19`",
+        ),
+    )
+    "###);
+    insta::assert_debug_snapshot!(parse::<i128>("-9223372036854775809"), @r###"
+    Err(
+        JsonError(
+            "Invalid value type: expected an integer, but found a number: `-9.223372036854776e+18`",
+        ),
+    )
+    "###);
+    insta::assert_debug_snapshot!(parse::<f64>("18446744073709551616"), @r###"
+    Ok(
+        1.8446744073709552e19,
+    )
+    "###);
+}
+
+#[test]
+fn float_range() {
+    let ret = deserr::deserialize::<Vec<f32>, _, JsonError>(json!([1, -2, 0.5, 3.4e38, -3.4e38]))
+        .unwrap();
+    insta::assert_debug_snapshot!(ret, @r###"
+    [
+        1.0,
+        -2.0,
+        0.5,
+        3.4e38,
+        -3.4e38,
+    ]
+    "###);
+
+    // a value that overflows `f32` is refused instead of becoming infinite
+    let ret = deserr::deserialize::<Vec<f32>, _, JsonError>(json!([1e39])).unwrap_err();
+    insta::assert_debug_snapshot!(ret, @r###"
+    JsonError(
+        "Invalid value at `[0]`: value: `1e39` is too large to be deserialized, maximum value authorized is `3.4028235e38`",
+    )
+    "###);
+    let ret = deserr::deserialize::<Vec<f32>, _, JsonError>(json!([0, -1e39])).unwrap_err();
+    insta::assert_debug_snapshot!(ret, @r###"
+    JsonError(
+        "Invalid value at `[1]`: value: `-1e39` is too small to be deserialized, minimum value authorized is `-3.4028235e38`",
     )
     "###);
+
+    // the values that are too small to be represented are rounded to zero
+    let ret = deserr::deserialize::<f32, _, JsonError>(json!(1e-50)).unwrap();
+    insta::assert_debug_snapshot!(ret, @"0.0");
+
+    let ret = deserr::deserialize::<f64, _, JsonError>(json!(1e39)).unwrap();
+    insta::assert_debug_snapshot!(ret, @"1e39");
 }
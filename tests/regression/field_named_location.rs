@@ -0,0 +1,20 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use serde_json::json;
+
+// The name of a field must not collide with the local variables of the derived implementation
+#[allow(dead_code)]
+#[derive(Debug, Deserr)]
+pub struct Event {
+    pub location: String,
+    #[deserr(alias = "place")]
+    pub value: String,
+}
+
+#[test]
+fn field_named_location() {
+    let event =
+        deserialize::<Event, _, JsonError>(json!({ "location": "Paris", "place": "Louvre" }))
+            .unwrap();
+    assert_eq!(event.location, "Paris");
+    assert_eq!(event.value, "Louvre");
+}
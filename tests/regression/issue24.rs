@@ -23,6 +23,7 @@ impl MergeWithError<MyError> for MyError {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Deserr)]
 #[deserr(error = MyError)]
 pub struct Point {
@@ -1,5 +1,5 @@
 use deserr::{deserialize, errors::JsonError, Deserr};
-use insta::assert_debug_snapshot;
+use insta::{assert_debug_snapshot, assert_snapshot};
 use serde_json::json;
 
 #[test]
@@ -158,6 +158,42 @@ fn tuple() {
         ),
     }
     "###);
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Colors {
+        rgba: (u8, u8, u8, f32),
+        twelve: (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, String),
+    }
+    let data = deserialize::<Colors, _, JsonError>(json!({
+       "rgba": [255, 0, 128, 0.5],
+       "twelve": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, "twelve"],
+    }))
+    .unwrap();
+
+    assert_debug_snapshot!(data.rgba, @r###"
+    (
+        255,
+        0,
+        128,
+        0.5,
+    )
+    "###);
+    assert_eq!(data.twelve.11, "twelve");
+
+    let error = deserialize::<Colors, _, JsonError>(json!({
+       "rgba": [255, 0, 128],
+       "twelve": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, "twelve"],
+    }))
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.rgba`: expected a sequence of 4 elements but instead found a sequence of 3 elements");
+
+    let error = deserialize::<Colors, _, JsonError>(json!({
+       "rgba": [255, 0, 256, 0.5],
+       "twelve": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, "twelve"],
+    }))
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.rgba[2]`: value: `256` is too large to be deserialized, maximum value authorized is `255`");
 }
 
 #[test]
@@ -234,3 +270,598 @@ fn array() {
     }
     "###);
 }
+
+#[test]
+fn array_errors() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Key {
+        key: [u8; 4],
+    }
+
+    let error = deserialize::<Key, _, JsonError>(json!({ "key": [1, 2, 3] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.key`: expected a sequence of 4 elements but instead found a sequence of 3 elements");
+
+    let error = deserialize::<Key, _, JsonError>(json!({ "key": [1, 2, 3, -4] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.key[3]`: expected a positive integer, but found a negative integer: `-4`");
+}
+
+#[test]
+fn array_drops_the_deserialized_elements_on_error() {
+    use deserr::{DeserializeError, IntoValue, Value, ValuePointerRef};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    struct Counted(u8);
+
+    impl<E: DeserializeError> Deserr<E> for Counted {
+        fn deserialize_from_value<V: IntoValue>(
+            value: Value<V>,
+            location: ValuePointerRef,
+        ) -> Result<Self, E> {
+            let n = u8::deserialize_from_value(value, location)?;
+            LIVE.fetch_add(1, Ordering::SeqCst);
+            Ok(Counted(n))
+        }
+    }
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            LIVE.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    let array = deserialize::<[Counted; 3], _, JsonError>(json!([1, 2, 3])).unwrap();
+    assert_eq!(LIVE.load(Ordering::SeqCst), 3);
+    drop(array);
+    assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+
+    deserialize::<[Counted; 3], _, JsonError>(json!([1, 2, "three"])).unwrap_err();
+    assert_eq!(LIVE.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn sorted_collections() {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Config {
+        ports: BTreeMap<u16, String>,
+        tags: BTreeSet<String>,
+    }
+
+    let data = deserialize::<Config, _, JsonError>(json!({
+        "ports": { "8080": "http", "443": "https" },
+        "tags": ["prod", "eu", "api"],
+    }))
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Config {
+        ports: {
+            443: "https",
+            8080: "http",
+        },
+        tags: {
+            "api",
+            "eu",
+            "prod",
+        },
+    }
+    "###);
+
+    let error = deserialize::<Config, _, JsonError>(json!({
+        "ports": { "8080": "http", "https": "443" },
+        "tags": [],
+    }))
+    .unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value at `.ports.https`: the key "https" could not be deserialized into the key type `u16`"###);
+
+    let error = deserialize::<Config, _, JsonError>(json!({
+        "ports": {},
+        "tags": ["prod", "eu", "prod"],
+    }))
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.tags[2]`: the sequence contains this element more than once");
+
+    let error =
+        deserialize::<std::collections::HashSet<u8>, _, JsonError>(json!([1, 2, 1])).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `[2]`: the sequence contains this element more than once");
+}
+
+#[test]
+fn sequential_collections() {
+    use std::collections::{LinkedList, VecDeque};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct History {
+        queue: VecDeque<u8>,
+        log: LinkedList<String>,
+    }
+
+    let data = deserialize::<History, _, JsonError>(json!({
+        "log": ["opened", "closed"],
+        "queue": [3, 1, 2],
+    }))
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    History {
+        queue: [
+            3,
+            1,
+            2,
+        ],
+        log: [
+            "opened",
+            "closed",
+        ],
+    }
+    "###);
+
+    let error = deserialize::<History, _, JsonError>(json!({
+        "log": [],
+        "queue": [3, -1],
+    }))
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.queue[1]`: expected a positive integer, but found a negative integer: `-1`");
+
+    let value = json!({ "log": ["opened", 2], "queue": [] });
+    let error = deserr::deserialize_ref::<History, _, JsonError>(&value).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.log[1]`: expected a string, but found a positive integer: `2`");
+}
+
+#[test]
+fn map_keys() {
+    use std::collections::HashMap;
+
+    let data =
+        deserialize::<HashMap<u32, bool>, _, JsonError>(json!({ "1": true, "2": false })).unwrap();
+    assert_eq!(data, HashMap::from([(1, true), (2, false)]));
+
+    let errors = deserr::deserialize_accumulate::<HashMap<u32, bool>, _, JsonError>(
+        json!({ "-3": true, "1": true, "two": false }),
+    )
+    .unwrap_err();
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_debug_snapshot!(errors, @r###"
+    [
+        "Invalid value at `.-3`: the key \"-3\" could not be deserialized into the key type `u32`",
+        "Invalid value at `.two`: the key \"two\" could not be deserialized into the key type `u32`",
+    ]
+    "###);
+}
+
+#[test]
+fn duration() {
+    use std::time::Duration;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        timeout: Duration,
+    }
+
+    let timeouts = [
+        json!(3),
+        json!("500ms"),
+        json!("2s"),
+        json!("1m"),
+        json!("12us"),
+    ]
+    .into_iter()
+    .map(|timeout| {
+        deserialize::<Struct, _, JsonError>(json!({ "timeout": timeout }))
+            .unwrap()
+            .timeout
+    })
+    .collect::<Vec<_>>();
+
+    assert_eq!(
+        timeouts,
+        [
+            Duration::from_secs(3),
+            Duration::from_millis(500),
+            Duration::from_secs(2),
+            Duration::from_secs(60),
+            Duration::from_micros(12),
+        ]
+    );
+
+    let error = deserialize::<Struct, _, JsonError>(json!({ "timeout": "2 days" })).unwrap_err();
+    insta::assert_snapshot!(error, @"Invalid value at `.timeout`: expected an integer number of seconds or a string such as `500ms`, `2s`, or `1m` with one of the units `ns`, `us`, `ms`, `s`, `m`, or `h`, but found `2 days`");
+
+    let error = deserialize::<Struct, _, JsonError>(json!({ "timeout": -2 })).unwrap_err();
+    insta::assert_snapshot!(error, @"Invalid value at `.timeout`: expected an integer number of seconds or a string such as `500ms`, `2s`, or `1m` with one of the units `ns`, `us`, `ms`, `s`, `m`, or `h`, but found a value of kind negative integer");
+}
+
+#[test]
+fn network_addresses() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        ip: IpAddr,
+        ipv4: Ipv4Addr,
+        ipv6: Ipv6Addr,
+        socket: SocketAddr,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({
+       "ip": "::1",
+       "ipv4": "127.0.0.1",
+       "ipv6": "2001:db8::8a2e:370:7334",
+       "socket": "0.0.0.0:7700",
+    }))
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        ip: ::1,
+        ipv4: 127.0.0.1,
+        ipv6: 2001:db8::8a2e:370:7334,
+        socket: 0.0.0.0:7700,
+    }
+    "###);
+
+    let error = deserialize::<Struct, _, JsonError>(json!({
+       "ip": "::1",
+       "ipv4": "127.0.0.256",
+       "ipv6": "::1",
+       "socket": "0.0.0.0:7700",
+    }))
+    .unwrap_err();
+
+    insta::assert_snapshot!(error, @"Invalid value at `.ipv4`: invalid IPv4 address syntax: `127.0.0.256`");
+}
+
+#[test]
+fn smart_pointers() {
+    use std::{rc::Rc, sync::Arc};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Inner {
+        name: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        boxed: Box<Inner>,
+        rc: Rc<Inner>,
+        arc: Arc<Vec<u8>>,
+        box_str: Box<str>,
+        rc_str: Rc<str>,
+        arc_str: Arc<str>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({
+       "boxed": { "name": "Kefir" },
+       "rc": { "name": "Echo" },
+       "arc": [1, 2],
+       "box_str": "doggo",
+       "rc_str": "catto",
+       "arc_str": "birdo",
+    }))
+    .unwrap();
+
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        boxed: Inner {
+            name: "Kefir",
+        },
+        rc: Inner {
+            name: "Echo",
+        },
+        arc: [
+            1,
+            2,
+        ],
+        box_str: "doggo",
+        rc_str: "catto",
+        arc_str: "birdo",
+    }
+    "###);
+
+    let error = deserialize::<Struct, _, JsonError>(json!({
+       "boxed": { "name": "Kefir" },
+       "rc": { "name": "Echo" },
+       "arc": [1, 2],
+       "box_str": "doggo",
+       "rc_str": "catto",
+       "arc_str": 3,
+    }))
+    .unwrap_err();
+
+    insta::assert_snapshot!(error, @"Invalid value type at `.arc_str`: expected a string, but found a positive integer: `3`");
+}
+
+#[test]
+fn cow_str() {
+    use std::borrow::Cow;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        name: Cow<'static, str>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(json!({ "name": "Kefir" })).unwrap();
+    assert!(matches!(data.name, Cow::Owned(ref name) if name == "Kefir"));
+
+    let error = deserialize::<Struct, _, JsonError>(json!({ "name": ["Kefir"] })).unwrap_err();
+    insta::assert_snapshot!(error, @r###"Invalid value type at `.name`: expected a string, but found an array: `["Kefir"]`"###);
+}
+
+#[test]
+fn paths_and_os_strings() {
+    use std::{ffi::OsString, path::PathBuf};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        path: PathBuf,
+        name: OsString,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "path": "doggos/kefir.json", "name": "Kefir" }),
+    )
+    .unwrap();
+    assert_eq!(data.path, PathBuf::from("doggos").join("kefir.json"));
+    assert!(data.path.is_relative());
+    assert_eq!(data.name, "Kefir");
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "path": 42, "name": "Kefir" })).unwrap_err();
+    insta::assert_snapshot!(error, @"Invalid value type at `.path`: expected a string, but found a positive integer: `42`");
+}
+
+#[test]
+fn fallible_fields() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(error = JsonError)]
+    struct Struct {
+        name: Result<String, String>,
+        age: Result<u8, String>,
+        toys: Result<Vec<String>, String>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "name": "Kefir", "age": 300, "toys": ["ball", 42] }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        name: Ok(
+            "Kefir",
+        ),
+        age: Err(
+            "Invalid value at `.age`: value: `300` is too large to be deserialized, maximum value authorized is `255`",
+        ),
+        toys: Err(
+            "Invalid value type at `.toys[1]`: expected a string, but found a positive integer: `42`",
+        ),
+    }
+    "###);
+
+    // the value is only captured, a missing field is still an error
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "name": "Kefir", "toys": [] })).unwrap_err();
+    assert_snapshot!(error, @"Missing field `age`");
+}
+
+#[test]
+fn from_str_wrapper() {
+    use deserr::{deserialize_ref, FromStrWrapper};
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq)]
+    struct Version(u32, u32);
+
+    impl FromStr for Version {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (major, minor) = s
+                .split_once('.')
+                .ok_or_else(|| format!("`{s}` is not a `major.minor` version"))?;
+            let parse = |n: &str| n.parse().map_err(|_| format!("`{n}` is not a number"));
+            Ok(Version(parse(major)?, parse(minor)?))
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        version: FromStrWrapper<Version>,
+        versions: Vec<FromStrWrapper<Version>>,
+    }
+
+    let value = json!({ "version": "1.2", "versions": ["0.1", "3.14"] });
+    let data = deserialize::<Struct, _, JsonError>(value.clone()).unwrap();
+    assert_eq!(*data.version, Version(1, 2));
+    assert_eq!(data.versions[1].0, Version(3, 14));
+    let data = deserialize_ref::<Struct, _, JsonError>(&value).unwrap();
+    assert_eq!(data.version.into_inner(), Version(1, 2));
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "version": "1", "versions": [] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.version`: `1` is not a `major.minor` version");
+
+    let error =
+        deserialize_ref::<Struct, _, JsonError>(&json!({ "version": "1.0", "versions": ["1.x"] }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.versions[0]`: `x` is not a number");
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "version": 1.2, "versions": [] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.version`: expected a string, but found a number: `1.2`");
+}
+
+#[test]
+fn ranges() {
+    use std::ops::{Range, RangeInclusive};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Struct {
+        price: Range<u32>,
+        age: RangeInclusive<i8>,
+    }
+
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "price": { "start": 10, "end": 20 }, "age": [-1, 5] }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        price: 10..20,
+        age: -1..=5,
+    }
+    "###);
+
+    // the bounds aren't compared
+    let data = deserialize::<Struct, _, JsonError>(
+        json!({ "price": [20, 10], "age": { "end": 1, "start": 2 } }),
+    )
+    .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Struct {
+        price: 20..10,
+        age: 2..=1,
+    }
+    "###);
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "price": { "end": 20 }, "age": [1, 2] }))
+            .unwrap_err();
+    assert_snapshot!(error, @"Missing field `start` inside `.price`");
+
+    let error = deserialize::<Struct, _, JsonError>(
+        json!({ "price": { "start": 10, "end": -20 }, "age": [1, 2] }),
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.price.end`: expected a positive integer, but found a negative integer: `-20`");
+
+    let error = deserialize::<Struct, _, JsonError>(
+        json!({ "price": { "start": 10, "end": 20, "step": 2 }, "age": [1, 2] }),
+    )
+    .unwrap_err();
+    assert_snapshot!(error, @"Unknown field `step` inside `.price`: expected one of `start`, `end`");
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "price": [10], "age": [1, 2] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value at `.price`: expected a sequence of 2 elements but instead found a sequence of 1 element");
+
+    let error =
+        deserialize::<Struct, _, JsonError>(json!({ "price": 10, "age": [1, 2] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.price`: expected an array or an object, but found a positive integer: `10`");
+}
+
+#[test]
+fn incorrect_value_kind_type_name() {
+    use std::ops::ControlFlow;
+
+    use deserr::{
+        DeserializeError, ErrorKind, IntoValue, MergeWithError, Value, ValueKind, ValuePointer,
+        ValuePointerRef,
+    };
+
+    /// Keep the name of the type that couldn't be deserialized.
+    #[derive(Debug)]
+    struct TypeNameError(ValuePointer, Option<String>);
+
+    impl MergeWithError<TypeNameError> for TypeNameError {
+        fn merge(
+            _self_: Option<Self>,
+            other: TypeNameError,
+            _merge_location: ValuePointerRef,
+        ) -> ControlFlow<Self, Self> {
+            ControlFlow::Break(other)
+        }
+    }
+
+    impl DeserializeError for TypeNameError {
+        fn error<V: IntoValue>(
+            _self_: Option<Self>,
+            _error: ErrorKind<V>,
+            location: ValuePointerRef,
+        ) -> ControlFlow<Self, Self> {
+            ControlFlow::Break(TypeNameError(location.to_owned(), None))
+        }
+
+        fn incorrect_value_kind_error<V: IntoValue>(
+            _self_: Option<Self>,
+            _actual: Value<V>,
+            _accepted: &[ValueKind],
+            type_name: &str,
+            location: ValuePointerRef,
+        ) -> ControlFlow<Self, Self> {
+            ControlFlow::Break(TypeNameError(
+                location.to_owned(),
+                Some(type_name.to_string()),
+            ))
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    #[deserr(error = TypeNameError)]
+    struct Struct {
+        age: u32,
+        names: Vec<String>,
+    }
+
+    let error =
+        deserialize::<Struct, _, TypeNameError>(json!({ "age": "2", "names": [] })).unwrap_err();
+    assert_eq!(error.0, ValuePointerRef::Origin.push_key("age").to_owned());
+    assert_eq!(error.1.as_deref(), Some("u32"));
+
+    let error =
+        deserialize::<Struct, _, TypeNameError>(json!({ "age": 2, "names": "Kefir" })).unwrap_err();
+    assert_eq!(
+        error.1.as_deref(),
+        Some("alloc::vec::Vec<alloc::string::String>")
+    );
+
+    let error = deserialize::<Struct, _, TypeNameError>(json!([2])).unwrap_err();
+    assert!(error.1.unwrap().ends_with("::Struct"));
+}
+
+#[test]
+fn wrapping_and_saturating() {
+    use std::num::{Saturating, Wrapping};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserr)]
+    struct Counters {
+        wrapping: Wrapping<u32>,
+        saturating: Saturating<i8>,
+    }
+
+    let data =
+        deserialize::<Counters, _, JsonError>(json!({ "wrapping": 12, "saturating": -1000 }))
+            .unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Counters {
+        wrapping: 12,
+        saturating: -128,
+    }
+    "###);
+
+    // A `Wrapping` integer doesn't wrap the values out of its range
+    let error = deserialize::<Counters, _, JsonError>(json!({ "wrapping": -1, "saturating": 0 }))
+        .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.wrapping`: expected a positive integer, but found a negative integer: `-1`");
+
+    let error = deserialize::<Counters, _, JsonError>(json!({ "wrapping": 1, "saturating": 1.5 }))
+        .unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.saturating`: expected an integer, but found a number: `1.5`");
+}
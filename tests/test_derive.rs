@@ -854,6 +854,28 @@ fn test_de() {
     );
 
     assert_ok_matches::<Hello, DefaultError>("true", Hello::A);
+    assert_ok_matches::<Hello2, DefaultError>("false", Hello2::B);
+    assert_ok_matches::<Hello3, DefaultError>(r#""B""#, Hello3::B);
+    assert_error_matches::<Hello3, DefaultError>(
+        r#""C""#,
+        DefaultError {
+            location: ValuePointerRef::Origin.to_owned(),
+            content: DefaultErrorContent::Unexpected("Hello3 from error".to_string()),
+        },
+    );
+
+    assert_ok_matches::<ContainsHello, DefaultError>(
+        r#"{ "_x": true }"#,
+        ContainsHello { _x: Hello::A },
+    );
+    assert_ok_matches::<ContainsHello2, DefaultError>(
+        r#"{ "_x": false }"#,
+        ContainsHello2 { _x: Hello::B },
+    );
+    assert_ok_matches::<ContainsHello3, DefaultError>(
+        r#"{ "_x": true }"#,
+        ContainsHello3 { _x: Hello::A },
+    );
 
     assert_error_matches::<Validated, DefaultError>(
         r#"{
@@ -866,6 +888,24 @@ fn test_de() {
             content: DefaultErrorContent::Validation,
         },
     );
+    assert_error_matches::<Validated2, DefaultError>(
+        r#"{ "x": 2, "y": 1 }"#,
+        DefaultError {
+            location: ValuePointerRef::Origin.to_owned(),
+            content: DefaultErrorContent::Validation,
+        },
+    );
+
+    assert_ok_matches::<Generic2<u8>, DefaultError>(
+        r#"{ "some_field": 2 }"#,
+        Generic2 {
+            some_field: Some(2),
+        },
+    );
+    assert_ok_matches::<FieldConditions, DefaultError>(
+        r#"{  }"#,
+        FieldConditions { some_field: None },
+    );
 
     assert_ok_matches::<FieldMap, DefaultError>(
         r#"{ "some_field": null }"#,
@@ -0,0 +1,67 @@
+use deserr::{deserialize, errors::JsonError, Deserr};
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde_json::json;
+
+#[test]
+fn newtype_struct() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Meters(f64);
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Hike {
+        distance: Meters,
+    }
+
+    let data = deserialize::<Hike, _, JsonError>(json!({ "distance": 12.5 })).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Hike {
+        distance: Meters(
+            12.5,
+        ),
+    }
+    "###);
+
+    // the newtype is deserialized as its field, so the error is at the location of the struct
+    let error = deserialize::<Hike, _, JsonError>(json!({ "distance": [12.5] })).unwrap_err();
+    assert_snapshot!(error, @"Invalid value type at `.distance`: expected a number, but found an array: `[12.5]`");
+}
+
+#[test]
+fn tuple_struct() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Point(f64, f64);
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Segment(Point, Point, #[deserr(skip)] Option<String>);
+
+    let data = deserialize::<Segment, _, JsonError>(json!([[0, 1.5], [2, -3]])).unwrap();
+    assert_debug_snapshot!(data, @r###"
+    Segment(
+        Point(
+            0.0,
+            1.5,
+        ),
+        Point(
+            2.0,
+            -3.0,
+        ),
+        None,
+    )
+    "###);
+
+    let error = deserialize::<Point, _, JsonError>(json!([0])).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: expected a sequence of 2 elements but instead found a sequence of 1 element");
+
+    let error = deserialize::<Point, _, JsonError>(json!([0, 1, 2])).unwrap_err();
+    assert_snapshot!(error, @"Invalid value: expected a sequence of 2 elements but instead found a sequence of 3 elements");
+
+    let error = deserialize::<Segment, _, JsonError>(json!([[0, 1], [2, "3"]])).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type at `[1][1]`: expected a number, but found a string: `"3"`"###);
+
+    let error = deserialize::<Point, _, JsonError>(json!({ "0": 0, "1": 1 })).unwrap_err();
+    assert_snapshot!(error, @r###"Invalid value type: expected an array, but found an object: `{"0":0,"1":1}`"###);
+}
@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr, Default)]
+#[deserr(fill_from_default)]
+enum Enum {
+    #[default]
+    Hello,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(partial, tag = "type")]
+enum Enum {
+    EmptyVariant,
+    VariantWithSomething { data: usize },
+}
+
+fn main() {}
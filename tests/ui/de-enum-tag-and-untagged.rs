@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(tag = "type", untagged)]
+enum Enum {
+    EmptyVariant,
+    VariantWithSomething { data: usize },
+}
+
+fn main() {}
@@ -3,7 +3,7 @@ use deserr::Deserr;
 #[derive(Deserr)]
 #[deserr(error = deserr::Error, tag = "t")]
 enum Enum {
-    Variant(i32),
+    Variant(i32, i32),
 }
 
 fn main() {}
@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+struct User {
+    username: String,
+    #[deserr(default_from = username, map = |s: String| s.to_lowercase())]
+    display_name: String,
+}
+
+fn main() {}
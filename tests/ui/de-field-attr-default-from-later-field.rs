@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+struct User {
+    #[deserr(default_from = username)]
+    display_name: String,
+    username: String,
+}
+
+fn main() {}
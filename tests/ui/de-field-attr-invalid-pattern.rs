@@ -0,0 +1,9 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+struct Struct {
+    #[deserr(pattern = "^[a-z+$")]
+    hello: String,
+}
+
+fn main() {}
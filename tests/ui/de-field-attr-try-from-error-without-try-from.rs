@@ -0,0 +1,9 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+struct Struct {
+    #[deserr(try_from_error = some_function)]
+    hello: usize,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(rename_all = "Kebab-Case")]
+struct Struct {
+    x: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(case_insensitive)]
+struct Struct {
+    a: i32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(deny_unknown_fields)]
+struct Search {
+    query: String,
+    #[deserr(flatten)]
+    pagination: Pagination,
+}
+
+#[derive(Deserr)]
+struct Pagination {
+    offset: usize,
+    limit: usize,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(from_sequence)]
+struct Search {
+    query: String,
+    #[deserr(flatten)]
+    pagination: Pagination,
+}
+
+#[derive(Deserr)]
+struct Pagination {
+    offset: usize,
+    limit: usize,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(from_sequence, deny_unknown_fields)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
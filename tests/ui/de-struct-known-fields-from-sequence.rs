@@ -0,0 +1,9 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(from_sequence, known_fields)]
+struct Struct {
+    hello: String,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(error = deserr::Error, transparent, deny_unknown_fields)]
+struct Struct(i32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+#[deserr(error = deserr::Error, transparent)]
+struct Struct {
+    a: i32,
+    b: i32,
+}
+
+fn main() {}
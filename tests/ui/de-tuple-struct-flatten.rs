@@ -0,0 +1,12 @@
+use deserr::Deserr;
+
+#[derive(Deserr)]
+struct Search(String, #[deserr(flatten)] Pagination);
+
+#[derive(Deserr)]
+struct Pagination {
+    offset: usize,
+    limit: usize,
+}
+
+fn main() {}
@@ -1,7 +1,7 @@
 use deserr::Deserr;
 
 #[derive(Deserr)]
-#[deserr(error = deserr::Error)]
+#[deserr(partial)]
 struct TupleStruct(i32, i32);
 
 fn main() {}
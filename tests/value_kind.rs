@@ -0,0 +1,42 @@
+use deserr::ValueKind;
+use insta::assert_snapshot;
+
+#[test]
+fn describe_group() {
+    use ValueKind::*;
+
+    assert_snapshot!(ValueKind::describe_group(&[]), @"");
+    assert_snapshot!(ValueKind::describe_group(&[String]), @"string");
+    assert_snapshot!(ValueKind::describe_group(&[Map, String]), @"string or map");
+    assert_snapshot!(ValueKind::describe_group(&[Integer, NegativeInteger, Float]), @"number");
+    assert_snapshot!(ValueKind::describe_group(&[Float, Integer, NegativeInteger, String, Map]), @"number, string, or map");
+    assert_snapshot!(ValueKind::describe_group(&[NegativeInteger, Integer, Null]), @"null or integer");
+    assert_snapshot!(ValueKind::describe_group(&[Integer, Float, Integer]), @"positive integer or float");
+    assert_snapshot!(ValueKind::describe_group(&[Boolean, NegativeInteger, Sequence]), @"boolean, negative integer, or sequence");
+}
+
+#[test]
+fn is_numeric() {
+    let numeric = [
+        ValueKind::Null,
+        ValueKind::Boolean,
+        ValueKind::Integer,
+        ValueKind::NegativeInteger,
+        ValueKind::Float,
+        ValueKind::String,
+        ValueKind::Sequence,
+        ValueKind::Map,
+    ]
+    .into_iter()
+    .filter(ValueKind::is_numeric)
+    .collect::<Vec<_>>();
+
+    assert_eq!(
+        numeric,
+        [
+            ValueKind::Integer,
+            ValueKind::NegativeInteger,
+            ValueKind::Float
+        ]
+    );
+}
@@ -0,0 +1,83 @@
+use deserr::{deserialize_with_warnings, errors::JsonError, Deserr, Warning, WarningKind};
+use insta::assert_debug_snapshot;
+use serde_json::json;
+
+fn describe(warnings: Vec<Warning>) -> Vec<String> {
+    warnings.iter().map(Warning::to_string).collect()
+}
+
+#[test]
+fn unknown_keys_ignored() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Config {
+        name: String,
+        #[deserr(default)]
+        backups: Vec<Backup>,
+    }
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Backup {
+        path: String,
+    }
+
+    let (config, warnings) = deserialize_with_warnings::<Config, _, JsonError>(json!({
+        "backups": [{ "path": "/tmp", "pth": "/var" }],
+        "name": "doggo",
+        "nmae": "catto",
+    }));
+    assert!(config.is_ok());
+    assert_eq!(warnings[0].kind, WarningKind::UnknownKeyIgnored);
+    assert_debug_snapshot!(describe(warnings), @r###"
+    [
+        "The unknown field `.backups[0].pth` was ignored",
+        "The unknown field `.nmae` was ignored",
+    ]
+    "###);
+
+    // the keys claimed by a flattened field or denied aren't reported
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    struct Flattened {
+        name: String,
+        #[deserr(flatten)]
+        rest: std::collections::HashMap<String, String>,
+    }
+    let (_, warnings) = deserialize_with_warnings::<Flattened, _, JsonError>(
+        json!({ "name": "doggo", "nmae": "catto" }),
+    );
+    assert!(warnings.is_empty());
+
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(deny_unknown_fields)]
+    struct Denied {
+        name: String,
+    }
+    let (denied, warnings) = deserialize_with_warnings::<Denied, _, JsonError>(
+        json!({ "name": "doggo", "nmae": "catto" }),
+    );
+    assert!(denied.is_err());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn untagged_enums_only_report_the_warnings_of_the_matching_variant() {
+    #[allow(unused)]
+    #[derive(Debug, Deserr)]
+    #[deserr(untagged)]
+    enum Shape {
+        Circle { radius: f64 },
+        Square { side: f64 },
+    }
+
+    let (shape, warnings) =
+        deserialize_with_warnings::<Shape, _, JsonError>(json!({ "side": 2.0, "color": "red" }));
+    assert!(shape.is_ok());
+    assert_debug_snapshot!(describe(warnings), @r###"
+    [
+        "The unknown field `.color` was ignored",
+    ]
+    "###);
+}